@@ -0,0 +1,2048 @@
+//! A blocking client for the steamgriddb api.
+//!
+//! This module is only available with the `blocking` feature.
+//! It can be enabled together with the `async` feature.
+
+use std::{
+    io::{Read, Write},
+    path::Path,
+    sync::Arc,
+};
+
+use serde::de::DeserializeOwned;
+
+use crate::{
+    cache::{CacheLookup, ResponseCache},
+    download::{stream_download_blocking, DownloadOptions, DownloadedImage, Variant},
+    error::Error,
+    files::AtomicFile,
+    games::{
+        get_game_by_platform_id_url, get_game_by_steam_app_id_url, get_game_url,
+        get_gameinfo_by_game_id_url, GameInfo, GameLookup,
+    },
+    images::{
+        get_delete_images_url, get_image_by_id_url, get_images_by_game_id_url,
+        get_images_by_game_ids_url, get_images_by_platform_id_url, get_images_by_platform_ids_url,
+        get_vote_url, Image, InnerEmptyResponse, InnerImageResponse,
+        InnerImagesMultipleIdsResponse, InnerImagesSingleIdResponse, PagedImages,
+        PagedImagesTarget, Vote,
+    },
+    matcher::{TitleMatch, TitleMatcher},
+    query_parameters::{ImageKind, Platform, QueryType},
+    rate_limit::RateLimiter,
+    resolver::{
+        direct_target, found, official_asset, ArtworkPolicy, GameIdentity, ResolvedArtwork,
+        ResolvedArtworkSet,
+    },
+    response::{
+        parse_response, response_to_data, response_to_data_flat, response_to_result_all,
+        response_to_success, single_result_to_result_all, Response, SteamGridDbResult,
+    },
+    retry::RetryPolicy,
+    search::{get_search_url, InnerSearchResult, SearchResult},
+    steam::grid::{ArtworkKind, GridFolder, InstalledArtwork},
+    steam_static::{fallback_urls, CheckedSteamStaticUrls, StaticAsset, SteamStaticUrls},
+    transport::{
+        BlockingTransport, HttpRequest, HttpResponse, Method, ResponseHead, StreamingResponse,
+    },
+    upload::{get_upload_url, UploadForm, UploadType},
+    DEFAULT_BASE_URL,
+};
+
+/// This Client provides a blocking way to interact with the SteamGrid API.
+///
+/// It has the same methods as the async client, but each call blocks until the response is parsed.
+///
+/// By default the client calls the API using the blocking [reqwest](https://crates.io/crates/reqwest) client, and any other HTTP client can be used through a [`BlockingTransport`].
+/// The results are parsed using the [serde](https://crates.io/crates/serde) crate.
+///
+/// Clones of the client share the same transport.
+///
+/// ### Examples
+///
+/// Searching for a game and getting images for it:
+/// ```no_run
+/// use steamgriddb_api::blocking::Client;
+/// use steamgriddb_api::query_parameters::QueryType::Grid;
+///
+/// fn example() -> Result<(), Box<dyn std::error::Error>> {
+///     let client = Client::new("my_auth_key");
+///     let games = client.search("Celeste")?;
+///     let first_game = games.iter().next().ok_or("No games found")?;
+///     assert_eq!("Celeste", first_game.name);
+///     let images = client.get_images_for_id(first_game.id, &Grid(None))?;
+///     Ok(())
+///  }
+/// ```
+#[derive(Clone)]
+pub struct Client {
+    auth_key: String,
+    base_url: String,
+    transport: Arc<dyn BlockingTransport>,
+    retry_policy: RetryPolicy,
+    rate_limiter: Option<RateLimiter>,
+    cache: Option<ResponseCache>,
+}
+
+impl Client {
+    /// Creates a new client with the given auth key.
+    ///
+    /// ### Examples
+    /// ```
+    /// use steamgriddb_api::blocking::Client;
+    /// # fn main() {
+    /// let client = Client::new("my_auth_key");
+    /// assert_eq!("my_auth_key", client.get_auth_key());
+    /// # }
+    /// ```
+    #[cfg(feature = "reqwest")]
+    pub fn new<S>(auth_key: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self::with_transport(auth_key, crate::transport::ReqwestBlockingTransport::new())
+    }
+
+    /// Creates a new client with the given auth key, that sends its requests using the given transport.
+    ///
+    /// ### Examples
+    /// ```
+    /// use steamgriddb_api::blocking::Client;
+    /// use steamgriddb_api::transport::ReqwestBlockingTransport;
+    /// # fn main() {
+    /// let client = Client::with_transport("my_auth_key", ReqwestBlockingTransport::new());
+    /// assert_eq!("my_auth_key", client.get_auth_key());
+    /// # }
+    /// ```
+    pub fn with_transport<S, T>(auth_key: S, transport: T) -> Self
+    where
+        S: Into<String>,
+        T: BlockingTransport + 'static,
+    {
+        Self {
+            auth_key: auth_key.into(),
+            base_url: DEFAULT_BASE_URL.to_owned(),
+            transport: Arc::new(transport),
+            retry_policy: RetryPolicy::default(),
+            rate_limiter: None,
+            cache: None,
+        }
+    }
+
+    /// Sets the base url for the client.
+    ///
+    /// The default url is <https://www.steamgriddb.com/api/v2>
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use steamgriddb_api::blocking::Client;
+    /// # fn main() {
+    /// let mut client = Client::new("my_auth_key");
+    /// client.set_base_url("https://localhost:8080/api/v2");
+    /// assert_eq!("https://localhost:8080/api/v2", client.base_url());
+    /// # }
+    /// ```
+    pub fn set_base_url<S>(&mut self, base_url: S)
+    where
+        S: Into<String>,
+    {
+        self.base_url = base_url.into();
+    }
+
+    /// Gets the base url for the client.
+    ///
+    /// The default url is <https://www.steamgriddb.com/api/v2>
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use steamgriddb_api::blocking::Client;
+    /// # fn main() {
+    /// let mut client = Client::new("my_auth_key");    
+    /// assert_eq!("https://www.steamgriddb.com/api/v2", client.base_url());
+    /// # }
+    /// ```
+    pub fn base_url(&'_ self) -> &'_ str {
+        self.base_url.as_str()
+    }
+
+    /// Sets the policy for retrying requests that failed because of rate limiting, server errors or connection problems.
+    ///
+    /// By default requests are retried up to 3 times, see [`RetryPolicy::default`].
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use steamgriddb_api::blocking::Client;
+    /// use steamgriddb_api::retry::RetryPolicy;
+    /// # fn main() {
+    /// let mut client = Client::new("my_auth_key");
+    /// client.set_retry_policy(RetryPolicy::none());
+    /// assert_eq!(1, client.retry_policy().max_attempts);
+    /// # }
+    /// ```
+    pub fn set_retry_policy(&mut self, retry_policy: RetryPolicy) {
+        self.retry_policy = retry_policy;
+    }
+
+    /// Gets the policy for retrying requests.
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.retry_policy
+    }
+
+    /// Limits the rate of requests sent by this client.
+    ///
+    /// The limiter is shared by the clones of the client, and can also be shared with other clients by cloning it.
+    /// Each attempt of a request, including retries, waits for the limiter.
+    /// By default there is no limit.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use steamgriddb_api::blocking::Client;
+    /// use steamgriddb_api::rate_limit::RateLimiter;
+    /// # fn main() {
+    /// let mut client = Client::new("my_auth_key");
+    /// client.set_rate_limiter(RateLimiter::new(5.0, 10));
+    /// assert_eq!(0, client.rate_limiter().unwrap().stats().requests);
+    /// # }
+    /// ```
+    pub fn set_rate_limiter(&mut self, rate_limiter: RateLimiter) {
+        self.rate_limiter = Some(rate_limiter);
+    }
+
+    /// Removes the rate limit of this client.
+    pub fn clear_rate_limiter(&mut self) {
+        self.rate_limiter = None;
+    }
+
+    /// Gets the rate limiter of this client, if it has one.
+    pub fn rate_limiter(&self) -> Option<&RateLimiter> {
+        self.rate_limiter.as_ref()
+    }
+
+    /// Caches the responses of GET requests made by this client, like searches, game info and image lists.
+    ///
+    /// The cache is shared by the clones of the client.
+    /// Responses are keyed by their url only, so a cache should not be shared between clients with different base urls.
+    /// By default nothing is cached.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use steamgriddb_api::blocking::Client;
+    /// use steamgriddb_api::cache::ResponseCache;
+    /// # fn main() {
+    /// let mut client = Client::new("my_auth_key");
+    /// client.set_cache(ResponseCache::new(Duration::from_secs(60 * 60), 1000));
+    /// assert!(client.cache().unwrap().is_empty());
+    /// # }
+    /// ```
+    pub fn set_cache(&mut self, cache: ResponseCache) {
+        self.cache = Some(cache);
+    }
+
+    /// Stops caching responses in this client.
+    pub fn disable_cache(&mut self) {
+        self.cache = None;
+    }
+
+    /// Gets the response cache of this client, if it has one.
+    pub fn cache(&self) -> Option<&ResponseCache> {
+        self.cache.as_ref()
+    }
+
+    /// Gets the auth key for the client.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use steamgriddb_api::blocking::Client;
+    /// # fn main() {
+    /// let client = Client::new("my_auth_key");
+    /// assert_eq!("my_auth_key", client.get_auth_key());
+    /// # }
+    /// ```
+    pub fn get_auth_key(&self) -> &'_ str {
+        self.auth_key.as_str()
+    }
+
+    /// Sets the auth key for the client.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use steamgriddb_api::blocking::Client;
+    /// # fn main() {
+    /// let mut client = Client::new("my_auth_key");
+    /// client.set_auth_key("another_key");
+    /// assert_eq!("another_key", client.get_auth_key());
+    /// # }
+    /// ```
+    pub fn set_auth_key<S>(&mut self, auth_key: S)
+    where
+        S: Into<String>,
+    {
+        self.auth_key = auth_key.into();
+    }
+
+    /// Fetches images given a game id and a query type.
+    ///    
+    /// ### Examples
+    /// The Query type decides which kind of images to fetch.
+    ///
+    /// ```no_run
+    /// use steamgriddb_api::blocking::Client;
+    /// use steamgriddb_api::query_parameters::QueryType::*;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut client = Client::new("my_auth_key");
+    /// let grid_images = client.get_images_for_id(7993, &Grid(None))?;
+    /// let hero_images = client.get_images_for_id(7993, &Hero(None))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Query parameters can be given to specify which images to fetch.
+    ///
+    /// ```no_run
+    /// use steamgriddb_api::blocking::Client;
+    /// use steamgriddb_api::query_parameters::GridDimentions::*;
+    /// use steamgriddb_api::query_parameters::QueryType::*;
+    /// use steamgriddb_api::query_parameters::GridQueryParameters;    
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut client = Client::new("my_auth_key");
+    /// let mut parameters = GridQueryParameters::default();
+    /// parameters.dimentions = Some(&[D600x900,D512x512]);
+    /// let filtered_grid_images = client.get_images_for_id(7993, &Grid(Some(parameters)))?;    
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_images_for_id(
+        &self,
+        game_id: usize,
+        query: &QueryType<'_>,
+    ) -> Result<Vec<Image>, Error> {
+        let url = get_images_by_game_id_url(self.base_url.as_str(), game_id, query);
+        let response = self.make_request::<InnerImagesSingleIdResponse>(url.as_str())?;
+        response_to_data(response)
+    }
+
+    /// Fetches images given a list game id's and a query type.
+    ///
+    /// The resulting list will be a SteamGridDbResult<Image> for each id.
+    ///            
+    /// ### Examples
+    /// One image will be fetched for each id.
+    ///
+    /// ```no_run
+    /// use steamgriddb_api::blocking::Client;
+    /// use steamgriddb_api::query_parameters::QueryType::*;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut client = Client::new("my_auth_key");
+    /// let ids = [7993,5153400];
+    /// let grid_images = client.get_images_for_ids(&ids, &Grid(None))?;
+    /// assert_eq!(ids.len(), grid_images.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_images_for_ids(
+        &self,
+        game_id: &[usize],
+        query: &QueryType<'_>,
+    ) -> Result<Vec<SteamGridDbResult<Image>>, Error> {
+        if game_id.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        if game_id.len() == 1 {
+            let single_result = self.get_images_for_id(game_id[0], query)?;
+            if !single_result.is_empty() {
+                return Ok(vec![SteamGridDbResult::Ok(single_result[0].clone())]);
+            }
+        }
+
+        let url = get_images_by_game_ids_url(self.base_url.as_str(), game_id, query);
+
+        let resposse = self.make_request::<InnerImagesMultipleIdsResponse>(url.as_str())?;
+        response_to_data_flat(resposse)
+    }
+
+    /// Fetches all images given a list game id's and a query type.
+    ///
+    /// The resulting list will be a SteamGridDbResult<Vec<Image>> for each id, in the same order as the ids.
+    ///
+    /// ### Examples
+    /// Every image will be fetched for each id.
+    ///
+    /// ```no_run
+    /// use steamgriddb_api::blocking::Client;
+    /// use steamgriddb_api::query_parameters::QueryType::*;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("my_auth_key");
+    /// let ids = [7993,5153400];
+    /// let grid_images = client.get_all_images_for_ids(&ids, &Grid(None))?;
+    /// assert_eq!(ids.len(), grid_images.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_all_images_for_ids(
+        &self,
+        game_id: &[usize],
+        query: &QueryType<'_>,
+    ) -> Result<Vec<SteamGridDbResult<Vec<Image>>>, Error> {
+        if game_id.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        if game_id.len() == 1 {
+            let single_result = self.get_images_for_id(game_id[0], query);
+            return single_result_to_result_all(single_result);
+        }
+
+        let url = get_images_by_game_ids_url(self.base_url.as_str(), game_id, query);
+        let response = self.make_request::<InnerImagesMultipleIdsResponse>(url.as_str())?;
+        response_to_result_all(response)
+    }
+
+    /// Fetches a single image given its kind and its steamgriddb id.
+    ///
+    /// ### Examples
+    ///
+    /// ```no_run
+    /// use steamgriddb_api::blocking::Client;
+    /// use steamgriddb_api::query_parameters::ImageKind;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("my_auth_key");
+    /// let image = client.get_image_by_id(&ImageKind::Grid, 80200)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_image_by_id(&self, kind: &ImageKind, image_id: u32) -> Result<Image, Error> {
+        let url = get_image_by_id_url(self.base_url.as_str(), kind, image_id);
+        let response = self.make_request::<InnerImageResponse>(url.as_str())?;
+        response_to_data(response)
+    }
+
+    /// Fetches a single grid image given its steamgriddb id.
+    ///
+    /// ### Examples
+    ///
+    /// ```no_run
+    /// use steamgriddb_api::blocking::Client;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("my_auth_key");
+    /// let grid = client.get_grid_by_id(80200)?;
+    /// println!("{} has {} upvotes", grid.url, grid.upvotes);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_grid_by_id(&self, image_id: u32) -> Result<Image, Error> {
+        self.get_image_by_id(&ImageKind::Grid, image_id)
+    }
+
+    /// Fetches a single hero image given its steamgriddb id.
+    pub fn get_hero_by_id(&self, image_id: u32) -> Result<Image, Error> {
+        self.get_image_by_id(&ImageKind::Hero, image_id)
+    }
+
+    /// Fetches a single logo image given its steamgriddb id.
+    pub fn get_logo_by_id(&self, image_id: u32) -> Result<Image, Error> {
+        self.get_image_by_id(&ImageKind::Logo, image_id)
+    }
+
+    /// Fetches a single icon image given its steamgriddb id.
+    pub fn get_icon_by_id(&self, image_id: u32) -> Result<Image, Error> {
+        self.get_image_by_id(&ImageKind::Icon, image_id)
+    }
+
+    /// Search for games given a search query.
+    ///     
+    /// The search query will be url encoded, so that it will be safe to use.           
+    ///     
+    /// ### Examples
+    ///
+    /// ```no_run
+    /// use steamgriddb_api::blocking::Client;
+    /// use steamgriddb_api::query_parameters::QueryType::*;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut client = Client::new("my_auth_key");    
+    /// let search_results = client.search("Celeste")?;
+    /// let first_result = search_results.iter().next().ok_or("None found")?;
+    /// assert_eq!(first_result.name, "Celeste");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn search(&self, query: &str) -> Result<Vec<SearchResult>, Error> {
+        let url = get_search_url(self.base_url.as_str(), query);
+        let response = self.make_request::<InnerSearchResult>(url.as_str())?;
+        response_to_data(response)
+    }
+
+    /// Search for a game, and get the result that best matches the title, if the matcher is confident enough.
+    ///
+    /// The results are scored by the matcher, not taken in the order the api returns them.
+    ///
+    /// ### Examples
+    ///
+    /// ```no_run
+    /// use steamgriddb_api::blocking::Client;
+    /// use steamgriddb_api::matcher::TitleMatcher;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("my_auth_key");
+    /// let matcher = TitleMatcher::new().year(2012);
+    /// let found = client.search_best_match("Assassins Creed 3", &matcher)?;
+    /// if let Some(found) = found {
+    ///     assert_eq!("Assassin's Creed® III", found.result.name);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn search_best_match(
+        &self,
+        title: &str,
+        matcher: &TitleMatcher,
+    ) -> Result<Option<TitleMatch>, Error> {
+        let results = self.search(title)?;
+        Ok(matcher.best_match(title, &results))
+    }
+
+    /// Fetches images given a platform type, a platform specific game id and a query type.
+    ///    
+    /// ### Examples
+    ///    
+    /// ```no_run
+    /// use steamgriddb_api::blocking::Client;
+    /// use steamgriddb_api::query_parameters::Platform::*;
+    /// use steamgriddb_api::query_parameters::QueryType::*;
+    /// use steamgriddb_api::query_parameters::GridQueryParameters;    
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut client = Client::new("my_auth_key");    
+    /// let platform = EpicGameStore;
+    /// let epic_games_images = client.get_images_for_platform_id(&platform, "Salt", &Grid(None))?;    
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_images_for_platform_id(
+        &self,
+        platform: &Platform,
+        game_id: &str,
+        qeury: &QueryType<'_>,
+    ) -> Result<Vec<Image>, Error> {
+        let url = get_images_by_platform_id_url(self.base_url.as_str(), platform, game_id, qeury);
+        let response = self.make_request::<InnerImagesSingleIdResponse>(url.as_str())?;
+        response_to_data(response)
+    }
+
+    /// Walks through all pages of images for a game, given its id and a query type.
+    ///
+    /// The page and limit of the query decides where to start and how many images to fetch per request.
+    ///
+    /// ### Examples
+    ///
+    /// ```no_run
+    /// use steamgriddb_api::blocking::Client;
+    /// use steamgriddb_api::query_parameters::QueryType::*;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("my_auth_key");
+    /// let pages = client.images_for_id_paged(7993, &Grid(None));
+    /// for page in pages {
+    ///     let grid_images = page?;
+    /// }
+    /// let all_grid_images = client.images_for_id_paged(7993, &Grid(None)).all()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn images_for_id_paged<'a>(
+        &'a self,
+        game_id: usize,
+        query: &QueryType<'a>,
+    ) -> ImagePages<'a> {
+        ImagePages {
+            client: self,
+            pages: PagedImages::new(PagedImagesTarget::GameId(game_id), query),
+        }
+    }
+
+    /// Walks through all pages of images for a game, given a platform type, a platform specific game id and a query type.
+    ///
+    /// ### Examples
+    ///
+    /// ```no_run
+    /// use steamgriddb_api::blocking::Client;
+    /// use steamgriddb_api::query_parameters::Platform::*;
+    /// use steamgriddb_api::query_parameters::QueryType::*;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("my_auth_key");
+    /// let all_grid_images = client
+    ///     .images_for_platform_id_paged(&Steam, "361420", &Grid(None))
+    ///     .all()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn images_for_platform_id_paged<'a>(
+        &'a self,
+        platform: &'a Platform,
+        game_id: &'a str,
+        query: &QueryType<'a>,
+    ) -> ImagePages<'a> {
+        ImagePages {
+            client: self,
+            pages: PagedImages::new(PagedImagesTarget::PlatformId(platform, game_id), query),
+        }
+    }
+
+    /// Fetches images given a platform type, a platform specific game ids and a query type.
+    ///    
+    /// The resulting list will be a SteamGridDbResult<Image> for each id.
+    ///    
+    /// ### Examples
+    ///    
+    /// ```no_run
+    /// use steamgriddb_api::blocking::Client;
+    /// use steamgriddb_api::query_parameters::Platform::*;
+    /// use steamgriddb_api::query_parameters::QueryType::*;
+    /// use steamgriddb_api::query_parameters::GridQueryParameters;    
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>>{
+    /// let mut client = Client::new("my_auth_key");    
+    /// let platform = EpicGameStore;
+    /// let ids = ["Salt", "Turkey"];
+    /// let epic_games_images = client.get_images_for_platform_ids(&platform, &ids, &Grid(None))?;  
+    /// # Ok(())  
+    /// # }
+    /// ```
+    pub fn get_images_for_platform_ids(
+        &self,
+        platform: &Platform,
+        game_id: &[&str],
+        qeury: &QueryType<'_>,
+    ) -> Result<Vec<SteamGridDbResult<Image>>, Error> {
+        let url = get_images_by_platform_ids_url(self.base_url.as_str(), platform, game_id, qeury);
+        let resposse = self.make_request::<InnerImagesMultipleIdsResponse>(url.as_str())?;
+        response_to_data_flat(resposse)
+    }
+
+    /// Fetches all images given a platform type, a platform specific game ids and a query type.
+    ///
+    /// The resulting list will be a SteamGridDbResult<Vec<Image>> for each id, in the same order as the ids.
+    ///
+    /// ### Examples
+    ///
+    /// ```no_run
+    /// use steamgriddb_api::blocking::Client;
+    /// use steamgriddb_api::query_parameters::Platform::*;
+    /// use steamgriddb_api::query_parameters::QueryType::*;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("my_auth_key");
+    /// let ids = ["Salt", "Turkey"];
+    /// let epic_games_images = client.get_all_images_for_platform_ids(&EpicGameStore, &ids, &Grid(None))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_all_images_for_platform_ids(
+        &self,
+        platform: &Platform,
+        game_id: &[&str],
+        query: &QueryType<'_>,
+    ) -> Result<Vec<SteamGridDbResult<Vec<Image>>>, Error> {
+        if game_id.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        if game_id.len() == 1 {
+            let single_result = self.get_images_for_platform_id(platform, game_id[0], query);
+            return single_result_to_result_all(single_result);
+        }
+
+        let url = get_images_by_platform_ids_url(self.base_url.as_str(), platform, game_id, query);
+        let response = self.make_request::<InnerImagesMultipleIdsResponse>(url.as_str())?;
+        response_to_result_all(response)
+    }
+
+    /// Fetch information about a game given a game id.
+    ///    
+    /// ### Examples
+    ///    
+    /// ```no_run
+    /// use steamgriddb_api::blocking::Client;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut client = Client::new("my_auth_key");    
+    /// let game_info = client.get_game_info_for_id(13136)?;    
+    /// assert_eq!(game_info.name, "Celeste");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_game_info_for_id(&self, game_id: usize) -> Result<GameInfo, Error> {
+        let url = get_gameinfo_by_game_id_url(self.base_url.as_str(), game_id);
+        let response = self.make_request::<Response<GameInfo>>(url.as_str())?;
+        response_to_data(response)
+    }
+
+    /// Fetch information about a game given a steam game id.
+    ///    
+    /// ### Examples
+    ///    
+    /// ```no_run
+    /// use steamgriddb_api::blocking::Client;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut client = Client::new("my_auth_key");    
+    /// let game_info = client.get_game_by_steam_app_id(361420)?;    
+    /// assert_eq!(game_info.name, "Astroneer");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_game_by_steam_app_id(&self, steam_app_id: usize) -> Result<GameInfo, Error> {
+        let url = get_game_by_steam_app_id_url(self.base_url.as_str(), steam_app_id);
+        let response = self.make_request::<Response<GameInfo>>(url.as_str())?;
+        response_to_data(response)
+    }
+
+    /// Fetch information about a game given a platform type and a platform specific game id.
+    ///
+    /// ### Examples
+    ///
+    /// ```no_run
+    /// use steamgriddb_api::blocking::Client;
+    /// use steamgriddb_api::query_parameters::Platform;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("my_auth_key");
+    /// let game_info = client.get_game_by_platform_id(&Platform::GoG, "1207658924")?;
+    /// println!("The SteamGridDB id is {}", game_info.id);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_game_by_platform_id(
+        &self,
+        platform: &Platform,
+        game_id: &str,
+    ) -> Result<GameInfo, Error> {
+        let url = get_game_by_platform_id_url(self.base_url.as_str(), platform, game_id);
+        let response = self.make_request::<Response<GameInfo>>(url.as_str())?;
+        response_to_data(response)
+    }
+
+    /// Fetch information about a game, including its ids and store data on the given platforms.
+    ///
+    /// ### Examples
+    ///
+    /// ```no_run
+    /// use steamgriddb_api::blocking::Client;
+    /// use steamgriddb_api::games::GameLookup;
+    /// use steamgriddb_api::query_parameters::Platform;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("my_auth_key");
+    /// let lookup = GameLookup::PlatformId(&Platform::EpicGameStore, "Salt");
+    /// let game_info = client.get_game(&lookup, &[Platform::Steam, Platform::GoG])?;
+    /// if let Some(gog_id) = game_info.platform_id(&Platform::GoG) {
+    ///     println!("The GOG id is {}", gog_id);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_game(
+        &self,
+        lookup: &GameLookup<'_>,
+        platform_data: &[Platform],
+    ) -> Result<GameInfo, Error> {
+        let url = get_game_url(self.base_url.as_str(), lookup, platform_data);
+        let response = self.make_request::<Response<GameInfo>>(url.as_str())?;
+        response_to_data(response)
+    }
+
+    /// Uploads an image for a game, given its steamgriddb id.
+    ///
+    /// The upload type decides the kind and style of the image.
+    /// The auth key of the client is used to identify the uploader.
+    ///
+    /// ### Examples
+    ///
+    /// ```no_run
+    /// use steamgriddb_api::blocking::Client;
+    /// use steamgriddb_api::query_parameters::Style;
+    /// use steamgriddb_api::upload::UploadType;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("my_auth_key");
+    /// let data = std::fs::read("my_grid.png")?;
+    /// let upload = UploadType::Grid(Style::Alternate);
+    /// let image = client.upload_image(13136, &upload, "my_grid.png", &data)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn upload_image(
+        &self,
+        game_id: usize,
+        upload: &UploadType,
+        file_name: &str,
+        data: &[u8],
+    ) -> Result<Image, Error> {
+        let url = get_upload_url(self.base_url.as_str(), upload);
+        let form = UploadForm::new(game_id, upload, file_name, data);
+        let response = self.make_upload_request::<InnerImageResponse>(url.as_str(), form)?;
+        response_to_data(response)
+    }
+
+    /// Uploads an image file for a game, given its steamgriddb id.
+    ///
+    /// See [`Client::upload_image`].
+    ///
+    /// ### Examples
+    ///
+    /// ```no_run
+    /// use steamgriddb_api::blocking::Client;
+    /// use steamgriddb_api::query_parameters::StyleLogo;
+    /// use steamgriddb_api::upload::UploadType;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("my_auth_key");
+    /// let upload = UploadType::Logo(StyleLogo::White);
+    /// let image = client.upload_image_from_path(13136, &upload, "my_logo.png")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn upload_image_from_path<P>(
+        &self,
+        game_id: usize,
+        upload: &UploadType,
+        path: P,
+    ) -> Result<Image, Error>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let data = std::fs::read(path)?;
+        let file_name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        self.upload_image(game_id, upload, &file_name, &data)
+    }
+
+    /// Deletes images uploaded by the user of the auth key.
+    ///
+    /// The images must all be of the given kind.
+    /// Deleting images uploaded by another user fails with [`Error::PermissionDenied`].
+    ///
+    /// ### Examples
+    ///
+    /// ```no_run
+    /// use steamgriddb_api::blocking::Client;
+    /// use steamgriddb_api::query_parameters::ImageKind;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("my_auth_key");
+    /// client.delete_images(&ImageKind::Grid, &[80200, 80201])?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn delete_images(&self, kind: &ImageKind, image_ids: &[u32]) -> Result<(), Error> {
+        if image_ids.is_empty() {
+            return Ok(());
+        }
+        let url = get_delete_images_url(self.base_url.as_str(), kind, image_ids);
+        let response =
+            self.send_request::<InnerEmptyResponse>(HttpRequest::new(Method::Delete, url))?;
+        response_to_success(response)
+    }
+
+    /// Votes on an image, as the user of the auth key.
+    ///
+    /// ### Examples
+    ///
+    /// ```no_run
+    /// use steamgriddb_api::blocking::Client;
+    /// use steamgriddb_api::images::Vote;
+    /// use steamgriddb_api::query_parameters::ImageKind;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("my_auth_key");
+    /// client.vote(&ImageKind::Hero, 25973, &Vote::Up)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn vote(&self, kind: &ImageKind, image_id: u32, vote: &Vote) -> Result<(), Error> {
+        let url = get_vote_url(self.base_url.as_str(), kind, image_id, vote);
+        let response =
+            self.send_request::<InnerEmptyResponse>(HttpRequest::new(Method::Post, url))?;
+        response_to_success(response)
+    }
+
+    /// Downloads the file of an image, checking its size and content type with the default [`DownloadOptions`].
+    ///
+    /// The file is downloaded from the steamgriddb CDN, without sending the auth key.
+    ///
+    /// ### Examples
+    ///
+    /// ```no_run
+    /// use steamgriddb_api::blocking::Client;
+    /// use steamgriddb_api::download::Variant;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("my_auth_key");
+    /// let grid = client.get_grid_by_id(80200)?;
+    /// let bytes = client.download_image(&grid, Variant::Full)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn download_image(&self, image: &Image, variant: Variant) -> Result<Vec<u8>, Error> {
+        let mut bytes = Vec::new();
+        self.download_image_to(image, variant, &mut bytes, &DownloadOptions::default())?;
+        Ok(bytes)
+    }
+
+    /// Downloads the file of an image, and writes it to the writer.
+    ///
+    /// ### Examples
+    ///
+    /// ```no_run
+    /// use steamgriddb_api::blocking::Client;
+    /// use steamgriddb_api::download::{DownloadOptions, Variant};
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("my_auth_key");
+    /// let grid = client.get_grid_by_id(80200)?;
+    /// let mut bytes = Vec::new();
+    /// let downloaded = client
+    ///     .download_image_to(&grid, Variant::Thumb, &mut bytes, &DownloadOptions::default())
+    ///     ?;
+    /// assert_eq!(downloaded.size, bytes.len() as u64);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn download_image_to<W>(
+        &self,
+        image: &Image,
+        variant: Variant,
+        writer: &mut W,
+        options: &DownloadOptions<'_>,
+    ) -> Result<DownloadedImage, Error>
+    where
+        W: Write + ?Sized,
+    {
+        let response = self.fetch_download(image, variant)?;
+        stream_download_blocking(image, variant, response, writer, options)
+    }
+
+    /// Downloads the file of an image, and saves it at the path.
+    ///
+    /// The file is first written next to the path with a ".tmp" extension, and then renamed,
+    /// so the path never contains a partial download.
+    ///
+    /// ### Examples
+    ///
+    /// ```no_run
+    /// use steamgriddb_api::blocking::Client;
+    /// use steamgriddb_api::download::{DownloadOptions, Variant};
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("my_auth_key");
+    /// let grid = client.get_grid_by_id(80200)?;
+    /// let options = DownloadOptions::default();
+    /// client.download_image_to_path(&grid, Variant::Full, "grid.png", &options)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn download_image_to_path<P>(
+        &self,
+        image: &Image,
+        variant: Variant,
+        path: P,
+        options: &DownloadOptions<'_>,
+    ) -> Result<DownloadedImage, Error>
+    where
+        P: AsRef<Path>,
+    {
+        let response = self.fetch_download(image, variant)?;
+        let mut file = AtomicFile::create(path.as_ref())?;
+        let downloaded = stream_download_blocking(image, variant, response, file.file(), options)?;
+        file.commit()?;
+        Ok(downloaded)
+    }
+
+    /// Downloads an image, and installs it as Steam artwork for an app.
+    ///
+    /// The file is named the way Steam expects for the kind of artwork, with the extension of the image.
+    /// Artwork of the same kind for the app with other extensions is removed, so Steam shows the new image.
+    /// Webp images are refused with [`Error::ContentType`] before they are downloaded, since Steam does not show them.
+    ///
+    /// ### Examples
+    ///
+    /// ```no_run
+    /// use steamgriddb_api::blocking::Client;
+    /// use steamgriddb_api::query_parameters::ImageKind;
+    /// use steamgriddb_api::steam::grid::{ArtworkKind, GridFolder};
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("my_auth_key");
+    /// let folder = GridFolder::new("/home/me/.steam/steam/userdata/12345678");
+    /// let grid = client.get_grid_by_id(80200)?;
+    /// let kind = ArtworkKind::for_image(&ImageKind::Grid, &grid);
+    /// let installed = client.install_artwork(&folder, 504230, kind, &grid)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn install_artwork(
+        &self,
+        folder: &GridFolder,
+        app_id: u32,
+        kind: ArtworkKind,
+        image: &Image,
+    ) -> Result<InstalledArtwork, Error> {
+        let path = folder.path_for_image(app_id, kind, image)?;
+        folder.create()?;
+        self.download_image_to_path(image, Variant::Full, &path, &DownloadOptions::default())?;
+        folder.remove_except(app_id, kind, Some(&path))?;
+        Ok(InstalledArtwork { app_id, kind, path })
+    }
+
+    fn fetch_download(
+        &self,
+        image: &Image,
+        variant: Variant,
+    ) -> Result<StreamingResponse<Box<dyn Read + Send>>, Error> {
+        let request = HttpRequest::new(Method::Get, variant.url(image));
+        self.with_retries(request.method, || {
+            self.transport.send_streaming(request.clone())
+        })
+    }
+
+    fn make_request<T>(&self, url: &str) -> Result<T, Error>
+    where
+        T: DeserializeOwned,
+    {
+        self.send_request(HttpRequest::new(Method::Get, url))
+    }
+
+    fn make_upload_request<T>(&self, url: &str, form: UploadForm) -> Result<T, Error>
+    where
+        T: DeserializeOwned,
+    {
+        let request = HttpRequest::new(Method::Post, url)
+            .with_header("Content-Type", form.content_type)
+            .with_body(form.body);
+        self.send_request(request)
+    }
+
+    fn send_request<T>(&self, request: HttpRequest) -> Result<T, Error>
+    where
+        T: DeserializeOwned,
+    {
+        let request = request.with_header("Authorization", format!("Bearer {}", self.auth_key));
+        let response = match (&self.cache, request.method) {
+            (Some(cache), Method::Get) => self.send_cached(cache, request)?,
+            _ => self.send_with_retries(request)?,
+        };
+        parse_response(response.status, &response.body)
+    }
+
+    fn send_cached(
+        &self,
+        cache: &ResponseCache,
+        request: HttpRequest,
+    ) -> Result<HttpResponse, Error> {
+        let url = request.url.clone();
+        let sent = match cache.lookup(&url) {
+            CacheLookup::Fresh(response) => return Ok(response),
+            CacheLookup::Revalidate(etag) => request.clone().with_header("If-None-Match", etag),
+            CacheLookup::Miss => request.clone(),
+        };
+        let response = cache.store(&url, self.send_with_retries(sent)?);
+        if response.status != 304 {
+            return Ok(response);
+        }
+        // The cached response was evicted while it was revalidated, so it is requested again in full
+        let response = self.send_with_retries(request)?;
+        Ok(cache.store(&url, response))
+    }
+
+    fn send_with_retries(&self, request: HttpRequest) -> Result<HttpResponse, Error> {
+        self.with_retries(request.method, || self.transport.send(request.clone()))
+    }
+
+    fn with_retries<R, S>(&self, method: Method, send: S) -> Result<R, Error>
+    where
+        R: ResponseHead,
+        S: Fn() -> Result<R, Error>,
+    {
+        let mut attempt = 1;
+        loop {
+            if let Some(rate_limiter) = &self.rate_limiter {
+                let reservation = rate_limiter.reserve();
+                if !reservation.wait().is_zero() {
+                    self.transport.sleep(reservation.wait());
+                }
+                reservation.sent();
+            }
+            let result = send();
+            match self.retry_policy.retry_delay(attempt, method, &result) {
+                Some(delay) => self.transport.sleep(delay),
+                None => return result,
+            }
+            attempt += 1;
+        }
+    }
+
+    /// Get a SteamStaticUrls that contains the expected urls for the official Steam store images.
+    pub fn get_official_steam_images_static(steam_app_id: &str) -> SteamStaticUrls {
+        SteamStaticUrls::new(steam_app_id)
+    }
+
+    /// Get a SteamStaticUrls that contains the expected urls for the official Steam store images.
+    pub fn get_official_steam_images(&self, steam_app_id: &str) -> SteamStaticUrls {
+        Self::get_official_steam_images_static(steam_app_id)
+    }
+
+    /// Check which of the official Steam store images exist, using HEAD requests.
+    ///
+    /// High resolution images that are missing fall back to their normal resolution, like `library_600x900.jpg` for the capsule.
+    ///
+    /// ### Examples
+    ///
+    /// ```no_run
+    /// use steamgriddb_api::blocking::Client;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("my_auth_key");
+    /// let images = client.check_official_steam_images("361420")?;
+    /// if images.hero.exists {
+    ///     println!("The hero is at {}", images.hero.url);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn check_official_steam_images(
+        &self,
+        steam_app_id: &str,
+    ) -> Result<CheckedSteamStaticUrls, Error> {
+        let urls = self.get_official_steam_images(steam_app_id);
+        Ok(CheckedSteamStaticUrls {
+            header: self.check_static_asset(&urls.header)?,
+            capsule: self.check_static_asset(&urls.capsule)?,
+            hero: self.check_static_asset(&urls.hero)?,
+            logo: self.check_static_asset(&urls.logo)?,
+        })
+    }
+
+    /// Check the url of an image, and its fallbacks, until one exists
+    fn check_static_asset(&self, url: &str) -> Result<StaticAsset, Error> {
+        let mut first = None;
+        for candidate in fallback_urls(url) {
+            let request = HttpRequest::new(Method::Head, candidate.as_str());
+            let response = self.send_with_retries(request)?;
+            let asset = StaticAsset::from_response(candidate, &response)?;
+            if asset.exists {
+                return Ok(asset);
+            }
+            first.get_or_insert(asset);
+        }
+        Ok(first.expect("There is always at least one url"))
+    }
+}
+
+/// Walks through the pages of images for a game.
+///
+/// Each item of the iterator is one page of images.
+/// Created by [`Client::images_for_id_paged`] and [`Client::images_for_platform_id_paged`].
+pub struct ImagePages<'a> {
+    client: &'a Client,
+    pages: PagedImages<'a>,
+}
+
+impl ImagePages<'_> {
+    /// Fetches all the remaining pages, and returns the images from all of them.
+    pub fn all(self) -> Result<Vec<Image>, Error> {
+        let mut images = Vec::new();
+        for page in self {
+            images.extend(page?);
+        }
+        Ok(images)
+    }
+}
+
+impl Iterator for ImagePages<'_> {
+    type Item = Result<Vec<Image>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let url = self.pages.next_url(self.client.base_url.as_str())?;
+        let response = self
+            .client
+            .make_request::<InnerImagesSingleIdResponse>(url.as_str());
+        self.pages.handle_response(response)
+    }
+}
+
+/// Picks the best artwork of each kind for a game, in one call.
+///
+/// The queries of the [`ArtworkPolicy`] are tried in order, and the first query with images
+/// gives the artwork, picked from them by the selector of the policy.
+/// When no image is found, the official Steam image is used if it exists.
+///
+/// ### Examples
+///
+/// ```no_run
+/// use steamgriddb_api::blocking::{ArtworkResolver, Client};
+/// use steamgriddb_api::resolver::GameIdentity;
+///
+/// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = Client::new("my_auth_key");
+/// let resolver = ArtworkResolver::new(&client);
+/// let artwork = resolver.resolve(&GameIdentity::SteamAppId(361420))?;
+/// if let Some(hero) = artwork.hero {
+///     println!("The hero is at {}", hero.url());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct ArtworkResolver<'a> {
+    client: &'a Client,
+    policy: ArtworkPolicy<'a>,
+}
+
+impl<'a> ArtworkResolver<'a> {
+    /// Creates a resolver with the default policy.
+    pub fn new(client: &'a Client) -> Self {
+        Self::with_policy(client, ArtworkPolicy::default())
+    }
+
+    /// Creates a resolver with the given policy.
+    pub fn with_policy(client: &'a Client, policy: ArtworkPolicy<'a>) -> Self {
+        Self { client, policy }
+    }
+
+    /// The policy used to pick the artwork.
+    pub fn policy(&self) -> &ArtworkPolicy<'a> {
+        &self.policy
+    }
+
+    /// Finds the artwork of every kind for a game.
+    ///
+    /// Games that are not known to SteamGridDB can still get the official Steam images, if their Steam app id is known.
+    pub fn resolve(&self, identity: &GameIdentity) -> Result<ResolvedArtworkSet, Error> {
+        let game_id = self.game_id(identity)?;
+        let target = direct_target(identity).or_else(|| game_id.map(PagedImagesTarget::GameId));
+        let mut artwork = ResolvedArtworkSet {
+            game_id,
+            ..Default::default()
+        };
+        for kind in ArtworkKind::ALL.iter() {
+            let resolved = self.resolve_for(target, identity, *kind)?;
+            artwork.set(*kind, resolved);
+        }
+        Ok(artwork)
+    }
+
+    /// Finds the artwork of one kind for a game.
+    pub fn resolve_kind(
+        &self,
+        identity: &GameIdentity,
+        kind: ArtworkKind,
+    ) -> Result<Option<ResolvedArtwork>, Error> {
+        let game_id = self.game_id(identity)?;
+        let target = direct_target(identity).or_else(|| game_id.map(PagedImagesTarget::GameId));
+        self.resolve_for(target, identity, kind)
+    }
+
+    /// Looks up the SteamGridDB id of the game, None if it is not found
+    fn game_id(&self, identity: &GameIdentity) -> Result<Option<usize>, Error> {
+        Ok(match identity {
+            GameIdentity::SteamGridDbId(game_id) => Some(*game_id),
+            GameIdentity::SteamAppId(app_id) => {
+                found(self.client.get_game_by_steam_app_id(*app_id))?.map(|game| game.id)
+            }
+            GameIdentity::Name(name) => found(self.client.search(name))?
+                .and_then(|results| results.into_iter().next())
+                .map(|result| result.id),
+            GameIdentity::PlatformId(platform, game_id) => {
+                found(self.client.get_game_by_platform_id(platform, game_id))?.map(|game| game.id)
+            }
+        })
+    }
+
+    fn resolve_for(
+        &self,
+        target: Option<PagedImagesTarget<'_>>,
+        identity: &GameIdentity,
+        kind: ArtworkKind,
+    ) -> Result<Option<ResolvedArtwork>, Error> {
+        if let Some(target) = target {
+            for query in self.policy.queries(kind) {
+                let query = &self.policy.selector.narrow_query(*query);
+                let images = match target {
+                    PagedImagesTarget::GameId(game_id) => {
+                        self.client.get_images_for_id(game_id, query)
+                    }
+                    PagedImagesTarget::PlatformId(platform, game_id) => self
+                        .client
+                        .get_images_for_platform_id(platform, game_id, query),
+                };
+                let selector = &self.policy.selector;
+                if let Some(image) = found(images)?.and_then(|images| selector.select_owned(images))
+                {
+                    return Ok(Some(ResolvedArtwork::SteamGridDb(Box::new(image))));
+                }
+            }
+        }
+        let steam_app_id = match identity.steam_app_id() {
+            Some(steam_app_id) if self.policy.official_fallback => steam_app_id,
+            _ => return Ok(None),
+        };
+        let asset = match official_asset(kind) {
+            Some(asset) => asset,
+            None => return Ok(None),
+        };
+        let urls = self.client.get_official_steam_images(&steam_app_id);
+        // A failed check of the CDN only means there is no official image to fall back to
+        let checked = match self.client.check_static_asset(urls.get(asset)) {
+            Ok(checked) => checked,
+            Err(_) => return Ok(None),
+        };
+        Ok(checked.exists.then_some(ResolvedArtwork::Official {
+            asset,
+            url: checked.url,
+        }))
+    }
+}
+
+#[cfg(all(test, feature = "reqwest"))]
+mod tests {
+
+    use super::*;
+    use crate::{
+        query_parameters::Style,
+        steam_static::SteamAsset,
+        test_server::{FakeTransport, StubResponse, StubServer},
+    };
+
+    fn test_client(server: &StubServer) -> Client {
+        let mut client = Client::new("my_auth_key");
+        client.set_base_url(server.url.as_str());
+        client.set_retry_policy(RetryPolicy {
+            base_delay: std::time::Duration::from_millis(1),
+            ..Default::default()
+        });
+        client
+    }
+
+    #[test]
+    fn upload_image_test() {
+        let json = std::fs::read_to_string("testdata/upload/upload.json").unwrap();
+        let server = StubServer::start(vec![StubResponse::json(200, &json)]);
+        let client = test_client(&server);
+        let upload = UploadType::Grid(Style::Alternate);
+        let image = client
+            .upload_image(13136, &upload, "grid.png", b"\x89PNG data")
+            .unwrap();
+        assert_eq!(92311, image.id);
+
+        let requests = server.requests();
+        assert_eq!(1, requests.len());
+        let request = &requests[0];
+        assert_eq!("POST", request.method);
+        assert_eq!("/grids", request.path);
+        assert_eq!(Some("Bearer my_auth_key"), request.header("authorization"));
+        let content_type = request.header("content-type").unwrap();
+        assert!(content_type.starts_with("multipart/form-data; boundary="));
+        let body = String::from_utf8_lossy(&request.body);
+        assert!(body.contains("name=\"game_id\"\r\n\r\n13136\r\n"));
+        assert!(body.contains("name=\"style\"\r\n\r\nalternate\r\n"));
+        assert!(body.contains("name=\"grid\"; filename=\"grid.png\""));
+    }
+
+    #[test]
+    fn upload_image_error_test() {
+        let json = std::fs::read_to_string("testdata/upload/error.json").unwrap();
+        let server = StubServer::start(vec![StubResponse::json(400, &json)]);
+        let client = test_client(&server);
+        let upload = UploadType::Hero(Style::Blurred);
+        let result = client.upload_image(13136, &upload, "hero.png", b"\x89PNG data");
+        match result {
+            Err(Error::Api(error)) => {
+                assert_eq!(Some(400), error.status);
+                assert_eq!(Some(vec!["Invalid style".to_string()]), error.errors);
+            }
+            _ => panic!("Expected an api error"),
+        }
+    }
+
+    #[test]
+    fn upload_image_from_missing_path_test() {
+        let client = Client::new("my_auth_key");
+        let upload = UploadType::Grid(Style::Alternate);
+        let result = client.upload_image_from_path(13136, &upload, "testdata/upload/missing.png");
+        assert!(matches!(result, Err(Error::Io(_))));
+    }
+
+    #[test]
+    fn delete_images_test() {
+        let server = StubServer::start(vec![StubResponse::json(200, r#"{"success":true}"#)]);
+        let client = test_client(&server);
+        client.delete_images(&ImageKind::Logo, &[1, 2]).unwrap();
+        let requests = server.requests();
+        assert_eq!("DELETE", requests[0].method);
+        assert_eq!("/logos/1,2", requests[0].path);
+        assert_eq!(
+            Some("Bearer my_auth_key"),
+            requests[0].header("authorization")
+        );
+    }
+
+    #[test]
+    fn delete_images_permission_denied_test() {
+        let body = r#"{"success":false,"errors":["You do not own this asset"]}"#;
+        let server = StubServer::start(vec![StubResponse::json(403, body)]);
+        let client = test_client(&server);
+        let result = client.delete_images(&ImageKind::Grid, &[80200]);
+        match result {
+            Err(Error::PermissionDenied(error)) => assert_eq!(Some(403), error.status),
+            _ => panic!("Expected a permission error"),
+        }
+    }
+
+    #[test]
+    fn vote_test() {
+        let server = StubServer::start(vec![StubResponse::json(200, r#"{"success":true}"#)]);
+        let client = test_client(&server);
+        client.vote(&ImageKind::Icon, 1234, &Vote::Down).unwrap();
+        let requests = server.requests();
+        assert_eq!("POST", requests[0].method);
+        assert_eq!("/icons/1234/vote/down", requests[0].path);
+    }
+
+    #[test]
+    fn vote_api_error_test() {
+        let body = r#"{"success":false,"errors":["Asset does not exist"]}"#;
+        let server = StubServer::start(vec![StubResponse::json(404, body)]);
+        let client = test_client(&server);
+        let result = client.vote(&ImageKind::Grid, 1, &Vote::Up);
+        assert!(matches!(result, Err(Error::Api(_))));
+    }
+
+    #[test]
+    fn get_grid_by_id_test() {
+        let json = std::fs::read_to_string("testdata/grids/grid_by_id.json").unwrap();
+        let server = StubServer::start(vec![StubResponse::json(200, &json)]);
+        let client = test_client(&server);
+        let grid = client.get_grid_by_id(80200).unwrap();
+        assert_eq!(80200, grid.id);
+        let requests = server.requests();
+        assert_eq!("GET", requests[0].method);
+        assert_eq!("/grids/80200", requests[0].path);
+    }
+
+    #[test]
+    fn get_grid_by_id_retries_test() {
+        let json = std::fs::read_to_string("testdata/grids/grid_by_id.json").unwrap();
+        let server = StubServer::start(vec![
+            StubResponse::json(503, "Service Unavailable"),
+            StubResponse::disconnect(),
+            StubResponse::json(200, &json),
+        ]);
+        let client = test_client(&server);
+        let grid = client.get_grid_by_id(80200).unwrap();
+        assert_eq!(80200, grid.id);
+        assert_eq!(3, server.requests().len());
+    }
+
+    #[test]
+    fn retry_after_test() {
+        let json = std::fs::read_to_string("testdata/grids/grid_by_id.json").unwrap();
+        let server = StubServer::start(vec![
+            StubResponse::json(429, "Too Many Requests").with_header("Retry-After", "1"),
+            StubResponse::json(200, &json),
+        ]);
+        let client = test_client(&server);
+        let start = std::time::Instant::now();
+        client.get_grid_by_id(80200).unwrap();
+        assert!(start.elapsed() >= std::time::Duration::from_secs(1));
+        assert_eq!(2, server.requests().len());
+    }
+
+    #[test]
+    fn retries_exhausted_test() {
+        let server = StubServer::start(vec![
+            StubResponse::json(500, "Internal Server Error"),
+            StubResponse::json(502, "Bad Gateway"),
+            StubResponse::json(504, "Gateway Timeout"),
+        ]);
+        let client = test_client(&server);
+        let result = client.get_grid_by_id(80200);
+        assert_eq!(Some(504), result.unwrap_err().status());
+        assert_eq!(3, server.requests().len());
+    }
+
+    #[test]
+    fn upload_image_not_retried_on_server_error_test() {
+        let server = StubServer::start(vec![StubResponse::json(503, "Service Unavailable")]);
+        let client = test_client(&server);
+        let upload = UploadType::Grid(Style::Alternate);
+        let result = client.upload_image(13136, &upload, "grid.png", b"\x89PNG data");
+        assert_eq!(Some(503), result.unwrap_err().status());
+        assert_eq!(1, server.requests().len());
+    }
+
+    #[test]
+    fn vote_retried_when_rate_limited_test() {
+        let server = StubServer::start(vec![
+            StubResponse::json(429, "Too Many Requests"),
+            StubResponse::json(200, r#"{"success":true}"#),
+        ]);
+        let client = test_client(&server);
+        client.vote(&ImageKind::Grid, 1, &Vote::Up).unwrap();
+        assert_eq!(2, server.requests().len());
+    }
+
+    #[test]
+    fn search_with_fake_transport_test() {
+        let transport = FakeTransport::default().with_fixture(
+            "https://www.steamgriddb.com/api/v2/search/autocomplete/Assassin%27s%20Creed",
+            "testdata/search/search.json",
+        );
+        let requests = transport.requests();
+        let client = Client::with_transport("my_auth_key", transport);
+        let results = client.search("Assassin's Creed").unwrap();
+        assert_eq!(15, results.len());
+        assert_eq!("Assassin's Creed", results[0].name);
+        let requests = requests.lock().unwrap();
+        assert_eq!(Method::Get, requests[0].method);
+        assert!(requests[0].headers.contains(&(
+            "Authorization".to_string(),
+            "Bearer my_auth_key".to_string()
+        )));
+    }
+
+    #[test]
+    fn game_not_found_with_fake_transport_test() {
+        let client = Client::with_transport("my_auth_key", FakeTransport::default());
+        let result = client.get_game_info_for_id(1);
+        match result {
+            Err(Error::Api(error)) => assert_eq!(Some(404), error.status),
+            _ => panic!("Expected an api error"),
+        }
+    }
+
+    #[test]
+    fn rate_limiter_shared_by_clones_test() {
+        let mut client = Client::with_transport("my_auth_key", FakeTransport::default());
+        client.set_rate_limiter(RateLimiter::new(20.0, 1));
+        let clone = client.clone();
+        let start = std::time::Instant::now();
+        let _ = client.get_game_info_for_id(1);
+        let _ = clone.get_game_info_for_id(1);
+        let _ = clone.get_game_info_for_id(1);
+        assert!(start.elapsed() >= std::time::Duration::from_millis(100));
+        let stats = client.rate_limiter().unwrap().stats();
+        assert_eq!(3, stats.requests);
+        assert_eq!(2, stats.throttled);
+        assert!(stats.total_wait >= std::time::Duration::from_millis(50));
+    }
+
+    #[test]
+    fn cached_response_test() {
+        let json = std::fs::read_to_string("testdata/grids/grid_by_id.json").unwrap();
+        let server = StubServer::start(vec![StubResponse::json(200, &json)]);
+        let mut client = test_client(&server);
+        client.set_cache(ResponseCache::new(std::time::Duration::from_secs(60), 10));
+        let first = client.get_grid_by_id(80200).unwrap();
+        let second = client.get_grid_by_id(80200).unwrap();
+        assert_eq!(first.id, second.id);
+        assert_eq!(1, server.requests().len());
+    }
+
+    #[test]
+    fn cache_revalidates_with_etag_test() {
+        let json = std::fs::read_to_string("testdata/grids/grid_by_id.json").unwrap();
+        let server = StubServer::start(vec![
+            StubResponse::json(200, &json).with_header("ETag", "\"abc\""),
+            StubResponse::json(304, ""),
+        ]);
+        let mut client = test_client(&server);
+        client.set_cache(ResponseCache::new(std::time::Duration::ZERO, 10));
+        client.get_grid_by_id(80200).unwrap();
+        let grid = client.get_grid_by_id(80200).unwrap();
+        assert_eq!(80200, grid.id);
+        let requests = server.requests();
+        assert_eq!(2, requests.len());
+        assert_eq!(None, requests[0].header("if-none-match"));
+        assert_eq!(Some("\"abc\""), requests[1].header("if-none-match"));
+    }
+
+    /// Answers revalidations with 304 Not Modified after clearing the cache,
+    /// as if the cached response was evicted while it was revalidated
+    struct EvictingTransport {
+        cache: ResponseCache,
+        if_none_match: Arc<std::sync::Mutex<Vec<Option<String>>>>,
+    }
+
+    impl EvictingTransport {
+        fn respond(&self, request: HttpRequest) -> Result<HttpResponse, Error> {
+            let etag = request
+                .headers
+                .iter()
+                .find(|(name, _)| name == "If-None-Match")
+                .map(|(_, value)| value.clone());
+            let revalidating = etag.is_some();
+            self.if_none_match.lock().unwrap().push(etag);
+            if revalidating {
+                self.cache.clear();
+                return Ok(HttpResponse {
+                    status: 304,
+                    headers: Vec::new(),
+                    body: Vec::new(),
+                });
+            }
+            Ok(HttpResponse {
+                status: 200,
+                headers: vec![("ETag".to_string(), "\"abc\"".to_string())],
+                body: std::fs::read("testdata/grids/grid_by_id.json").unwrap(),
+            })
+        }
+    }
+
+    impl BlockingTransport for EvictingTransport {
+        fn send(&self, request: HttpRequest) -> Result<HttpResponse, Error> {
+            self.respond(request)
+        }
+    }
+
+    #[test]
+    fn cache_refetches_evicted_response_test() {
+        let cache = ResponseCache::new(std::time::Duration::ZERO, 10);
+        let if_none_match = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let transport = EvictingTransport {
+            cache: cache.clone(),
+            if_none_match: if_none_match.clone(),
+        };
+        let mut client = Client::with_transport("my_auth_key", transport);
+        client.set_cache(cache);
+        client.get_grid_by_id(80200).unwrap();
+        let grid = client.get_grid_by_id(80200).unwrap();
+        assert_eq!(80200, grid.id);
+        assert_eq!(
+            vec![None, Some("\"abc\"".to_string()), None],
+            *if_none_match.lock().unwrap()
+        );
+    }
+
+    fn image_served_by(server: &StubServer, path: &str) -> Image {
+        let json = std::fs::read_to_string("testdata/grids/grid_by_id.json").unwrap();
+        let response: Response<Image> = serde_json::from_str(&json).unwrap();
+        let mut image = response.data.unwrap();
+        image.url = format!("{}{}", server.url, path);
+        image
+    }
+
+    fn png_response() -> StubResponse {
+        StubResponse {
+            status: 200,
+            headers: vec![("Content-Type".to_string(), "image/png".to_string())],
+            body: b"\x89PNG image".to_vec(),
+            disconnect: false,
+        }
+    }
+
+    #[test]
+    fn download_image_test() {
+        let server = StubServer::start(vec![png_response()]);
+        let client = test_client(&server);
+        let image = image_served_by(&server, "/grid.png");
+        let bytes = client.download_image(&image, Variant::Full).unwrap();
+        assert_eq!(b"\x89PNG image".to_vec(), bytes);
+        let requests = server.requests();
+        assert_eq!("/grid.png", requests[0].path);
+        assert_eq!(None, requests[0].header("authorization"));
+    }
+
+    #[test]
+    fn download_image_to_path_test() {
+        let server = StubServer::start(vec![png_response()]);
+        let client = test_client(&server);
+        let image = image_served_by(&server, "/grid.png");
+        let path = std::env::temp_dir().join(format!(
+            "steamgriddb_api_blocking_download_{}.png",
+            std::process::id()
+        ));
+        let downloaded = client
+            .download_image_to_path(&image, Variant::Full, &path, &DownloadOptions::default())
+            .unwrap();
+        assert_eq!(10, downloaded.size);
+        assert_eq!("image/png", downloaded.content_type);
+        assert_eq!(b"\x89PNG image".to_vec(), std::fs::read(&path).unwrap());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn download_image_to_path_keeps_nothing_on_error_test() {
+        let server = StubServer::start(vec![StubResponse::json(200, "{}")]);
+        let client = test_client(&server);
+        let image = image_served_by(&server, "/grid.png");
+        let path = std::env::temp_dir().join(format!(
+            "steamgriddb_api_blocking_download_error_{}.png",
+            std::process::id()
+        ));
+        let result = client.download_image_to_path(
+            &image,
+            Variant::Full,
+            &path,
+            &DownloadOptions::default(),
+        );
+        assert!(matches!(result, Err(Error::ContentType { .. })));
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn download_image_to_path_checks_crc32_test() {
+        let server = StubServer::start(vec![png_response(), png_response()]);
+        let client = test_client(&server);
+        let image = image_served_by(&server, "/grid.png");
+        let path = std::env::temp_dir().join(format!(
+            "steamgriddb_api_blocking_download_crc32_{}.png",
+            std::process::id()
+        ));
+        let options = DownloadOptions {
+            expected_crc32: Some(0),
+            ..Default::default()
+        };
+        let result = client.download_image_to_path(&image, Variant::Full, &path, &options);
+        let actual = crate::crc32::crc32(b"\x89PNG image");
+        assert!(matches!(
+            result,
+            Err(Error::Checksum { expected: 0, actual: a }) if a == actual
+        ));
+        assert!(!path.exists());
+        let options = DownloadOptions {
+            expected_crc32: Some(actual),
+            ..Default::default()
+        };
+        let downloaded = client
+            .download_image_to_path(&image, Variant::Full, &path, &options)
+            .unwrap();
+        assert_eq!(actual, downloaded.crc32);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn install_artwork_refuses_webp_test() {
+        let server = StubServer::start(vec![]);
+        let client = test_client(&server);
+        let mut image = image_served_by(&server, "/grid.webp");
+        image.mime = crate::images::MimeTypes::from("image/webp".to_string());
+        let folder = GridFolder::new(std::env::temp_dir().join(format!(
+            "steamgriddb_api_blocking_install_webp_{}",
+            std::process::id()
+        )));
+        let result = client.install_artwork(&folder, 504230, ArtworkKind::GridPortrait, &image);
+        assert!(matches!(result, Err(Error::ContentType { .. })));
+        assert!(server.requests().is_empty());
+        assert!(!folder.path().exists());
+    }
+
+    #[test]
+    fn install_artwork_test() {
+        let server = StubServer::start(vec![png_response()]);
+        let client = test_client(&server);
+        let image = image_served_by(&server, "/grid.png");
+        let folder = GridFolder::new(std::env::temp_dir().join(format!(
+            "steamgriddb_api_blocking_install_{}",
+            std::process::id()
+        )));
+        folder.create().unwrap();
+        std::fs::write(
+            folder.file_path(504230, ArtworkKind::GridPortrait, "jpg"),
+            b"old",
+        )
+        .unwrap();
+        std::fs::write(folder.file_path(504230, ArtworkKind::Hero, "jpg"), b"old").unwrap();
+
+        let kind = ArtworkKind::for_image(&ImageKind::Grid, &image);
+        assert_eq!(ArtworkKind::GridPortrait, kind);
+        let installed = client
+            .install_artwork(&folder, 504230, kind, &image)
+            .unwrap();
+        assert_eq!(folder.file_path(504230, kind, "png"), installed.path);
+        let kinds: Vec<(ArtworkKind, bool)> = folder
+            .installed_for(504230)
+            .unwrap()
+            .into_iter()
+            .map(|artwork| (artwork.kind, artwork.path == installed.path))
+            .collect();
+        assert_eq!(
+            vec![
+                (ArtworkKind::GridPortrait, true),
+                (ArtworkKind::Hero, false)
+            ],
+            kinds
+        );
+        std::fs::remove_dir_all(folder.path().parent().unwrap().parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn check_official_steam_images_test() {
+        let base = "https://cdn.cloudflare.steamstatic.com/steam/apps/361420";
+        let transport = FakeTransport::default()
+            .with_response(
+                &format!("{}/header.jpg", base),
+                200,
+                &[("Content-Type", "image/jpeg"), ("Content-Length", "84564")],
+            )
+            .with_response(
+                &format!("{}/library_600x900.jpg", base),
+                200,
+                &[("Content-Type", "image/jpeg")],
+            )
+            .with_response(&format!("{}/logo.png", base), 200, &[]);
+        let requests = transport.requests();
+        let client = Client::with_transport("my_auth_key", transport);
+        let images = client.check_official_steam_images("361420").unwrap();
+
+        assert!(images.header.exists);
+        assert_eq!(Some(84564), images.header.content_length);
+        assert_eq!(Some("image/jpeg".to_string()), images.header.content_type);
+        assert!(images.capsule.exists);
+        assert_eq!(format!("{}/library_600x900.jpg", base), images.capsule.url);
+        assert!(!images.hero.exists);
+        assert_eq!(format!("{}/library_hero.jpg", base), images.hero.url);
+        assert!(images.logo.exists);
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(5, requests.len());
+        assert!(requests
+            .iter()
+            .all(|request| request.method == Method::Head));
+        assert!(requests.iter().all(|request| request
+            .headers
+            .iter()
+            .all(|(name, _)| name != "Authorization")));
+    }
+
+    #[test]
+    fn resolve_artwork_by_steam_app_id_test() {
+        let policy = ArtworkPolicy::default();
+        let images_url =
+            |query: &QueryType| get_images_by_game_id_url(DEFAULT_BASE_URL, 13136, query);
+        let official = SteamStaticUrls::new("504230");
+        let transport = FakeTransport::default()
+            .with_fixture(
+                "https://www.steamgriddb.com/api/v2/games/steam/504230",
+                "testdata/games/game.json",
+            )
+            .with_fixture(
+                &images_url(&policy.grid[0]),
+                "testdata/grids/grids_for_single_id.json",
+            )
+            .with_json(
+                &images_url(&policy.wide_grid[0]),
+                200,
+                r#"{"success":true,"data":[]}"#,
+            )
+            .with_response(&official.header, 200, &[("Content-Type", "image/jpeg")])
+            .with_fixture(&images_url(&policy.hero[1]), "testdata/heroes/heroes.json")
+            .with_fixture(
+                &images_url(&policy.icon[0]),
+                "testdata/icons/icons_for_single_id.json",
+            );
+        let client = Client::with_transport("my_auth_key", transport);
+        let resolver = ArtworkResolver::with_policy(&client, policy);
+        let artwork = resolver.resolve(&GameIdentity::SteamAppId(504230)).unwrap();
+
+        assert_eq!(Some(13136), artwork.game_id);
+        match artwork.get(ArtworkKind::GridPortrait) {
+            Some(ResolvedArtwork::SteamGridDb(image)) => assert_eq!(80200, image.id),
+            _ => panic!("Expected a SteamGridDB grid"),
+        }
+        let header = ResolvedArtwork::Official {
+            asset: SteamAsset::Header,
+            url: official.header.clone(),
+        };
+        assert_eq!(Some(header), artwork.wide_grid);
+        match &artwork.hero {
+            Some(ResolvedArtwork::SteamGridDb(image)) => assert_eq!(25973, image.id),
+            _ => panic!("Expected a SteamGridDB hero, from the second query"),
+        }
+        assert_eq!(None, artwork.logo);
+        assert!(matches!(
+            artwork.icon,
+            Some(ResolvedArtwork::SteamGridDb(_))
+        ));
+    }
+
+    #[test]
+    fn resolve_artwork_by_name_test() {
+        let policy = ArtworkPolicy {
+            official_fallback: false,
+            ..Default::default()
+        };
+        let transport = FakeTransport::default()
+            .with_fixture(
+                "https://www.steamgriddb.com/api/v2/search/autocomplete/Assassin%27s%20Creed",
+                "testdata/search/search.json",
+            )
+            .with_fixture(
+                &get_images_by_game_id_url(DEFAULT_BASE_URL, 1451, &policy.hero[0]),
+                "testdata/heroes/heroes.json",
+            );
+        let requests = transport.requests();
+        let client = Client::with_transport("my_auth_key", transport);
+        let resolver = ArtworkResolver::with_policy(&client, policy);
+        let identity = GameIdentity::Name("Assassin's Creed".to_string());
+        let hero = resolver.resolve_kind(&identity, ArtworkKind::Hero).unwrap();
+        assert!(matches!(hero, Some(ResolvedArtwork::SteamGridDb(_))));
+
+        let artwork = resolver.resolve(&identity).unwrap();
+        assert_eq!(Some(1451), artwork.game_id);
+        assert_eq!(hero, artwork.hero);
+        assert_eq!(None, artwork.grid);
+        assert_eq!(None, artwork.logo);
+        let requests = requests.lock().unwrap();
+        assert!(requests.iter().all(|request| request.method == Method::Get));
+    }
+
+    #[test]
+    fn resolve_artwork_for_unknown_game_test() {
+        let steam_app_id = "361420";
+        let official = SteamStaticUrls::new(steam_app_id);
+        let transport = FakeTransport::default()
+            .with_response(&official.logo, 200, &[("Content-Type", "image/png")])
+            .with_response(&official.hero, 200, &[]);
+        let client = Client::with_transport("my_auth_key", transport);
+        let resolver = ArtworkResolver::new(&client);
+        let identity = GameIdentity::PlatformId(Platform::Steam, steam_app_id.to_string());
+        let artwork = resolver.resolve(&identity).unwrap();
+        assert_eq!(None, artwork.game_id);
+        assert_eq!(None, artwork.grid);
+        assert_eq!(
+            Some(official.hero.as_str()),
+            artwork.hero.as_ref().map(|hero| hero.url())
+        );
+        assert_eq!(
+            Some(official.logo.as_str()),
+            artwork.logo.as_ref().map(|logo| logo.url())
+        );
+        assert_eq!(None, artwork.icon);
+    }
+
+    #[test]
+    fn resolve_artwork_official_check_failed_test() {
+        let steam_app_id = "361420";
+        let official = SteamStaticUrls::new(steam_app_id);
+        let transport = FakeTransport::default()
+            .with_response(&official.logo, 200, &[("Content-Type", "image/png")])
+            .with_response(&official.hero, 503, &[]);
+        let mut client = Client::with_transport("my_auth_key", transport);
+        client.set_retry_policy(RetryPolicy::none());
+        let resolver = ArtworkResolver::new(&client);
+        let identity = GameIdentity::PlatformId(Platform::Steam, steam_app_id.to_string());
+        let artwork = resolver.resolve(&identity).unwrap();
+        assert_eq!(None, artwork.hero);
+        assert_eq!(
+            Some(official.logo.as_str()),
+            artwork.logo.as_ref().map(|logo| logo.url())
+        );
+    }
+
+    #[test]
+    fn get_game_by_platform_id_test() {
+        let transport = FakeTransport::default().with_fixture(
+            "https://www.steamgriddb.com/api/v2/games/egs/Salt",
+            "testdata/games/game.json",
+        );
+        let client = Client::with_transport("my_auth_key", transport);
+        let game = client
+            .get_game_by_platform_id(&Platform::EpicGameStore, "Salt")
+            .unwrap();
+        assert_eq!(13136, game.id);
+        assert_eq!(vec![Platform::Steam, Platform::EpicGameStore], game.types);
+
+        match client.get_game_by_platform_id(&Platform::GoG, "1") {
+            Err(Error::Api(error)) => assert_eq!(Some(404), error.status),
+            _ => panic!("Expected an api error"),
+        }
+    }
+
+    #[test]
+    fn get_game_with_platform_data_test() {
+        let transport = FakeTransport::default().with_fixture(
+            "https://www.steamgriddb.com/api/v2/games/id/13136?platformdata=steam,gog",
+            "testdata/games/game_with_platform_data.json",
+        );
+        let client = Client::with_transport("my_auth_key", transport);
+        let game = client
+            .get_game(&GameLookup::Id(13136), &[Platform::Steam, Platform::GoG])
+            .unwrap();
+        assert_eq!(Some("504230"), game.platform_id(&Platform::Steam));
+        assert_eq!(Some("1207658924"), game.platform_id(&Platform::GoG));
+    }
+
+    #[test]
+    fn search_best_match_test() {
+        let transport = FakeTransport::default().with_fixture(
+            "https://www.steamgriddb.com/api/v2/search/autocomplete/Assassins%20Creed%203",
+            "testdata/search/search.json",
+        );
+        let client = Client::with_transport("my_auth_key", transport);
+        let matcher = TitleMatcher::new();
+        let found = client
+            .search_best_match("Assassins Creed 3", &matcher)
+            .unwrap()
+            .unwrap();
+        assert_eq!(1937, found.result.id);
+        assert_eq!(1.0, found.confidence);
+        let strict = matcher.year(2013).threshold(0.96);
+        let found = client
+            .search_best_match("Assassins Creed 3", &strict)
+            .unwrap();
+        assert_eq!(None, found);
+    }
+
+    #[test]
+    fn download_image_to_stops_at_max_size_test() {
+        let server = StubServer::start(vec![png_response(), png_response()]);
+        let client = test_client(&server);
+        let image = image_served_by(&server, "/grid.png");
+        let calls = std::sync::Mutex::new(Vec::new());
+        let progress =
+            |received: u64, total: Option<u64>| calls.lock().unwrap().push((received, total));
+        let options = DownloadOptions {
+            max_size: Some(4),
+            progress: Some(&progress),
+            ..Default::default()
+        };
+        let mut bytes = Vec::new();
+        let result = client.download_image_to(&image, Variant::Full, &mut bytes, &options);
+        assert!(matches!(
+            result,
+            Err(Error::TooLarge { limit: 4, size: 10 })
+        ));
+        assert!(bytes.is_empty());
+        assert!(calls.lock().unwrap().is_empty());
+
+        let options = DownloadOptions {
+            progress: Some(&progress),
+            ..Default::default()
+        };
+        client
+            .download_image_to(&image, Variant::Full, &mut bytes, &options)
+            .unwrap();
+        assert_eq!(b"\x89PNG image".to_vec(), bytes);
+        let calls = calls.lock().unwrap();
+        assert_eq!(Some(&(0, Some(10))), calls.first());
+        assert_eq!(Some(&(10, Some(10))), calls.last());
+    }
+}
@@ -0,0 +1,462 @@
+//! Caching api responses.
+//!
+//! A [`ResponseCache`] keeps the bodies of successful GET responses, keyed by their url,
+//! like the urls made by [`crate::images::get_images_by_game_id_url`] or [`crate::search::get_search_url`].
+//! Fresh responses are answered from the cache without calling the api.
+//! When a stale response has an ETag, the client asks the server if it has changed using `If-None-Match`,
+//! and keeps using the cached body if it has not.
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::Error, files::write_atomically, response::is_success_response, transport::HttpResponse,
+};
+
+/// An in-memory cache of api responses, that can be persisted to a JSON file.
+///
+/// The cache holds up to `capacity` responses, and evicts the least recently used when it is full.
+/// Clones of the cache share the same entries.
+///
+/// ### Examples
+/// ```
+/// use std::time::Duration;
+/// use steamgriddb_api::cache::ResponseCache;
+/// use steamgriddb_api::client::Client;
+/// # fn main() {
+/// let mut client = Client::new("my_auth_key");
+/// client.set_cache(ResponseCache::new(Duration::from_secs(60 * 60), 1000));
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ResponseCache {
+    state: Arc<Mutex<CacheState>>,
+}
+
+#[derive(Debug)]
+struct CacheState {
+    ttl: Duration,
+    capacity: usize,
+    path: Option<PathBuf>,
+    entries: HashMap<String, CacheEntry>,
+    /// Are there changes that have not been saved to the file
+    dirty: bool,
+    /// Increased on every use, to find the least recently used entry
+    clock: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    body: String,
+    etag: Option<String>,
+    /// Seconds since the unix epoch
+    stored_at: u64,
+    /// The value of the cache clock when the entry was last used
+    #[serde(default)]
+    last_used: u64,
+}
+
+/// The result of looking up a request in the cache
+pub(crate) enum CacheLookup {
+    /// The cached response is fresh, and can be used without calling the api
+    Fresh(HttpResponse),
+    /// The cached response is stale, but can be revalidated with its ETag
+    Revalidate(String),
+    /// There is no usable cached response
+    Miss,
+}
+
+impl ResponseCache {
+    /// Create an in-memory cache, where responses are fresh for `ttl`, holding up to `capacity` responses.
+    pub fn new(ttl: Duration, capacity: usize) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(CacheState {
+                ttl,
+                capacity,
+                path: None,
+                entries: HashMap::new(),
+                dirty: false,
+                clock: 0,
+            })),
+        }
+    }
+
+    /// Create a cache that is loaded from, and saved to, a JSON file.
+    ///
+    /// Stored responses are only kept in memory, until the cache is saved with [`ResponseCache::save`].
+    /// The file is created when the cache is first saved, if it does not exist.
+    pub fn with_file<P>(ttl: Duration, capacity: usize, path: P) -> Result<Self, Error>
+    where
+        P: Into<PathBuf>,
+    {
+        let path = path.into();
+        let entries = if path.exists() {
+            let content = fs::read(&path)?;
+            serde_json::from_slice(&content).map_err(|source| Error::Deserialize {
+                source,
+                body: crate::error::body_snippet(&content),
+            })?
+        } else {
+            HashMap::new()
+        };
+        let cache = Self::new(ttl, capacity);
+        {
+            let mut state = cache.state.lock().unwrap();
+            state.entries = entries;
+            state.path = Some(path);
+            state.restore_clock();
+            state.evict();
+        }
+        Ok(cache)
+    }
+
+    /// Save the cache to its file, if it has one and it changed since it was last saved.
+    ///
+    /// ### Examples
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use steamgriddb_api::cache::ResponseCache;
+    /// use steamgriddb_api::client::Client;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let cache = ResponseCache::with_file(Duration::from_secs(60 * 60), 1000, "cache.json")?;
+    /// let mut client = Client::new("my_auth_key");
+    /// client.set_cache(cache.clone());
+    /// let results = client.search("Celeste").await?;
+    /// cache.save()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn save(&self) -> Result<(), Error> {
+        // Only the serializing happens while the cache is locked, not the writing
+        let (path, json) = {
+            let mut state = self.state.lock().unwrap();
+            let path = match &state.path {
+                Some(path) if state.dirty => path.clone(),
+                _ => return Ok(()),
+            };
+            let json = serde_json::to_vec(&state.entries).map_err(std::io::Error::from)?;
+            state.dirty = false;
+            (path, json)
+        };
+        let result = write_atomically(&path, &json);
+        if result.is_err() {
+            self.state.lock().unwrap().dirty = true;
+        }
+        result
+    }
+
+    /// Are there changes that have not been saved to the file
+    pub fn has_unsaved_changes(&self) -> bool {
+        let state = self.state.lock().unwrap();
+        state.path.is_some() && state.dirty
+    }
+
+    /// The amount of cached responses
+    pub fn len(&self) -> usize {
+        self.state.lock().unwrap().entries.len()
+    }
+
+    /// Is the cache empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Remove the cached response for an url
+    pub fn remove(&self, url: &str) {
+        let mut state = self.state.lock().unwrap();
+        if state.entries.remove(url).is_some() {
+            state.dirty = true;
+        }
+    }
+
+    /// Remove all cached responses
+    pub fn clear(&self) {
+        let mut state = self.state.lock().unwrap();
+        if !state.entries.is_empty() {
+            state.entries.clear();
+            state.dirty = true;
+        }
+    }
+
+    pub(crate) fn lookup(&self, url: &str) -> CacheLookup {
+        self.lookup_at(url, SystemTime::now())
+    }
+
+    fn lookup_at(&self, url: &str, now: SystemTime) -> CacheLookup {
+        let mut state = self.state.lock().unwrap();
+        let ttl = state.ttl;
+        let tick = state.tick();
+        let entry = match state.entries.get_mut(url) {
+            Some(entry) => entry,
+            None => return CacheLookup::Miss,
+        };
+        entry.last_used = tick;
+        let age = Duration::from_secs(now_secs(now).saturating_sub(entry.stored_at));
+        if age < ttl {
+            CacheLookup::Fresh(entry.to_response())
+        } else {
+            match &entry.etag {
+                Some(etag) => CacheLookup::Revalidate(etag.clone()),
+                None => CacheLookup::Miss,
+            }
+        }
+    }
+
+    /// Store the response for an url, and get the response to use.
+    ///
+    /// Only successful api responses are stored, not error statuses or bodies with `"success": false`.
+    /// A 304 Not Modified response is replaced by the cached response, if it is still cached.
+    pub(crate) fn store(&self, url: &str, response: HttpResponse) -> HttpResponse {
+        self.store_at(url, response, SystemTime::now())
+    }
+
+    fn store_at(&self, url: &str, response: HttpResponse, now: SystemTime) -> HttpResponse {
+        let mut state = self.state.lock().unwrap();
+        let tick = state.tick();
+        let stored_at = now_secs(now);
+        let response = if response.status == 304 {
+            match state.entries.get_mut(url) {
+                Some(entry) => {
+                    entry.stored_at = stored_at;
+                    entry.last_used = tick;
+                    entry.to_response()
+                }
+                None => return response,
+            }
+        } else if is_success_response(response.status, &response.body) {
+            let body = match String::from_utf8(response.body.clone()) {
+                Ok(body) => body,
+                Err(_) => return response,
+            };
+            let entry = CacheEntry {
+                body,
+                etag: response.header("ETag").map(|etag| etag.to_string()),
+                stored_at,
+                last_used: tick,
+            };
+            state.entries.insert(url.to_string(), entry);
+            state.evict();
+            response
+        } else {
+            return response;
+        };
+        state.dirty = true;
+        response
+    }
+}
+
+impl CacheState {
+    fn tick(&mut self) -> u64 {
+        self.clock += 1;
+        self.clock
+    }
+
+    /// Number the loaded entries from the least to the most recently used, and continue the clock after them.
+    ///
+    /// Entries saved without a last use, by older versions, are ordered by when they were stored.
+    fn restore_clock(&mut self) {
+        let mut entries: Vec<&mut CacheEntry> = self.entries.values_mut().collect();
+        entries.sort_by_key(|entry| (entry.last_used, entry.stored_at));
+        for (index, entry) in entries.into_iter().enumerate() {
+            entry.last_used = index as u64 + 1;
+        }
+        self.clock = self.entries.len() as u64;
+    }
+
+    fn evict(&mut self) {
+        while self.entries.len() > self.capacity {
+            let oldest = self
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(url, _)| url.clone());
+            match oldest {
+                Some(url) => self.entries.remove(&url),
+                None => return,
+            };
+        }
+    }
+}
+
+impl CacheEntry {
+    fn to_response(&self) -> HttpResponse {
+        let mut headers = vec![("Content-Type".to_string(), "application/json".to_string())];
+        if let Some(etag) = &self.etag {
+            headers.push(("ETag".to_string(), etag.clone()));
+        }
+        HttpResponse {
+            status: 200,
+            headers,
+            body: self.body.as_bytes().to_vec(),
+        }
+    }
+}
+
+fn now_secs(now: SystemTime) -> u64 {
+    now.duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn ok(body: &str, etag: Option<&str>) -> HttpResponse {
+        HttpResponse {
+            status: 200,
+            headers: etag
+                .map(|etag| vec![("etag".to_string(), etag.to_string())])
+                .unwrap_or_default(),
+            body: body.as_bytes().to_vec(),
+        }
+    }
+
+    fn not_modified() -> HttpResponse {
+        HttpResponse {
+            status: 304,
+            headers: Vec::new(),
+            body: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn lookup_fresh_and_expired_test() {
+        let cache = ResponseCache::new(Duration::from_secs(60), 10);
+        let now = SystemTime::now();
+        assert!(matches!(cache.lookup_at("a", now), CacheLookup::Miss));
+        cache.store_at("a", ok(r#"{"success":true}"#, None), now);
+        match cache.lookup_at("a", now + Duration::from_secs(30)) {
+            CacheLookup::Fresh(response) => {
+                assert_eq!(br#"{"success":true}"#.to_vec(), response.body)
+            }
+            _ => panic!("Expected a fresh response"),
+        }
+        assert!(matches!(
+            cache.lookup_at("a", now + Duration::from_secs(61)),
+            CacheLookup::Miss
+        ));
+    }
+
+    #[test]
+    fn revalidate_with_etag_test() {
+        let cache = ResponseCache::new(Duration::from_secs(60), 10);
+        let now = SystemTime::now();
+        cache.store_at("a", ok(r#"{"success":true,"data":1}"#, Some("\"v1\"")), now);
+        let later = now + Duration::from_secs(120);
+        match cache.lookup_at("a", later) {
+            CacheLookup::Revalidate(etag) => assert_eq!("\"v1\"", etag),
+            _ => panic!("Expected a revalidation"),
+        }
+        let response = cache.store_at("a", not_modified(), later);
+        assert_eq!(200, response.status);
+        assert_eq!(br#"{"success":true,"data":1}"#.to_vec(), response.body);
+        assert!(matches!(cache.lookup_at("a", later), CacheLookup::Fresh(_)));
+    }
+
+    #[test]
+    fn errors_are_not_cached_test() {
+        let cache = ResponseCache::new(Duration::from_secs(60), 10);
+        let response = HttpResponse {
+            status: 500,
+            headers: Vec::new(),
+            body: Vec::new(),
+        };
+        cache.store("a", response);
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn evicts_least_recently_used_test() {
+        let cache = ResponseCache::new(Duration::from_secs(60), 2);
+        let now = SystemTime::now();
+        cache.store_at("a", ok(r#"{"success":true,"data":"a"}"#, None), now);
+        cache.store_at("b", ok(r#"{"success":true,"data":"b"}"#, None), now);
+        cache.lookup_at("a", now);
+        cache.store_at("c", ok(r#"{"success":true,"data":"c"}"#, None), now);
+        assert_eq!(2, cache.len());
+        assert!(matches!(cache.lookup_at("a", now), CacheLookup::Fresh(_)));
+        assert!(matches!(cache.lookup_at("b", now), CacheLookup::Miss));
+        assert!(matches!(cache.lookup_at("c", now), CacheLookup::Fresh(_)));
+    }
+
+    #[test]
+    fn persist_to_file_test() {
+        let path = std::env::temp_dir().join(format!(
+            "steamgriddb_api_cache_test_{}.json",
+            std::process::id()
+        ));
+        let _ = fs::remove_file(&path);
+        let cache = ResponseCache::with_file(Duration::from_secs(60), 10, &path).unwrap();
+        cache.store("a", ok(r#"{"success":true,"data":1}"#, Some("\"v1\"")));
+        // Storing a response does not write the file
+        assert!(!path.exists());
+        assert!(cache.has_unsaved_changes());
+        cache.save().unwrap();
+        assert!(!cache.has_unsaved_changes());
+
+        let loaded = ResponseCache::with_file(Duration::from_secs(60), 10, &path).unwrap();
+        match loaded.lookup("a") {
+            CacheLookup::Fresh(response) => {
+                assert_eq!(br#"{"success":true,"data":1}"#.to_vec(), response.body);
+                assert_eq!(Some("\"v1\""), response.header("etag"));
+            }
+            _ => panic!("Expected a fresh response"),
+        }
+        assert!(!loaded.has_unsaved_changes());
+        loaded.remove("a");
+        assert!(loaded.has_unsaved_changes());
+        loaded.save().unwrap();
+        let reloaded = ResponseCache::with_file(Duration::from_secs(60), 10, &path).unwrap();
+        assert!(reloaded.is_empty());
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn failed_api_responses_are_not_cached_test() {
+        let cache = ResponseCache::new(Duration::from_secs(60), 10);
+        cache.store(
+            "a",
+            ok(r#"{"success":false,"errors":["Game not found"]}"#, None),
+        );
+        cache.store("b", ok("not json", None));
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn not_modified_without_cached_response_test() {
+        let cache = ResponseCache::new(Duration::from_secs(60), 10);
+        let response = cache.store("a", not_modified());
+        assert_eq!(304, response.status);
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn persist_keeps_least_recently_used_order_test() {
+        let path = std::env::temp_dir().join(format!(
+            "steamgriddb_api_cache_lru_test_{}.json",
+            std::process::id()
+        ));
+        let _ = fs::remove_file(&path);
+        let now = SystemTime::now();
+        let cache = ResponseCache::with_file(Duration::from_secs(60), 2, &path).unwrap();
+        cache.store_at("a", ok(r#"{"success":true,"data":"a"}"#, None), now);
+        cache.store_at("b", ok(r#"{"success":true,"data":"b"}"#, None), now);
+        cache.lookup_at("a", now);
+        cache.save().unwrap();
+
+        let loaded = ResponseCache::with_file(Duration::from_secs(60), 2, &path).unwrap();
+        loaded.store_at("c", ok(r#"{"success":true,"data":"c"}"#, None), now);
+        assert!(matches!(loaded.lookup_at("a", now), CacheLookup::Fresh(_)));
+        assert!(matches!(loaded.lookup_at("b", now), CacheLookup::Miss));
+        fs::remove_file(&path).unwrap();
+    }
+}
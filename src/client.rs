@@ -1,22 +1,52 @@
+use std::{future::Future, path::Path, sync::Arc};
+
 use serde::de::DeserializeOwned;
-use crate::response::Response;
+use tokio::io::AsyncWrite;
 
 use crate::{
-    games::{get_game_by_steam_app_id_url, get_gameinfo_by_game_id_url, GameInfo},
+    cache::{CacheLookup, ResponseCache},
+    download::{stream_download, DownloadOptions, DownloadedImage, Variant},
+    error::Error,
+    files::AtomicFile,
+    games::{
+        get_game_by_platform_id_url, get_game_by_steam_app_id_url, get_game_url,
+        get_gameinfo_by_game_id_url, GameInfo, GameLookup,
+    },
     images::{
-        get_images_by_game_id_url, get_images_by_game_ids_url, get_images_by_platform_id_url,
-        get_images_by_platform_ids_url, Image, InnerImagesMultipleIdsResponse,
-        InnerImagesSingleIdResponse,
+        get_delete_images_url, get_image_by_id_url, get_images_by_game_id_url,
+        get_images_by_game_ids_url, get_images_by_platform_id_url, get_images_by_platform_ids_url,
+        get_vote_url, Image, InnerEmptyResponse, InnerImageResponse,
+        InnerImagesMultipleIdsResponse, InnerImagesSingleIdResponse, PagedImages,
+        PagedImagesTarget, Vote,
+    },
+    matcher::{TitleMatch, TitleMatcher},
+    query_parameters::{ImageKind, Platform, QueryType},
+    rate_limit::RateLimiter,
+    resolver::{
+        direct_target, found, official_asset, ArtworkPolicy, GameIdentity, ResolvedArtwork,
+        ResolvedArtworkSet,
+    },
+    response::{
+        parse_response, response_to_data, response_to_data_flat, response_to_result_all,
+        response_to_success, single_result_to_result_all, Response, SteamGridDbResult,
     },
-    query_parameters::{Platform, QueryType},
-    response::{response_to_result, response_to_result_flat, SteamGridDbResult},
+    retry::RetryPolicy,
     search::{get_search_url, InnerSearchResult, SearchResult},
-    steam_static::SteamStaticUrls,
+    steam::grid::{ArtworkKind, GridFolder, InstalledArtwork},
+    steam_static::{fallback_urls, CheckedSteamStaticUrls, StaticAsset, SteamStaticUrls},
+    transport::{
+        BodyStream, HttpRequest, HttpResponse, Method, ResponseHead, StreamingResponse, Transport,
+    },
+    upload::{get_upload_url, UploadForm, UploadType},
+    DEFAULT_BASE_URL,
 };
 
 /// This Client provides a convenient way to interact with the SteamGrid API.
 ///
-/// The client calls the API using the [reqwest](https://crates.io/crates/reqwest) crate and parses the result using the [serde](https://crates.io/crates/serde) crate.
+/// By default the client calls the API using the [reqwest](https://crates.io/crates/reqwest) crate, and any other HTTP client can be used through a [`Transport`].
+/// The results are parsed using the [serde](https://crates.io/crates/serde) crate.
+///
+/// Clones of the client share the same transport.
 ///
 /// ### Examples
 ///
@@ -34,13 +64,14 @@ use crate::{
 ///     Ok(())
 ///  }
 /// ```
+#[derive(Clone)]
 pub struct Client {
     auth_key: String,
     base_url: String,
-    #[cfg(feature = "async")]
-    client: reqwest::Client,
-    #[cfg(feature = "blocking")]
-    client: reqwest::blocking::Client,
+    transport: Arc<dyn Transport>,
+    retry_policy: RetryPolicy,
+    rate_limiter: Option<RateLimiter>,
+    cache: Option<ResponseCache>,
 }
 
 impl Client {
@@ -54,22 +85,37 @@ impl Client {
     /// assert_eq!("my_auth_key", client.get_auth_key());
     /// # }
     /// ```
+    #[cfg(feature = "reqwest")]
     pub fn new<S>(auth_key: S) -> Self
     where
         S: Into<String>,
     {
-        let default_base_url = "https://www.steamgriddb.com/api/v2";
-
-        #[cfg(feature = "async")]
-        let client = reqwest::Client::new();
-
-        #[cfg(feature = "blocking")]
-        let client = reqwest::blocking::Client::new();
+        Self::with_transport(auth_key, crate::transport::ReqwestTransport::new())
+    }
 
+    /// Creates a new client with the given auth key, that sends its requests using the given transport.
+    ///
+    /// ### Examples
+    /// ```
+    /// use steamgriddb_api::client::Client;
+    /// use steamgriddb_api::transport::ReqwestTransport;
+    /// # fn main() {
+    /// let client = Client::with_transport("my_auth_key", ReqwestTransport::new());
+    /// assert_eq!("my_auth_key", client.get_auth_key());
+    /// # }
+    /// ```
+    pub fn with_transport<S, T>(auth_key: S, transport: T) -> Self
+    where
+        S: Into<String>,
+        T: Transport + 'static,
+    {
         Self {
             auth_key: auth_key.into(),
-            base_url: default_base_url.to_owned(),
-            client,
+            base_url: DEFAULT_BASE_URL.to_owned(),
+            transport: Arc::new(transport),
+            retry_policy: RetryPolicy::default(),
+            rate_limiter: None,
+            cache: None,
         }
     }
 
@@ -111,6 +157,93 @@ impl Client {
         self.base_url.as_str()
     }
 
+    /// Sets the policy for retrying requests that failed because of rate limiting, server errors or connection problems.
+    ///
+    /// By default requests are retried up to 3 times, see [`RetryPolicy::default`].
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use steamgriddb_api::client::Client;
+    /// use steamgriddb_api::retry::RetryPolicy;
+    /// # fn main() {
+    /// let mut client = Client::new("my_auth_key");
+    /// client.set_retry_policy(RetryPolicy::none());
+    /// assert_eq!(1, client.retry_policy().max_attempts);
+    /// # }
+    /// ```
+    pub fn set_retry_policy(&mut self, retry_policy: RetryPolicy) {
+        self.retry_policy = retry_policy;
+    }
+
+    /// Gets the policy for retrying requests.
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.retry_policy
+    }
+
+    /// Limits the rate of requests sent by this client.
+    ///
+    /// The limiter is shared by the clones of the client, and can also be shared with other clients by cloning it.
+    /// Each attempt of a request, including retries, waits for the limiter.
+    /// By default there is no limit.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use steamgriddb_api::client::Client;
+    /// use steamgriddb_api::rate_limit::RateLimiter;
+    /// # fn main() {
+    /// let mut client = Client::new("my_auth_key");
+    /// client.set_rate_limiter(RateLimiter::new(5.0, 10));
+    /// assert_eq!(0, client.rate_limiter().unwrap().stats().requests);
+    /// # }
+    /// ```
+    pub fn set_rate_limiter(&mut self, rate_limiter: RateLimiter) {
+        self.rate_limiter = Some(rate_limiter);
+    }
+
+    /// Removes the rate limit of this client.
+    pub fn clear_rate_limiter(&mut self) {
+        self.rate_limiter = None;
+    }
+
+    /// Gets the rate limiter of this client, if it has one.
+    pub fn rate_limiter(&self) -> Option<&RateLimiter> {
+        self.rate_limiter.as_ref()
+    }
+
+    /// Caches the responses of GET requests made by this client, like searches, game info and image lists.
+    ///
+    /// The cache is shared by the clones of the client.
+    /// Responses are keyed by their url only, so a cache should not be shared between clients with different base urls.
+    /// By default nothing is cached.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use steamgriddb_api::client::Client;
+    /// use steamgriddb_api::cache::ResponseCache;
+    /// # fn main() {
+    /// let mut client = Client::new("my_auth_key");
+    /// client.set_cache(ResponseCache::new(Duration::from_secs(60 * 60), 1000));
+    /// assert!(client.cache().unwrap().is_empty());
+    /// # }
+    /// ```
+    pub fn set_cache(&mut self, cache: ResponseCache) {
+        self.cache = Some(cache);
+    }
+
+    /// Stops caching responses in this client.
+    pub fn disable_cache(&mut self) {
+        self.cache = None;
+    }
+
+    /// Gets the response cache of this client, if it has one.
+    pub fn cache(&self) -> Option<&ResponseCache> {
+        self.cache.as_ref()
+    }
+
     /// Gets the auth key for the client.
     ///
     /// ### Examples
@@ -178,29 +311,18 @@ impl Client {
     /// # Ok(())
     /// # }
     /// ```
-    #[cfg(feature = "async")]
     pub async fn get_images_for_id(
         &self,
         game_id: usize,
         query: &QueryType<'_>,
-    ) -> Result<Vec<Image>, Box<dyn std::error::Error>> {
+    ) -> Result<Vec<Image>, Error> {
         let url = get_images_by_game_id_url(self.base_url.as_str(), game_id, query);
         let response = self
             .make_request::<InnerImagesSingleIdResponse>(url.as_str())
             .await?;
-        Ok(response_to_result(response)?)
+        response_to_data(response)
     }
 
-    #[cfg(feature = "blocking")]
-    pub fn get_images_for_id(
-        &self,
-        game_id: usize,
-        query: &QueryType<'_>,
-    ) -> Result<Vec<Image>, Box<dyn std::error::Error>> {
-        let url = get_images_by_game_id_url(self.base_url.as_str(), game_id, query);
-        let response = self.make_request::<InnerImagesSingleIdResponse>(url.as_str())?;
-        Ok(response_to_result(response)?)
-    }
     /// Fetches images given a list game id's and a query type.
     ///
     /// The resulting list will be a SteamGridDbResult<Image> for each id.
@@ -220,12 +342,11 @@ impl Client {
     /// # Ok(())
     /// # }
     /// ```
-    #[cfg(feature = "async")]
     pub async fn get_images_for_ids(
         &self,
         game_id: &[usize],
         query: &QueryType<'_>,
-    ) -> Result<Vec<SteamGridDbResult<Image>>, Box<dyn std::error::Error>> {
+    ) -> Result<Vec<SteamGridDbResult<Image>>, Error> {
         if game_id.is_empty() {
             return Ok(Vec::new());
         }
@@ -242,30 +363,102 @@ impl Client {
         let resposse = self
             .make_request::<InnerImagesMultipleIdsResponse>(url.as_str())
             .await?;
-        Ok(response_to_result_flat(resposse)?)
+        response_to_data_flat(resposse)
     }
 
-    #[cfg(feature = "blocking")]
-    pub fn get_images_for_ids(
+    /// Fetches all images given a list game id's and a query type.
+    ///
+    /// The resulting list will be a SteamGridDbResult<Vec<Image>> for each id, in the same order as the ids.
+    ///
+    /// ### Examples
+    /// Every image will be fetched for each id.
+    ///
+    /// ```no_run
+    /// use steamgriddb_api::client::Client;
+    /// use steamgriddb_api::query_parameters::QueryType::*;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("my_auth_key");
+    /// let ids = [7993,5153400];
+    /// let grid_images = client.get_all_images_for_ids(&ids, &Grid(None)).await?;
+    /// assert_eq!(ids.len(), grid_images.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_all_images_for_ids(
         &self,
         game_id: &[usize],
         query: &QueryType<'_>,
-    ) -> Result<Vec<SteamGridDbResult<Image>>, Box<dyn std::error::Error>> {
+    ) -> Result<Vec<SteamGridDbResult<Vec<Image>>>, Error> {
         if game_id.is_empty() {
             return Ok(Vec::new());
         }
 
         if game_id.len() == 1 {
-            let single_result = self.get_images_for_id(game_id[0], query)?;
-            if !single_result.is_empty() {
-                return Ok(vec![SteamGridDbResult::Ok(single_result[0].clone())]);
-            }
+            let single_result = self.get_images_for_id(game_id[0], query).await;
+            return single_result_to_result_all(single_result);
         }
 
         let url = get_images_by_game_ids_url(self.base_url.as_str(), game_id, query);
+        let response = self
+            .make_request::<InnerImagesMultipleIdsResponse>(url.as_str())
+            .await?;
+        response_to_result_all(response)
+    }
+
+    /// Fetches a single image given its kind and its steamgriddb id.
+    ///
+    /// ### Examples
+    ///
+    /// ```no_run
+    /// use steamgriddb_api::client::Client;
+    /// use steamgriddb_api::query_parameters::ImageKind;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("my_auth_key");
+    /// let image = client.get_image_by_id(&ImageKind::Grid, 80200).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_image_by_id(&self, kind: &ImageKind, image_id: u32) -> Result<Image, Error> {
+        let url = get_image_by_id_url(self.base_url.as_str(), kind, image_id);
+        let response = self
+            .make_request::<InnerImageResponse>(url.as_str())
+            .await?;
+        response_to_data(response)
+    }
+
+    /// Fetches a single grid image given its steamgriddb id.
+    ///
+    /// ### Examples
+    ///
+    /// ```no_run
+    /// use steamgriddb_api::client::Client;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("my_auth_key");
+    /// let grid = client.get_grid_by_id(80200).await?;
+    /// println!("{} has {} upvotes", grid.url, grid.upvotes);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_grid_by_id(&self, image_id: u32) -> Result<Image, Error> {
+        self.get_image_by_id(&ImageKind::Grid, image_id).await
+    }
+
+    /// Fetches a single hero image given its steamgriddb id.
+    pub async fn get_hero_by_id(&self, image_id: u32) -> Result<Image, Error> {
+        self.get_image_by_id(&ImageKind::Hero, image_id).await
+    }
+
+    /// Fetches a single logo image given its steamgriddb id.
+    pub async fn get_logo_by_id(&self, image_id: u32) -> Result<Image, Error> {
+        self.get_image_by_id(&ImageKind::Logo, image_id).await
+    }
 
-        let resposse = self.make_request::<InnerImagesMultipleIdsResponse>(url.as_str())?;
-        Ok(response_to_result_flat(resposse)?)
+    /// Fetches a single icon image given its steamgriddb id.
+    pub async fn get_icon_by_id(&self, image_id: u32) -> Result<Image, Error> {
+        self.get_image_by_id(&ImageKind::Icon, image_id).await
     }
 
     /// Search for games given a search query.
@@ -286,22 +479,41 @@ impl Client {
     /// # Ok(())
     /// # }
     /// ```
-    #[cfg(feature = "async")]
-    pub async fn search(
-        &self,
-        query: &str,
-    ) -> Result<Vec<SearchResult>, Box<dyn std::error::Error>> {
+    pub async fn search(&self, query: &str) -> Result<Vec<SearchResult>, Error> {
         let url = get_search_url(self.base_url.as_str(), query);
         let response = self.make_request::<InnerSearchResult>(url.as_str()).await?;
-        Ok(response_to_result(response)?)
+        response_to_data(response)
     }
 
-    #[cfg(feature = "blocking")]
-    pub fn search(&self, query: &str) -> Result<Vec<SearchResult>, Box<dyn std::error::Error>> {
-        let url = get_search_url(self.base_url.as_str(), query);
-        let response = self.make_request::<InnerSearchResult>(url.as_str())?;
-        Ok(response_to_result(response)?)
+    /// Search for a game, and get the result that best matches the title, if the matcher is confident enough.
+    ///
+    /// The results are scored by the matcher, not taken in the order the api returns them.
+    ///
+    /// ### Examples
+    ///
+    /// ```no_run
+    /// use steamgriddb_api::client::Client;
+    /// use steamgriddb_api::matcher::TitleMatcher;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("my_auth_key");
+    /// let matcher = TitleMatcher::new().year(2012);
+    /// let found = client.search_best_match("Assassins Creed 3", &matcher).await?;
+    /// if let Some(found) = found {
+    ///     assert_eq!("Assassin's Creed® III", found.result.name);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn search_best_match(
+        &self,
+        title: &str,
+        matcher: &TitleMatcher,
+    ) -> Result<Option<TitleMatch>, Error> {
+        let results = self.search(title).await?;
+        Ok(matcher.best_match(title, &results))
     }
+
     /// Fetches images given a platform type, a platform specific game id and a query type.
     ///    
     /// ### Examples
@@ -319,32 +531,80 @@ impl Client {
     /// # Ok(())
     /// # }
     /// ```
-
-    #[cfg(feature = "async")]
     pub async fn get_images_for_platform_id(
         &self,
         platform: &Platform,
         game_id: &str,
         qeury: &QueryType<'_>,
-    ) -> Result<Vec<Image>, Box<dyn std::error::Error>> {
+    ) -> Result<Vec<Image>, Error> {
         let url = get_images_by_platform_id_url(self.base_url.as_str(), platform, game_id, qeury);
         let response = self
             .make_request::<InnerImagesSingleIdResponse>(url.as_str())
             .await?;
-        Ok(response_to_result(response)?)
+        response_to_data(response)
     }
 
-    #[cfg(feature = "blocking")]
-    pub fn get_images_for_platform_id(
-        &self,
-        platform: &Platform,
-        game_id: &str,
-        qeury: &QueryType<'_>,
-    ) -> Result<Vec<Image>, Box<dyn std::error::Error>> {
-        let url = get_images_by_platform_id_url(self.base_url.as_str(), platform, game_id, qeury);
-        let response = self.make_request::<InnerImagesSingleIdResponse>(url.as_str())?;
-        Ok(response_to_result(response)?)
+    /// Walks through all pages of images for a game, given its id and a query type.
+    ///
+    /// The page and limit of the query decides where to start and how many images to fetch per request.
+    ///
+    /// ### Examples
+    ///
+    /// ```no_run
+    /// use steamgriddb_api::client::Client;
+    /// use steamgriddb_api::query_parameters::QueryType::*;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("my_auth_key");
+    /// let mut pages = client.images_for_id_paged(7993, &Grid(None));
+    /// while let Some(page) = pages.next_page().await {
+    ///     let grid_images = page?;
+    /// }
+    /// let all_grid_images = client.images_for_id_paged(7993, &Grid(None)).all().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn images_for_id_paged<'a>(
+        &'a self,
+        game_id: usize,
+        query: &QueryType<'a>,
+    ) -> ImagePages<'a> {
+        ImagePages {
+            client: self,
+            pages: PagedImages::new(PagedImagesTarget::GameId(game_id), query),
+        }
+    }
+
+    /// Walks through all pages of images for a game, given a platform type, a platform specific game id and a query type.
+    ///
+    /// ### Examples
+    ///
+    /// ```no_run
+    /// use steamgriddb_api::client::Client;
+    /// use steamgriddb_api::query_parameters::Platform::*;
+    /// use steamgriddb_api::query_parameters::QueryType::*;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("my_auth_key");
+    /// let all_grid_images = client
+    ///     .images_for_platform_id_paged(&Steam, "361420", &Grid(None))
+    ///     .all()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn images_for_platform_id_paged<'a>(
+        &'a self,
+        platform: &'a Platform,
+        game_id: &'a str,
+        query: &QueryType<'a>,
+    ) -> ImagePages<'a> {
+        ImagePages {
+            client: self,
+            pages: PagedImages::new(PagedImagesTarget::PlatformId(platform, game_id), query),
+        }
     }
+
     /// Fetches images given a platform type, a platform specific game ids and a query type.
     ///    
     /// The resulting list will be a SteamGridDbResult<Image> for each id.
@@ -365,31 +625,61 @@ impl Client {
     /// # Ok(())  
     /// # }
     /// ```
-    #[cfg(feature = "async")]
     pub async fn get_images_for_platform_ids(
         &self,
         platform: &Platform,
         game_id: &[&str],
         qeury: &QueryType<'_>,
-    ) -> Result<Vec<SteamGridDbResult<Image>>, Box<dyn std::error::Error>> {
+    ) -> Result<Vec<SteamGridDbResult<Image>>, Error> {
         let url = get_images_by_platform_ids_url(self.base_url.as_str(), platform, game_id, qeury);
         let resposse = self
             .make_request::<InnerImagesMultipleIdsResponse>(url.as_str())
             .await?;
-        Ok(response_to_result_flat(resposse)?)
+        response_to_data_flat(resposse)
     }
 
-    #[cfg(feature = "blocking")]
-    pub fn get_images_for_platform_ids(
+    /// Fetches all images given a platform type, a platform specific game ids and a query type.
+    ///
+    /// The resulting list will be a SteamGridDbResult<Vec<Image>> for each id, in the same order as the ids.
+    ///
+    /// ### Examples
+    ///
+    /// ```no_run
+    /// use steamgriddb_api::client::Client;
+    /// use steamgriddb_api::query_parameters::Platform::*;
+    /// use steamgriddb_api::query_parameters::QueryType::*;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("my_auth_key");
+    /// let ids = ["Salt", "Turkey"];
+    /// let epic_games_images = client.get_all_images_for_platform_ids(&EpicGameStore, &ids, &Grid(None)).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_all_images_for_platform_ids(
         &self,
         platform: &Platform,
         game_id: &[&str],
-        qeury: &QueryType<'_>,
-    ) -> Result<Vec<SteamGridDbResult<Image>>, Box<dyn std::error::Error>> {
-        let url = get_images_by_platform_ids_url(self.base_url.as_str(), platform, game_id, qeury);
-        let resposse = self.make_request::<InnerImagesMultipleIdsResponse>(url.as_str())?;
-        Ok(response_to_result_flat(resposse)?)
+        query: &QueryType<'_>,
+    ) -> Result<Vec<SteamGridDbResult<Vec<Image>>>, Error> {
+        if game_id.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        if game_id.len() == 1 {
+            let single_result = self
+                .get_images_for_platform_id(platform, game_id[0], query)
+                .await;
+            return single_result_to_result_all(single_result);
+        }
+
+        let url = get_images_by_platform_ids_url(self.base_url.as_str(), platform, game_id, query);
+        let response = self
+            .make_request::<InnerImagesMultipleIdsResponse>(url.as_str())
+            .await?;
+        response_to_result_all(response)
     }
+
     /// Fetch information about a game given a game id.
     ///    
     /// ### Examples
@@ -404,25 +694,14 @@ impl Client {
     /// # Ok(())
     /// # }
     /// ```
-    #[cfg(feature = "async")]
-    pub async fn get_game_info_for_id(
-        &self,
-        game_id: usize,
-    ) -> Result<GameInfo, Box<dyn std::error::Error>> {
+    pub async fn get_game_info_for_id(&self, game_id: usize) -> Result<GameInfo, Error> {
         let url = get_gameinfo_by_game_id_url(self.base_url.as_str(), game_id);
-        let response = self.make_request::<GameInfo>(url.as_str()).await?;
-        Ok(response)
+        let response = self
+            .make_request::<Response<GameInfo>>(url.as_str())
+            .await?;
+        response_to_data(response)
     }
 
-    #[cfg(feature = "blocking")]
-    pub fn get_game_info_for_id(
-        &self,
-        game_id: usize,
-    ) -> Result<GameInfo, Box<dyn std::error::Error>> {
-        let url = get_gameinfo_by_game_id_url(self.base_url.as_str(), game_id);
-        let response = self.make_request::<GameInfo>(url.as_str())?;
-        Ok(response)
-    }
     /// Fetch information about a game given a steam game id.
     ///    
     /// ### Examples
@@ -437,52 +716,412 @@ impl Client {
     /// # Ok(())
     /// # }
     /// ```
-    #[cfg(feature = "async")]
-    pub async fn get_game_by_steam_app_id(
-        &self,
-        steam_app_id: usize,
-    ) -> Result<GameInfo, Box<dyn std::error::Error>> {
+    pub async fn get_game_by_steam_app_id(&self, steam_app_id: usize) -> Result<GameInfo, Error> {
         let url = get_game_by_steam_app_id_url(self.base_url.as_str(), steam_app_id);
-        let response = self.make_request::<Response<GameInfo>>(url.as_str()).await?;
-        response.data.ok_or("Data was empty".to_string().into())
+        let response = self
+            .make_request::<Response<GameInfo>>(url.as_str())
+            .await?;
+        response_to_data(response)
     }
 
-    #[cfg(feature = "blocking")]
-    pub fn get_game_by_steam_app_id(
+    /// Fetch information about a game given a platform type and a platform specific game id.
+    ///
+    /// ### Examples
+    ///
+    /// ```no_run
+    /// use steamgriddb_api::client::Client;
+    /// use steamgriddb_api::query_parameters::Platform;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("my_auth_key");
+    /// let game_info = client.get_game_by_platform_id(&Platform::GoG, "1207658924").await?;
+    /// println!("The SteamGridDB id is {}", game_info.id);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_game_by_platform_id(
         &self,
-        steam_app_id: usize,
-    ) -> Result<GameInfo, Box<dyn std::error::Error>> {
-        let url = get_game_by_steam_app_id_url(self.base_url.as_str(), steam_app_id);
-        let response = self.make_request::<Response<GameInfo>>(url.as_str())?;
-        response.data.ok_or("Data was empty".to_string().into())
+        platform: &Platform,
+        game_id: &str,
+    ) -> Result<GameInfo, Error> {
+        let url = get_game_by_platform_id_url(self.base_url.as_str(), platform, game_id);
+        let response = self
+            .make_request::<Response<GameInfo>>(url.as_str())
+            .await?;
+        response_to_data(response)
     }
 
-    #[cfg(feature = "async")]
-    async fn make_request<'de, T>(&self, url: &str) -> Result<T, Box<dyn std::error::Error>>
-    where
-        T: DeserializeOwned,
-    {
-        Ok(self
-            .client
-            .get(url)
-            .bearer_auth(self.auth_key.as_str())
-            .send()
-            .await?
-            .json::<T>()
-            .await?)
+    /// Fetch information about a game, including its ids and store data on the given platforms.
+    ///
+    /// ### Examples
+    ///
+    /// ```no_run
+    /// use steamgriddb_api::client::Client;
+    /// use steamgriddb_api::games::GameLookup;
+    /// use steamgriddb_api::query_parameters::Platform;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("my_auth_key");
+    /// let lookup = GameLookup::PlatformId(&Platform::EpicGameStore, "Salt");
+    /// let game_info = client.get_game(&lookup, &[Platform::Steam, Platform::GoG]).await?;
+    /// if let Some(gog_id) = game_info.platform_id(&Platform::GoG) {
+    ///     println!("The GOG id is {}", gog_id);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_game(
+        &self,
+        lookup: &GameLookup<'_>,
+        platform_data: &[Platform],
+    ) -> Result<GameInfo, Error> {
+        let url = get_game_url(self.base_url.as_str(), lookup, platform_data);
+        let response = self
+            .make_request::<Response<GameInfo>>(url.as_str())
+            .await?;
+        response_to_data(response)
     }
 
-    #[cfg(feature = "blocking")]
-    fn make_request<'de, T>(&self, url: &str) -> Result<T, Box<dyn std::error::Error>>
-    where
-        T: DeserializeOwned,
-    {
-        Ok(self
-            .client
-            .get(url)
-            .bearer_auth(self.auth_key.as_str())
-            .send()?
-            .json::<T>()?)
+    /// Uploads an image for a game, given its steamgriddb id.
+    ///
+    /// The upload type decides the kind and style of the image.
+    /// The auth key of the client is used to identify the uploader.
+    ///
+    /// ### Examples
+    ///
+    /// ```no_run
+    /// use steamgriddb_api::client::Client;
+    /// use steamgriddb_api::query_parameters::Style;
+    /// use steamgriddb_api::upload::UploadType;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("my_auth_key");
+    /// let data = std::fs::read("my_grid.png")?;
+    /// let upload = UploadType::Grid(Style::Alternate);
+    /// let image = client.upload_image(13136, &upload, "my_grid.png", &data).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn upload_image(
+        &self,
+        game_id: usize,
+        upload: &UploadType,
+        file_name: &str,
+        data: &[u8],
+    ) -> Result<Image, Error> {
+        let url = get_upload_url(self.base_url.as_str(), upload);
+        let form = UploadForm::new(game_id, upload, file_name, data);
+        let response = self
+            .make_upload_request::<InnerImageResponse>(url.as_str(), form)
+            .await?;
+        response_to_data(response)
+    }
+
+    /// Uploads an image file for a game, given its steamgriddb id.
+    ///
+    /// See [`Client::upload_image`].
+    ///
+    /// ### Examples
+    ///
+    /// ```no_run
+    /// use steamgriddb_api::client::Client;
+    /// use steamgriddb_api::query_parameters::StyleLogo;
+    /// use steamgriddb_api::upload::UploadType;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("my_auth_key");
+    /// let upload = UploadType::Logo(StyleLogo::White);
+    /// let image = client.upload_image_from_path(13136, &upload, "my_logo.png").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn upload_image_from_path<P>(
+        &self,
+        game_id: usize,
+        upload: &UploadType,
+        path: P,
+    ) -> Result<Image, Error>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let data = std::fs::read(path)?;
+        let file_name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        self.upload_image(game_id, upload, &file_name, &data).await
+    }
+
+    /// Deletes images uploaded by the user of the auth key.
+    ///
+    /// The images must all be of the given kind.
+    /// Deleting images uploaded by another user fails with [`Error::PermissionDenied`].
+    ///
+    /// ### Examples
+    ///
+    /// ```no_run
+    /// use steamgriddb_api::client::Client;
+    /// use steamgriddb_api::query_parameters::ImageKind;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("my_auth_key");
+    /// client.delete_images(&ImageKind::Grid, &[80200, 80201]).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn delete_images(&self, kind: &ImageKind, image_ids: &[u32]) -> Result<(), Error> {
+        if image_ids.is_empty() {
+            return Ok(());
+        }
+        let url = get_delete_images_url(self.base_url.as_str(), kind, image_ids);
+        let response = self
+            .send_request::<InnerEmptyResponse>(HttpRequest::new(Method::Delete, url))
+            .await?;
+        response_to_success(response)
+    }
+
+    /// Votes on an image, as the user of the auth key.
+    ///
+    /// ### Examples
+    ///
+    /// ```no_run
+    /// use steamgriddb_api::client::Client;
+    /// use steamgriddb_api::images::Vote;
+    /// use steamgriddb_api::query_parameters::ImageKind;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("my_auth_key");
+    /// client.vote(&ImageKind::Hero, 25973, &Vote::Up).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn vote(&self, kind: &ImageKind, image_id: u32, vote: &Vote) -> Result<(), Error> {
+        let url = get_vote_url(self.base_url.as_str(), kind, image_id, vote);
+        let response = self
+            .send_request::<InnerEmptyResponse>(HttpRequest::new(Method::Post, url))
+            .await?;
+        response_to_success(response)
+    }
+
+    /// Downloads the file of an image, checking its size and content type with the default [`DownloadOptions`].
+    ///
+    /// The file is downloaded from the steamgriddb CDN, without sending the auth key.
+    ///
+    /// ### Examples
+    ///
+    /// ```no_run
+    /// use steamgriddb_api::client::Client;
+    /// use steamgriddb_api::download::Variant;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("my_auth_key");
+    /// let grid = client.get_grid_by_id(80200).await?;
+    /// let bytes = client.download_image(&grid, Variant::Full).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn download_image(&self, image: &Image, variant: Variant) -> Result<Vec<u8>, Error> {
+        let mut bytes = Vec::new();
+        self.download_image_to(image, variant, &mut bytes, &DownloadOptions::default())
+            .await?;
+        Ok(bytes)
+    }
+
+    /// Downloads the file of an image, and writes it to the async writer.
+    ///
+    /// ### Examples
+    ///
+    /// ```no_run
+    /// use steamgriddb_api::client::Client;
+    /// use steamgriddb_api::download::{DownloadOptions, Variant};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("my_auth_key");
+    /// let grid = client.get_grid_by_id(80200).await?;
+    /// let mut bytes = Vec::new();
+    /// let downloaded = client
+    ///     .download_image_to(&grid, Variant::Thumb, &mut bytes, &DownloadOptions::default())
+    ///     .await?;
+    /// assert_eq!(downloaded.size, bytes.len() as u64);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn download_image_to<W>(
+        &self,
+        image: &Image,
+        variant: Variant,
+        writer: &mut W,
+        options: &DownloadOptions<'_>,
+    ) -> Result<DownloadedImage, Error>
+    where
+        W: AsyncWrite + Unpin + ?Sized,
+    {
+        let response = self.fetch_download(image, variant).await?;
+        stream_download(image, variant, response, writer, options).await
+    }
+
+    /// Downloads the file of an image, and saves it at the path.
+    ///
+    /// The file is first written next to the path with a ".tmp" extension, and then renamed,
+    /// so the path never contains a partial download.
+    ///
+    /// ### Examples
+    ///
+    /// ```no_run
+    /// use steamgriddb_api::client::Client;
+    /// use steamgriddb_api::download::{DownloadOptions, Variant};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("my_auth_key");
+    /// let grid = client.get_grid_by_id(80200).await?;
+    /// let options = DownloadOptions::default();
+    /// client.download_image_to_path(&grid, Variant::Full, "grid.png", &options).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn download_image_to_path<P>(
+        &self,
+        image: &Image,
+        variant: Variant,
+        path: P,
+        options: &DownloadOptions<'_>,
+    ) -> Result<DownloadedImage, Error>
+    where
+        P: AsRef<Path>,
+    {
+        let response = self.fetch_download(image, variant).await?;
+        let mut file = AtomicFile::create(path.as_ref())?;
+        let downloaded = stream_download(image, variant, response, &mut file, options).await?;
+        file.commit()?;
+        Ok(downloaded)
+    }
+
+    /// Downloads an image, and installs it as Steam artwork for an app.
+    ///
+    /// The file is named the way Steam expects for the kind of artwork, with the extension of the image.
+    /// Artwork of the same kind for the app with other extensions is removed, so Steam shows the new image.
+    /// Webp images are refused with [`Error::ContentType`] before they are downloaded, since Steam does not show them.
+    ///
+    /// ### Examples
+    ///
+    /// ```no_run
+    /// use steamgriddb_api::client::Client;
+    /// use steamgriddb_api::query_parameters::ImageKind;
+    /// use steamgriddb_api::steam::grid::{ArtworkKind, GridFolder};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("my_auth_key");
+    /// let folder = GridFolder::new("/home/me/.steam/steam/userdata/12345678");
+    /// let grid = client.get_grid_by_id(80200).await?;
+    /// let kind = ArtworkKind::for_image(&ImageKind::Grid, &grid);
+    /// let installed = client.install_artwork(&folder, 504230, kind, &grid).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn install_artwork(
+        &self,
+        folder: &GridFolder,
+        app_id: u32,
+        kind: ArtworkKind,
+        image: &Image,
+    ) -> Result<InstalledArtwork, Error> {
+        let path = folder.path_for_image(app_id, kind, image)?;
+        folder.create()?;
+        self.download_image_to_path(image, Variant::Full, &path, &DownloadOptions::default())
+            .await?;
+        folder.remove_except(app_id, kind, Some(&path))?;
+        Ok(InstalledArtwork { app_id, kind, path })
+    }
+
+    async fn fetch_download(
+        &self,
+        image: &Image,
+        variant: Variant,
+    ) -> Result<StreamingResponse<Box<dyn BodyStream>>, Error> {
+        let request = HttpRequest::new(Method::Get, variant.url(image));
+        self.with_retries(request.method, || {
+            self.transport.send_streaming(request.clone())
+        })
+        .await
+    }
+
+    async fn make_request<T>(&self, url: &str) -> Result<T, Error>
+    where
+        T: DeserializeOwned,
+    {
+        self.send_request(HttpRequest::new(Method::Get, url)).await
+    }
+
+    async fn make_upload_request<T>(&self, url: &str, form: UploadForm) -> Result<T, Error>
+    where
+        T: DeserializeOwned,
+    {
+        let request = HttpRequest::new(Method::Post, url)
+            .with_header("Content-Type", form.content_type)
+            .with_body(form.body);
+        self.send_request(request).await
+    }
+
+    async fn send_request<T>(&self, request: HttpRequest) -> Result<T, Error>
+    where
+        T: DeserializeOwned,
+    {
+        let request = request.with_header("Authorization", format!("Bearer {}", self.auth_key));
+        let response = match (&self.cache, request.method) {
+            (Some(cache), Method::Get) => self.send_cached(cache, request).await?,
+            _ => self.send_with_retries(request).await?,
+        };
+        parse_response(response.status, &response.body)
+    }
+
+    async fn send_cached(
+        &self,
+        cache: &ResponseCache,
+        request: HttpRequest,
+    ) -> Result<HttpResponse, Error> {
+        let url = request.url.clone();
+        let sent = match cache.lookup(&url) {
+            CacheLookup::Fresh(response) => return Ok(response),
+            CacheLookup::Revalidate(etag) => request.clone().with_header("If-None-Match", etag),
+            CacheLookup::Miss => request.clone(),
+        };
+        let response = cache.store(&url, self.send_with_retries(sent).await?);
+        if response.status != 304 {
+            return Ok(response);
+        }
+        // The cached response was evicted while it was revalidated, so it is requested again in full
+        let response = self.send_with_retries(request).await?;
+        Ok(cache.store(&url, response))
+    }
+
+    async fn send_with_retries(&self, request: HttpRequest) -> Result<HttpResponse, Error> {
+        self.with_retries(request.method, || self.transport.send(request.clone()))
+            .await
+    }
+
+    async fn with_retries<R, F, S>(&self, method: Method, send: S) -> Result<R, Error>
+    where
+        R: ResponseHead,
+        F: Future<Output = Result<R, Error>>,
+        S: Fn() -> F,
+    {
+        let mut attempt = 1;
+        loop {
+            if let Some(rate_limiter) = &self.rate_limiter {
+                let reservation = rate_limiter.reserve();
+                if !reservation.wait().is_zero() {
+                    self.transport.sleep(reservation.wait()).await;
+                }
+                reservation.sent();
+            }
+            let result = send().await;
+            match self.retry_policy.retry_delay(attempt, method, &result) {
+                Some(delay) => self.transport.sleep(delay).await,
+                None => return result,
+            }
+            attempt += 1;
+        }
     }
 
     /// Get a SteamStaticUrls that contains the expected urls for the official Steam store images.
@@ -494,4 +1133,994 @@ impl Client {
     pub fn get_official_steam_images(&self, steam_app_id: &str) -> SteamStaticUrls {
         Self::get_official_steam_images_static(steam_app_id)
     }
+
+    /// Check which of the official Steam store images exist, using HEAD requests.
+    ///
+    /// High resolution images that are missing fall back to their normal resolution, like `library_600x900.jpg` for the capsule.
+    ///
+    /// ### Examples
+    ///
+    /// ```no_run
+    /// use steamgriddb_api::client::Client;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("my_auth_key");
+    /// let images = client.check_official_steam_images("361420").await?;
+    /// if images.hero.exists {
+    ///     println!("The hero is at {}", images.hero.url);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn check_official_steam_images(
+        &self,
+        steam_app_id: &str,
+    ) -> Result<CheckedSteamStaticUrls, Error> {
+        let urls = self.get_official_steam_images(steam_app_id);
+        Ok(CheckedSteamStaticUrls {
+            header: self.check_static_asset(&urls.header).await?,
+            capsule: self.check_static_asset(&urls.capsule).await?,
+            hero: self.check_static_asset(&urls.hero).await?,
+            logo: self.check_static_asset(&urls.logo).await?,
+        })
+    }
+
+    /// Check the url of an image, and its fallbacks, until one exists
+    async fn check_static_asset(&self, url: &str) -> Result<StaticAsset, Error> {
+        let mut first = None;
+        for candidate in fallback_urls(url) {
+            let request = HttpRequest::new(Method::Head, candidate.as_str());
+            let response = self.send_with_retries(request).await?;
+            let asset = StaticAsset::from_response(candidate, &response)?;
+            if asset.exists {
+                return Ok(asset);
+            }
+            first.get_or_insert(asset);
+        }
+        Ok(first.expect("There is always at least one url"))
+    }
+}
+
+/// Walks through the pages of images for a game.
+///
+/// Created by [`Client::images_for_id_paged`] and [`Client::images_for_platform_id_paged`].
+pub struct ImagePages<'a> {
+    client: &'a Client,
+    pages: PagedImages<'a>,
+}
+
+impl ImagePages<'_> {
+    /// Fetches the next page of images.
+    ///
+    /// Returns None when there are no more pages.
+    pub async fn next_page(&mut self) -> Option<Result<Vec<Image>, Error>> {
+        let url = self.pages.next_url(self.client.base_url.as_str())?;
+        let response = self
+            .client
+            .make_request::<InnerImagesSingleIdResponse>(url.as_str())
+            .await;
+        self.pages.handle_response(response)
+    }
+
+    /// Fetches all the remaining pages, and returns the images from all of them.
+    pub async fn all(mut self) -> Result<Vec<Image>, Error> {
+        let mut images = Vec::new();
+        while let Some(page) = self.next_page().await {
+            images.extend(page?);
+        }
+        Ok(images)
+    }
+}
+
+/// Picks the best artwork of each kind for a game, in one call.
+///
+/// The queries of the [`ArtworkPolicy`] are tried in order, and the first query with images
+/// gives the artwork, picked from them by the selector of the policy.
+/// When no image is found, the official Steam image is used if it exists.
+///
+/// ### Examples
+///
+/// ```no_run
+/// use steamgriddb_api::client::{ArtworkResolver, Client};
+/// use steamgriddb_api::resolver::GameIdentity;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = Client::new("my_auth_key");
+/// let resolver = ArtworkResolver::new(&client);
+/// let artwork = resolver.resolve(&GameIdentity::SteamAppId(361420)).await?;
+/// if let Some(hero) = artwork.hero {
+///     println!("The hero is at {}", hero.url());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct ArtworkResolver<'a> {
+    client: &'a Client,
+    policy: ArtworkPolicy<'a>,
+}
+
+impl<'a> ArtworkResolver<'a> {
+    /// Creates a resolver with the default policy.
+    pub fn new(client: &'a Client) -> Self {
+        Self::with_policy(client, ArtworkPolicy::default())
+    }
+
+    /// Creates a resolver with the given policy.
+    pub fn with_policy(client: &'a Client, policy: ArtworkPolicy<'a>) -> Self {
+        Self { client, policy }
+    }
+
+    /// The policy used to pick the artwork.
+    pub fn policy(&self) -> &ArtworkPolicy<'a> {
+        &self.policy
+    }
+
+    /// Finds the artwork of every kind for a game.
+    ///
+    /// Games that are not known to SteamGridDB can still get the official Steam images, if their Steam app id is known.
+    pub async fn resolve(&self, identity: &GameIdentity) -> Result<ResolvedArtworkSet, Error> {
+        let game_id = self.game_id(identity).await?;
+        let target = direct_target(identity).or_else(|| game_id.map(PagedImagesTarget::GameId));
+        let mut artwork = ResolvedArtworkSet {
+            game_id,
+            ..Default::default()
+        };
+        for kind in ArtworkKind::ALL.iter() {
+            let resolved = self.resolve_for(target, identity, *kind).await?;
+            artwork.set(*kind, resolved);
+        }
+        Ok(artwork)
+    }
+
+    /// Finds the artwork of one kind for a game.
+    pub async fn resolve_kind(
+        &self,
+        identity: &GameIdentity,
+        kind: ArtworkKind,
+    ) -> Result<Option<ResolvedArtwork>, Error> {
+        let game_id = self.game_id(identity).await?;
+        let target = direct_target(identity).or_else(|| game_id.map(PagedImagesTarget::GameId));
+        self.resolve_for(target, identity, kind).await
+    }
+
+    /// Looks up the SteamGridDB id of the game, None if it is not found
+    async fn game_id(&self, identity: &GameIdentity) -> Result<Option<usize>, Error> {
+        Ok(match identity {
+            GameIdentity::SteamGridDbId(game_id) => Some(*game_id),
+            GameIdentity::SteamAppId(app_id) => {
+                found(self.client.get_game_by_steam_app_id(*app_id).await)?.map(|game| game.id)
+            }
+            GameIdentity::Name(name) => found(self.client.search(name).await)?
+                .and_then(|results| results.into_iter().next())
+                .map(|result| result.id),
+            GameIdentity::PlatformId(platform, game_id) => {
+                found(self.client.get_game_by_platform_id(platform, game_id).await)?
+                    .map(|game| game.id)
+            }
+        })
+    }
+
+    async fn resolve_for(
+        &self,
+        target: Option<PagedImagesTarget<'_>>,
+        identity: &GameIdentity,
+        kind: ArtworkKind,
+    ) -> Result<Option<ResolvedArtwork>, Error> {
+        if let Some(target) = target {
+            for query in self.policy.queries(kind) {
+                let query = &self.policy.selector.narrow_query(*query);
+                let images = match target {
+                    PagedImagesTarget::GameId(game_id) => {
+                        self.client.get_images_for_id(game_id, query).await
+                    }
+                    PagedImagesTarget::PlatformId(platform, game_id) => {
+                        self.client
+                            .get_images_for_platform_id(platform, game_id, query)
+                            .await
+                    }
+                };
+                let selector = &self.policy.selector;
+                if let Some(image) = found(images)?.and_then(|images| selector.select_owned(images))
+                {
+                    return Ok(Some(ResolvedArtwork::SteamGridDb(Box::new(image))));
+                }
+            }
+        }
+        let steam_app_id = match identity.steam_app_id() {
+            Some(steam_app_id) if self.policy.official_fallback => steam_app_id,
+            _ => return Ok(None),
+        };
+        let asset = match official_asset(kind) {
+            Some(asset) => asset,
+            None => return Ok(None),
+        };
+        let urls = self.client.get_official_steam_images(&steam_app_id);
+        // A failed check of the CDN only means there is no official image to fall back to
+        let checked = match self.client.check_static_asset(urls.get(asset)).await {
+            Ok(checked) => checked,
+            Err(_) => return Ok(None),
+        };
+        Ok(checked.exists.then_some(ResolvedArtwork::Official {
+            asset,
+            url: checked.url,
+        }))
+    }
+}
+
+#[cfg(all(test, feature = "reqwest"))]
+mod tests {
+
+    use super::*;
+    use crate::{
+        query_parameters::Style,
+        steam_static::SteamAsset,
+        test_server::{FakeTransport, StubResponse, StubServer},
+    };
+
+    fn test_client(server: &StubServer) -> Client {
+        let mut client = Client::new("my_auth_key");
+        client.set_base_url(server.url.as_str());
+        client.set_retry_policy(RetryPolicy {
+            base_delay: std::time::Duration::from_millis(1),
+            ..Default::default()
+        });
+        client
+    }
+
+    #[tokio::test]
+    async fn upload_image_test() {
+        let json = std::fs::read_to_string("testdata/upload/upload.json").unwrap();
+        let server = StubServer::start(vec![StubResponse::json(200, &json)]);
+        let client = test_client(&server);
+        let upload = UploadType::Grid(Style::Alternate);
+        let image = client
+            .upload_image(13136, &upload, "grid.png", b"\x89PNG data")
+            .await
+            .unwrap();
+        assert_eq!(92311, image.id);
+
+        let requests = server.requests();
+        assert_eq!(1, requests.len());
+        let request = &requests[0];
+        assert_eq!("POST", request.method);
+        assert_eq!("/grids", request.path);
+        assert_eq!(Some("Bearer my_auth_key"), request.header("authorization"));
+        let content_type = request.header("content-type").unwrap();
+        assert!(content_type.starts_with("multipart/form-data; boundary="));
+        let body = String::from_utf8_lossy(&request.body);
+        assert!(body.contains("name=\"game_id\"\r\n\r\n13136\r\n"));
+        assert!(body.contains("name=\"style\"\r\n\r\nalternate\r\n"));
+        assert!(body.contains("name=\"grid\"; filename=\"grid.png\""));
+    }
+
+    #[tokio::test]
+    async fn upload_image_error_test() {
+        let json = std::fs::read_to_string("testdata/upload/error.json").unwrap();
+        let server = StubServer::start(vec![StubResponse::json(400, &json)]);
+        let client = test_client(&server);
+        let upload = UploadType::Hero(Style::Blurred);
+        let result = client
+            .upload_image(13136, &upload, "hero.png", b"\x89PNG data")
+            .await;
+        match result {
+            Err(Error::Api(error)) => {
+                assert_eq!(Some(400), error.status);
+                assert_eq!(Some(vec!["Invalid style".to_string()]), error.errors);
+            }
+            _ => panic!("Expected an api error"),
+        }
+    }
+
+    #[tokio::test]
+    async fn upload_image_from_missing_path_test() {
+        let client = Client::new("my_auth_key");
+        let upload = UploadType::Grid(Style::Alternate);
+        let result = client
+            .upload_image_from_path(13136, &upload, "testdata/upload/missing.png")
+            .await;
+        assert!(matches!(result, Err(Error::Io(_))));
+    }
+
+    #[tokio::test]
+    async fn delete_images_test() {
+        let server = StubServer::start(vec![StubResponse::json(200, r#"{"success":true}"#)]);
+        let client = test_client(&server);
+        client
+            .delete_images(&ImageKind::Logo, &[1, 2])
+            .await
+            .unwrap();
+        let requests = server.requests();
+        assert_eq!("DELETE", requests[0].method);
+        assert_eq!("/logos/1,2", requests[0].path);
+        assert_eq!(
+            Some("Bearer my_auth_key"),
+            requests[0].header("authorization")
+        );
+    }
+
+    #[tokio::test]
+    async fn delete_images_permission_denied_test() {
+        let body = r#"{"success":false,"errors":["You do not own this asset"]}"#;
+        let server = StubServer::start(vec![StubResponse::json(403, body)]);
+        let client = test_client(&server);
+        let result = client.delete_images(&ImageKind::Grid, &[80200]).await;
+        match result {
+            Err(Error::PermissionDenied(error)) => assert_eq!(Some(403), error.status),
+            _ => panic!("Expected a permission error"),
+        }
+    }
+
+    #[tokio::test]
+    async fn vote_test() {
+        let server = StubServer::start(vec![StubResponse::json(200, r#"{"success":true}"#)]);
+        let client = test_client(&server);
+        client
+            .vote(&ImageKind::Icon, 1234, &Vote::Down)
+            .await
+            .unwrap();
+        let requests = server.requests();
+        assert_eq!("POST", requests[0].method);
+        assert_eq!("/icons/1234/vote/down", requests[0].path);
+    }
+
+    #[tokio::test]
+    async fn vote_api_error_test() {
+        let body = r#"{"success":false,"errors":["Asset does not exist"]}"#;
+        let server = StubServer::start(vec![StubResponse::json(404, body)]);
+        let client = test_client(&server);
+        let result = client.vote(&ImageKind::Grid, 1, &Vote::Up).await;
+        assert!(matches!(result, Err(Error::Api(_))));
+    }
+
+    #[tokio::test]
+    async fn get_grid_by_id_test() {
+        let json = std::fs::read_to_string("testdata/grids/grid_by_id.json").unwrap();
+        let server = StubServer::start(vec![StubResponse::json(200, &json)]);
+        let client = test_client(&server);
+        let grid = client.get_grid_by_id(80200).await.unwrap();
+        assert_eq!(80200, grid.id);
+        let requests = server.requests();
+        assert_eq!("GET", requests[0].method);
+        assert_eq!("/grids/80200", requests[0].path);
+    }
+
+    #[tokio::test]
+    async fn get_grid_by_id_retries_test() {
+        let json = std::fs::read_to_string("testdata/grids/grid_by_id.json").unwrap();
+        let server = StubServer::start(vec![
+            StubResponse::json(503, "Service Unavailable"),
+            StubResponse::disconnect(),
+            StubResponse::json(200, &json),
+        ]);
+        let client = test_client(&server);
+        let grid = client.get_grid_by_id(80200).await.unwrap();
+        assert_eq!(80200, grid.id);
+        assert_eq!(3, server.requests().len());
+    }
+
+    #[tokio::test]
+    async fn retry_after_test() {
+        let json = std::fs::read_to_string("testdata/grids/grid_by_id.json").unwrap();
+        let server = StubServer::start(vec![
+            StubResponse::json(429, "Too Many Requests").with_header("Retry-After", "1"),
+            StubResponse::json(200, &json),
+        ]);
+        let client = test_client(&server);
+        let start = std::time::Instant::now();
+        client.get_grid_by_id(80200).await.unwrap();
+        assert!(start.elapsed() >= std::time::Duration::from_secs(1));
+        assert_eq!(2, server.requests().len());
+    }
+
+    #[tokio::test]
+    async fn retries_exhausted_test() {
+        let server = StubServer::start(vec![
+            StubResponse::json(500, "Internal Server Error"),
+            StubResponse::json(502, "Bad Gateway"),
+            StubResponse::json(504, "Gateway Timeout"),
+        ]);
+        let client = test_client(&server);
+        let result = client.get_grid_by_id(80200).await;
+        assert_eq!(Some(504), result.unwrap_err().status());
+        assert_eq!(3, server.requests().len());
+    }
+
+    #[tokio::test]
+    async fn upload_image_not_retried_on_server_error_test() {
+        let server = StubServer::start(vec![StubResponse::json(503, "Service Unavailable")]);
+        let client = test_client(&server);
+        let upload = UploadType::Grid(Style::Alternate);
+        let result = client
+            .upload_image(13136, &upload, "grid.png", b"\x89PNG data")
+            .await;
+        assert_eq!(Some(503), result.unwrap_err().status());
+        assert_eq!(1, server.requests().len());
+    }
+
+    #[tokio::test]
+    async fn vote_retried_when_rate_limited_test() {
+        let server = StubServer::start(vec![
+            StubResponse::json(429, "Too Many Requests"),
+            StubResponse::json(200, r#"{"success":true}"#),
+        ]);
+        let client = test_client(&server);
+        client.vote(&ImageKind::Grid, 1, &Vote::Up).await.unwrap();
+        assert_eq!(2, server.requests().len());
+    }
+
+    #[tokio::test]
+    async fn search_with_fake_transport_test() {
+        let transport = FakeTransport::default().with_fixture(
+            "https://www.steamgriddb.com/api/v2/search/autocomplete/Assassin%27s%20Creed",
+            "testdata/search/search.json",
+        );
+        let requests = transport.requests();
+        let client = Client::with_transport("my_auth_key", transport);
+        let results = client.search("Assassin's Creed").await.unwrap();
+        assert_eq!(15, results.len());
+        assert_eq!("Assassin's Creed", results[0].name);
+        let requests = requests.lock().unwrap();
+        assert_eq!(Method::Get, requests[0].method);
+        assert!(requests[0].headers.contains(&(
+            "Authorization".to_string(),
+            "Bearer my_auth_key".to_string()
+        )));
+    }
+
+    #[tokio::test]
+    async fn game_not_found_with_fake_transport_test() {
+        let client = Client::with_transport("my_auth_key", FakeTransport::default());
+        let result = client.get_game_info_for_id(1).await;
+        match result {
+            Err(Error::Api(error)) => assert_eq!(Some(404), error.status),
+            _ => panic!("Expected an api error"),
+        }
+    }
+
+    #[tokio::test]
+    async fn rate_limiter_shared_by_clones_test() {
+        let mut client = Client::with_transport("my_auth_key", FakeTransport::default());
+        client.set_rate_limiter(RateLimiter::new(20.0, 1));
+        let clone = client.clone();
+        let start = std::time::Instant::now();
+        let _ = client.get_game_info_for_id(1).await;
+        let _ = clone.get_game_info_for_id(1).await;
+        let _ = clone.get_game_info_for_id(1).await;
+        assert!(start.elapsed() >= std::time::Duration::from_millis(100));
+        let stats = client.rate_limiter().unwrap().stats();
+        assert_eq!(3, stats.requests);
+        assert_eq!(2, stats.throttled);
+        assert!(stats.total_wait >= std::time::Duration::from_millis(50));
+    }
+
+    #[tokio::test]
+    async fn rate_limiter_token_given_back_when_cancelled_test() {
+        let mut client = Client::with_transport("my_auth_key", FakeTransport::default());
+        client.set_rate_limiter(RateLimiter::new(1.0, 1));
+        let _ = client.get_game_info_for_id(1).await;
+        let waiting = tokio::time::timeout(
+            std::time::Duration::from_millis(20),
+            client.get_game_info_for_id(1),
+        )
+        .await;
+        assert!(waiting.is_err());
+        let stats = client.rate_limiter().unwrap().stats();
+        assert_eq!(1, stats.requests);
+        assert_eq!(0, stats.throttled);
+        assert_eq!(std::time::Duration::ZERO, stats.total_wait);
+    }
+
+    #[tokio::test]
+    async fn cached_response_test() {
+        let json = std::fs::read_to_string("testdata/grids/grid_by_id.json").unwrap();
+        let server = StubServer::start(vec![StubResponse::json(200, &json)]);
+        let mut client = test_client(&server);
+        client.set_cache(ResponseCache::new(std::time::Duration::from_secs(60), 10));
+        let first = client.get_grid_by_id(80200).await.unwrap();
+        let second = client.get_grid_by_id(80200).await.unwrap();
+        assert_eq!(first.id, second.id);
+        assert_eq!(1, server.requests().len());
+    }
+
+    #[tokio::test]
+    async fn cache_revalidates_with_etag_test() {
+        let json = std::fs::read_to_string("testdata/grids/grid_by_id.json").unwrap();
+        let server = StubServer::start(vec![
+            StubResponse::json(200, &json).with_header("ETag", "\"abc\""),
+            StubResponse::json(304, ""),
+        ]);
+        let mut client = test_client(&server);
+        client.set_cache(ResponseCache::new(std::time::Duration::ZERO, 10));
+        client.get_grid_by_id(80200).await.unwrap();
+        let grid = client.get_grid_by_id(80200).await.unwrap();
+        assert_eq!(80200, grid.id);
+        let requests = server.requests();
+        assert_eq!(2, requests.len());
+        assert_eq!(None, requests[0].header("if-none-match"));
+        assert_eq!(Some("\"abc\""), requests[1].header("if-none-match"));
+    }
+
+    /// Answers revalidations with 304 Not Modified after clearing the cache,
+    /// as if the cached response was evicted while it was revalidated
+    struct EvictingTransport {
+        cache: ResponseCache,
+        if_none_match: Arc<std::sync::Mutex<Vec<Option<String>>>>,
+    }
+
+    impl EvictingTransport {
+        fn respond(&self, request: HttpRequest) -> Result<HttpResponse, Error> {
+            let etag = request
+                .headers
+                .iter()
+                .find(|(name, _)| name == "If-None-Match")
+                .map(|(_, value)| value.clone());
+            let revalidating = etag.is_some();
+            self.if_none_match.lock().unwrap().push(etag);
+            if revalidating {
+                self.cache.clear();
+                return Ok(HttpResponse {
+                    status: 304,
+                    headers: Vec::new(),
+                    body: Vec::new(),
+                });
+            }
+            Ok(HttpResponse {
+                status: 200,
+                headers: vec![("ETag".to_string(), "\"abc\"".to_string())],
+                body: std::fs::read("testdata/grids/grid_by_id.json").unwrap(),
+            })
+        }
+    }
+
+    impl Transport for EvictingTransport {
+        fn send(&self, request: HttpRequest) -> crate::transport::TransportFuture<'_> {
+            let response = self.respond(request);
+            Box::pin(async move { response })
+        }
+
+        fn sleep(&self, _duration: std::time::Duration) -> crate::transport::SleepFuture<'_> {
+            Box::pin(async {})
+        }
+    }
+
+    #[tokio::test]
+    async fn cache_refetches_evicted_response_test() {
+        let cache = ResponseCache::new(std::time::Duration::ZERO, 10);
+        let if_none_match = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let transport = EvictingTransport {
+            cache: cache.clone(),
+            if_none_match: if_none_match.clone(),
+        };
+        let mut client = Client::with_transport("my_auth_key", transport);
+        client.set_cache(cache);
+        client.get_grid_by_id(80200).await.unwrap();
+        let grid = client.get_grid_by_id(80200).await.unwrap();
+        assert_eq!(80200, grid.id);
+        assert_eq!(
+            vec![None, Some("\"abc\"".to_string()), None],
+            *if_none_match.lock().unwrap()
+        );
+    }
+
+    fn image_served_by(server: &StubServer, path: &str) -> Image {
+        let json = std::fs::read_to_string("testdata/grids/grid_by_id.json").unwrap();
+        let response: Response<Image> = serde_json::from_str(&json).unwrap();
+        let mut image = response.data.unwrap();
+        image.url = format!("{}{}", server.url, path);
+        image
+    }
+
+    fn png_response() -> StubResponse {
+        StubResponse {
+            status: 200,
+            headers: vec![("Content-Type".to_string(), "image/png".to_string())],
+            body: b"\x89PNG image".to_vec(),
+            disconnect: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn download_image_test() {
+        let server = StubServer::start(vec![png_response()]);
+        let client = test_client(&server);
+        let image = image_served_by(&server, "/grid.png");
+        let bytes = client.download_image(&image, Variant::Full).await.unwrap();
+        assert_eq!(b"\x89PNG image".to_vec(), bytes);
+        let requests = server.requests();
+        assert_eq!("/grid.png", requests[0].path);
+        assert_eq!(None, requests[0].header("authorization"));
+    }
+
+    #[tokio::test]
+    async fn download_image_to_path_test() {
+        let server = StubServer::start(vec![png_response()]);
+        let client = test_client(&server);
+        let image = image_served_by(&server, "/grid.png");
+        let path = std::env::temp_dir().join(format!(
+            "steamgriddb_api_client_download_{}.png",
+            std::process::id()
+        ));
+        let downloaded = client
+            .download_image_to_path(&image, Variant::Full, &path, &DownloadOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(10, downloaded.size);
+        assert_eq!("image/png", downloaded.content_type);
+        assert_eq!(b"\x89PNG image".to_vec(), std::fs::read(&path).unwrap());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn download_image_to_path_keeps_nothing_on_error_test() {
+        let server = StubServer::start(vec![StubResponse::json(200, "{}")]);
+        let client = test_client(&server);
+        let image = image_served_by(&server, "/grid.png");
+        let path = std::env::temp_dir().join(format!(
+            "steamgriddb_api_client_download_error_{}.png",
+            std::process::id()
+        ));
+        let result = client
+            .download_image_to_path(&image, Variant::Full, &path, &DownloadOptions::default())
+            .await;
+        assert!(matches!(result, Err(Error::ContentType { .. })));
+        assert!(!path.exists());
+    }
+
+    #[tokio::test]
+    async fn download_image_to_path_checks_crc32_test() {
+        let server = StubServer::start(vec![png_response(), png_response()]);
+        let client = test_client(&server);
+        let image = image_served_by(&server, "/grid.png");
+        let path = std::env::temp_dir().join(format!(
+            "steamgriddb_api_client_download_crc32_{}.png",
+            std::process::id()
+        ));
+        let options = DownloadOptions {
+            expected_crc32: Some(0),
+            ..Default::default()
+        };
+        let result = client
+            .download_image_to_path(&image, Variant::Full, &path, &options)
+            .await;
+        let actual = crate::crc32::crc32(b"\x89PNG image");
+        assert!(matches!(
+            result,
+            Err(Error::Checksum { expected: 0, actual: a }) if a == actual
+        ));
+        assert!(!path.exists());
+        let options = DownloadOptions {
+            expected_crc32: Some(actual),
+            ..Default::default()
+        };
+        let downloaded = client
+            .download_image_to_path(&image, Variant::Full, &path, &options)
+            .await
+            .unwrap();
+        assert_eq!(actual, downloaded.crc32);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn install_artwork_refuses_webp_test() {
+        let server = StubServer::start(vec![]);
+        let client = test_client(&server);
+        let mut image = image_served_by(&server, "/grid.webp");
+        image.mime = crate::images::MimeTypes::from("image/webp".to_string());
+        let folder = GridFolder::new(std::env::temp_dir().join(format!(
+            "steamgriddb_api_client_install_webp_{}",
+            std::process::id()
+        )));
+        let result = client
+            .install_artwork(&folder, 504230, ArtworkKind::GridPortrait, &image)
+            .await;
+        assert!(matches!(result, Err(Error::ContentType { .. })));
+        assert!(server.requests().is_empty());
+        assert!(!folder.path().exists());
+    }
+
+    #[tokio::test]
+    async fn install_artwork_test() {
+        let server = StubServer::start(vec![png_response()]);
+        let client = test_client(&server);
+        let image = image_served_by(&server, "/grid.png");
+        let folder = GridFolder::new(std::env::temp_dir().join(format!(
+            "steamgriddb_api_client_install_{}",
+            std::process::id()
+        )));
+        folder.create().unwrap();
+        std::fs::write(
+            folder.file_path(504230, ArtworkKind::GridPortrait, "jpg"),
+            b"old",
+        )
+        .unwrap();
+        std::fs::write(folder.file_path(504230, ArtworkKind::Hero, "jpg"), b"old").unwrap();
+
+        let kind = ArtworkKind::for_image(&ImageKind::Grid, &image);
+        assert_eq!(ArtworkKind::GridPortrait, kind);
+        let installed = client
+            .install_artwork(&folder, 504230, kind, &image)
+            .await
+            .unwrap();
+        assert_eq!(folder.file_path(504230, kind, "png"), installed.path);
+        let kinds: Vec<(ArtworkKind, bool)> = folder
+            .installed_for(504230)
+            .unwrap()
+            .into_iter()
+            .map(|artwork| (artwork.kind, artwork.path == installed.path))
+            .collect();
+        assert_eq!(
+            vec![
+                (ArtworkKind::GridPortrait, true),
+                (ArtworkKind::Hero, false)
+            ],
+            kinds
+        );
+        std::fs::remove_dir_all(folder.path().parent().unwrap().parent().unwrap()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn check_official_steam_images_test() {
+        let base = "https://cdn.cloudflare.steamstatic.com/steam/apps/361420";
+        let transport = FakeTransport::default()
+            .with_response(
+                &format!("{}/header.jpg", base),
+                200,
+                &[("Content-Type", "image/jpeg"), ("Content-Length", "84564")],
+            )
+            .with_response(
+                &format!("{}/library_600x900.jpg", base),
+                200,
+                &[("Content-Type", "image/jpeg")],
+            )
+            .with_response(&format!("{}/logo.png", base), 200, &[]);
+        let requests = transport.requests();
+        let client = Client::with_transport("my_auth_key", transport);
+        let images = client.check_official_steam_images("361420").await.unwrap();
+
+        assert!(images.header.exists);
+        assert_eq!(Some(84564), images.header.content_length);
+        assert_eq!(Some("image/jpeg".to_string()), images.header.content_type);
+        assert!(images.capsule.exists);
+        assert_eq!(format!("{}/library_600x900.jpg", base), images.capsule.url);
+        assert!(!images.hero.exists);
+        assert_eq!(format!("{}/library_hero.jpg", base), images.hero.url);
+        assert!(images.logo.exists);
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(5, requests.len());
+        assert!(requests
+            .iter()
+            .all(|request| request.method == Method::Head));
+        assert!(requests.iter().all(|request| request
+            .headers
+            .iter()
+            .all(|(name, _)| name != "Authorization")));
+    }
+
+    #[tokio::test]
+    async fn resolve_artwork_by_steam_app_id_test() {
+        let policy = ArtworkPolicy::default();
+        let images_url =
+            |query: &QueryType| get_images_by_game_id_url(DEFAULT_BASE_URL, 13136, query);
+        let official = SteamStaticUrls::new("504230");
+        let transport = FakeTransport::default()
+            .with_fixture(
+                "https://www.steamgriddb.com/api/v2/games/steam/504230",
+                "testdata/games/game.json",
+            )
+            .with_fixture(
+                &images_url(&policy.grid[0]),
+                "testdata/grids/grids_for_single_id.json",
+            )
+            .with_json(
+                &images_url(&policy.wide_grid[0]),
+                200,
+                r#"{"success":true,"data":[]}"#,
+            )
+            .with_response(&official.header, 200, &[("Content-Type", "image/jpeg")])
+            .with_fixture(&images_url(&policy.hero[1]), "testdata/heroes/heroes.json")
+            .with_fixture(
+                &images_url(&policy.icon[0]),
+                "testdata/icons/icons_for_single_id.json",
+            );
+        let client = Client::with_transport("my_auth_key", transport);
+        let resolver = ArtworkResolver::with_policy(&client, policy);
+        let artwork = resolver
+            .resolve(&GameIdentity::SteamAppId(504230))
+            .await
+            .unwrap();
+
+        assert_eq!(Some(13136), artwork.game_id);
+        match artwork.get(ArtworkKind::GridPortrait) {
+            Some(ResolvedArtwork::SteamGridDb(image)) => assert_eq!(80200, image.id),
+            _ => panic!("Expected a SteamGridDB grid"),
+        }
+        let header = ResolvedArtwork::Official {
+            asset: SteamAsset::Header,
+            url: official.header.clone(),
+        };
+        assert_eq!(Some(header), artwork.wide_grid);
+        match &artwork.hero {
+            Some(ResolvedArtwork::SteamGridDb(image)) => assert_eq!(25973, image.id),
+            _ => panic!("Expected a SteamGridDB hero, from the second query"),
+        }
+        assert_eq!(None, artwork.logo);
+        assert!(matches!(
+            artwork.icon,
+            Some(ResolvedArtwork::SteamGridDb(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn resolve_artwork_by_name_test() {
+        let policy = ArtworkPolicy {
+            official_fallback: false,
+            ..Default::default()
+        };
+        let transport = FakeTransport::default()
+            .with_fixture(
+                "https://www.steamgriddb.com/api/v2/search/autocomplete/Assassin%27s%20Creed",
+                "testdata/search/search.json",
+            )
+            .with_fixture(
+                &get_images_by_game_id_url(DEFAULT_BASE_URL, 1451, &policy.hero[0]),
+                "testdata/heroes/heroes.json",
+            );
+        let requests = transport.requests();
+        let client = Client::with_transport("my_auth_key", transport);
+        let resolver = ArtworkResolver::with_policy(&client, policy);
+        let identity = GameIdentity::Name("Assassin's Creed".to_string());
+        let hero = resolver
+            .resolve_kind(&identity, ArtworkKind::Hero)
+            .await
+            .unwrap();
+        assert!(matches!(hero, Some(ResolvedArtwork::SteamGridDb(_))));
+
+        let artwork = resolver.resolve(&identity).await.unwrap();
+        assert_eq!(Some(1451), artwork.game_id);
+        assert_eq!(hero, artwork.hero);
+        assert_eq!(None, artwork.grid);
+        assert_eq!(None, artwork.logo);
+        let requests = requests.lock().unwrap();
+        assert!(requests.iter().all(|request| request.method == Method::Get));
+    }
+
+    #[tokio::test]
+    async fn resolve_artwork_for_unknown_game_test() {
+        let steam_app_id = "361420";
+        let official = SteamStaticUrls::new(steam_app_id);
+        let transport = FakeTransport::default()
+            .with_response(&official.logo, 200, &[("Content-Type", "image/png")])
+            .with_response(&official.hero, 200, &[]);
+        let client = Client::with_transport("my_auth_key", transport);
+        let resolver = ArtworkResolver::new(&client);
+        let identity = GameIdentity::PlatformId(Platform::Steam, steam_app_id.to_string());
+        let artwork = resolver.resolve(&identity).await.unwrap();
+        assert_eq!(None, artwork.game_id);
+        assert_eq!(None, artwork.grid);
+        assert_eq!(
+            Some(official.hero.as_str()),
+            artwork.hero.as_ref().map(|hero| hero.url())
+        );
+        assert_eq!(
+            Some(official.logo.as_str()),
+            artwork.logo.as_ref().map(|logo| logo.url())
+        );
+        assert_eq!(None, artwork.icon);
+    }
+
+    #[tokio::test]
+    async fn resolve_artwork_official_check_failed_test() {
+        let steam_app_id = "361420";
+        let official = SteamStaticUrls::new(steam_app_id);
+        let transport = FakeTransport::default()
+            .with_response(&official.logo, 200, &[("Content-Type", "image/png")])
+            .with_response(&official.hero, 503, &[]);
+        let mut client = Client::with_transport("my_auth_key", transport);
+        client.set_retry_policy(RetryPolicy::none());
+        let resolver = ArtworkResolver::new(&client);
+        let identity = GameIdentity::PlatformId(Platform::Steam, steam_app_id.to_string());
+        let artwork = resolver.resolve(&identity).await.unwrap();
+        assert_eq!(None, artwork.hero);
+        assert_eq!(
+            Some(official.logo.as_str()),
+            artwork.logo.as_ref().map(|logo| logo.url())
+        );
+    }
+
+    #[tokio::test]
+    async fn get_game_by_platform_id_test() {
+        let transport = FakeTransport::default().with_fixture(
+            "https://www.steamgriddb.com/api/v2/games/egs/Salt",
+            "testdata/games/game.json",
+        );
+        let client = Client::with_transport("my_auth_key", transport);
+        let game = client
+            .get_game_by_platform_id(&Platform::EpicGameStore, "Salt")
+            .await
+            .unwrap();
+        assert_eq!(13136, game.id);
+        assert_eq!(vec![Platform::Steam, Platform::EpicGameStore], game.types);
+
+        match client.get_game_by_platform_id(&Platform::GoG, "1").await {
+            Err(Error::Api(error)) => assert_eq!(Some(404), error.status),
+            _ => panic!("Expected an api error"),
+        }
+    }
+
+    #[tokio::test]
+    async fn get_game_with_platform_data_test() {
+        let transport = FakeTransport::default().with_fixture(
+            "https://www.steamgriddb.com/api/v2/games/id/13136?platformdata=steam,gog",
+            "testdata/games/game_with_platform_data.json",
+        );
+        let client = Client::with_transport("my_auth_key", transport);
+        let game = client
+            .get_game(&GameLookup::Id(13136), &[Platform::Steam, Platform::GoG])
+            .await
+            .unwrap();
+        assert_eq!(Some("504230"), game.platform_id(&Platform::Steam));
+        assert_eq!(Some("1207658924"), game.platform_id(&Platform::GoG));
+    }
+
+    #[tokio::test]
+    async fn search_best_match_test() {
+        let transport = FakeTransport::default().with_fixture(
+            "https://www.steamgriddb.com/api/v2/search/autocomplete/Assassins%20Creed%203",
+            "testdata/search/search.json",
+        );
+        let client = Client::with_transport("my_auth_key", transport);
+        let matcher = TitleMatcher::new();
+        let found = client
+            .search_best_match("Assassins Creed 3", &matcher)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(1937, found.result.id);
+        assert_eq!(1.0, found.confidence);
+        let strict = matcher.year(2013).threshold(0.96);
+        let found = client
+            .search_best_match("Assassins Creed 3", &strict)
+            .await
+            .unwrap();
+        assert_eq!(None, found);
+    }
+
+    #[tokio::test]
+    async fn download_image_to_stops_at_max_size_test() {
+        let server = StubServer::start(vec![png_response(), png_response()]);
+        let client = test_client(&server);
+        let image = image_served_by(&server, "/grid.png");
+        let calls = std::sync::Mutex::new(Vec::new());
+        let progress =
+            |received: u64, total: Option<u64>| calls.lock().unwrap().push((received, total));
+        let options = DownloadOptions {
+            max_size: Some(4),
+            progress: Some(&progress),
+            ..Default::default()
+        };
+        let mut bytes = Vec::new();
+        let result = client
+            .download_image_to(&image, Variant::Full, &mut bytes, &options)
+            .await;
+        assert!(matches!(
+            result,
+            Err(Error::TooLarge { limit: 4, size: 10 })
+        ));
+        assert!(bytes.is_empty());
+        assert!(calls.lock().unwrap().is_empty());
+
+        let options = DownloadOptions {
+            progress: Some(&progress),
+            ..Default::default()
+        };
+        client
+            .download_image_to(&image, Variant::Full, &mut bytes, &options)
+            .await
+            .unwrap();
+        assert_eq!(b"\x89PNG image".to_vec(), bytes);
+        let calls = calls.lock().unwrap();
+        assert_eq!(Some(&(0, Some(10))), calls.first());
+        assert_eq!(Some(&(10, Some(10))), calls.last());
+    }
 }
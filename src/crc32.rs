@@ -0,0 +1,59 @@
+//! The CRC-32 checksum (IEEE 802.3), as used by zip, png and Steam.
+
+const POLYNOMIAL: u32 = 0xEDB8_8320;
+
+const TABLE: [u32; 256] = make_table();
+
+const fn make_table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ POLYNOMIAL
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
+
+/// Calculate the CRC-32 checksum of the data.
+pub(crate) fn crc32(data: &[u8]) -> u32 {
+    crc32_update(0, data)
+}
+
+/// Continue the CRC-32 checksum of earlier data with more data.
+pub(crate) fn crc32_update(checksum: u32, data: &[u8]) -> u32 {
+    let mut crc = !checksum;
+    for byte in data {
+        crc = TABLE[((crc ^ u32::from(*byte)) & 0xFF) as usize] ^ (crc >> 8);
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn crc32_test() {
+        assert_eq!(0, crc32(b""));
+        assert_eq!(0xCBF4_3926, crc32(b"123456789"));
+        assert_eq!(
+            0x414F_A339,
+            crc32(b"The quick brown fox jumps over the lazy dog")
+        );
+        assert_eq!(
+            crc32(b"123456789"),
+            crc32_update(crc32_update(crc32(b""), b"1234"), b"56789")
+        );
+    }
+}
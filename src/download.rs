@@ -0,0 +1,592 @@
+//! Downloading the files of images.
+
+use std::borrow::Cow;
+#[cfg(feature = "blocking")]
+use std::io::Read;
+#[cfg(any(feature = "blocking", test))]
+use std::io::Write;
+
+#[cfg(feature = "async")]
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+
+#[cfg(any(feature = "async", feature = "blocking"))]
+use crate::error::body_snippet;
+#[cfg(feature = "async")]
+use crate::transport::BodyStream;
+use crate::{
+    crc32::crc32_update, error::Error, images::Image, transport::StreamingResponse,
+    upload::guess_mime,
+};
+
+/// The default maximum size of a download, 64 MiB.
+pub const DEFAULT_MAX_SIZE: u64 = 64 * 1024 * 1024;
+
+/// The size of the chunks read by the blocking client.
+#[cfg(feature = "blocking")]
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Which file of an image to download
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Variant {
+    /// The full size image
+    Full,
+    /// The thumbnail of the image
+    Thumb,
+}
+
+impl Variant {
+    /// Get the url of this variant of the image
+    ///
+    /// ### Examples
+    /// ```
+    /// # use steamgriddb_api::images::Image;
+    /// use steamgriddb_api::download::Variant;
+    /// # fn example(image: &Image) {
+    /// assert_eq!(image.thumb, Variant::Thumb.url(image));
+    /// # }
+    /// ```
+    pub fn url<'a>(&self, image: &'a Image) -> &'a str {
+        match self {
+            Variant::Full => image.url.as_str(),
+            Variant::Thumb => image.thumb.as_str(),
+        }
+    }
+}
+
+/// Options for downloading an image.
+///
+/// ### Examples
+/// ```
+/// use steamgriddb_api::download::DownloadOptions;
+/// let progress = |received: u64, total: Option<u64>| match total {
+///     Some(total) => println!("{}/{}", received, total),
+///     None => println!("{}", received),
+/// };
+/// let options = DownloadOptions {
+///     max_size: Some(10 * 1024 * 1024),
+///     progress: Some(&progress),
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Clone, Copy)]
+pub struct DownloadOptions<'a> {
+    /// The maximum size of the download in bytes, or None for no limit.
+    ///
+    /// A download is refused when the server announces a larger size, and stopped as soon as more is received.
+    pub max_size: Option<u64>,
+    /// Check that the content type of the download matches the mime type of the image.
+    ///
+    /// Thumbnails are only checked to be images, since they can have another format than the full image.
+    pub verify_content_type: bool,
+    /// Called before the download starts and after each chunk received,
+    /// with the amount of bytes received so far, and the total amount of bytes if the server announced it
+    pub progress: Option<&'a (dyn Fn(u64, Option<u64>) + Sync)>,
+    /// The CRC-32 checksum the downloaded content must have, or None to not check it.
+    ///
+    /// A download with another checksum fails with [`Error::Checksum`] once it is complete.
+    /// What was already written to a writer is not removed, but a download to a path leaves no file.
+    pub expected_crc32: Option<u32>,
+}
+
+impl Default for DownloadOptions<'_> {
+    fn default() -> Self {
+        Self {
+            max_size: Some(DEFAULT_MAX_SIZE),
+            verify_content_type: true,
+            progress: None,
+            expected_crc32: None,
+        }
+    }
+}
+
+/// Information about a downloaded image
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DownloadedImage {
+    /// The size in bytes
+    pub size: u64,
+    /// The content type, like "image/png"
+    pub content_type: String,
+    /// The CRC-32 checksum of the content, as received.
+    ///
+    /// It is only checked when [`DownloadOptions::expected_crc32`] is given.
+    pub crc32: u32,
+}
+
+/// The amount of bytes of an error response that are read, for the error message
+#[cfg(any(feature = "async", feature = "blocking"))]
+const ERROR_BODY_LENGTH: usize = 4 * 1024;
+
+/// The amount of bytes needed to guess the content type, when the server does not say
+const SNIFF_LENGTH: usize = 16;
+
+/// Checks a download while its body arrives, and gives the bytes that can be written.
+///
+/// The announced size is checked before the body is read, and the received size after each chunk,
+/// so a download stops as soon as it is larger than allowed.
+/// Nothing is given to write until the content type is checked.
+pub(crate) struct Download<'a, 'o> {
+    image: &'a Image,
+    variant: Variant,
+    options: &'a DownloadOptions<'o>,
+    /// The size announced by the server
+    total: Option<u64>,
+    /// The checked content type, once it is known
+    content_type: Option<String>,
+    /// The first bytes, kept until the content type can be guessed from them
+    pending: Vec<u8>,
+    size: u64,
+    crc32: u32,
+}
+
+impl<'a, 'o> Download<'a, 'o> {
+    /// Start a download from a response with a success status, before its body is read.
+    pub(crate) fn start<B>(
+        image: &'a Image,
+        variant: Variant,
+        response: &StreamingResponse<B>,
+        options: &'a DownloadOptions<'o>,
+    ) -> Result<Self, Error> {
+        let total = response
+            .header("Content-Length")
+            .and_then(|length| length.trim().parse::<u64>().ok());
+        if let (Some(limit), Some(size)) = (options.max_size, total) {
+            if size > limit {
+                return Err(Error::TooLarge { limit, size });
+            }
+        }
+        let mut download = Self {
+            image,
+            variant,
+            options,
+            total,
+            content_type: None,
+            pending: Vec::new(),
+            size: 0,
+            // The checksum of no data
+            crc32: 0,
+        };
+        if let Some(content_type) = header_content_type(response) {
+            download.check_content_type(content_type)?;
+        }
+        download.report_progress();
+        Ok(download)
+    }
+
+    /// Check a chunk of the body, and get the bytes that can be written now
+    pub(crate) fn receive<'c>(&mut self, chunk: &'c [u8]) -> Result<Cow<'c, [u8]>, Error> {
+        self.size += chunk.len() as u64;
+        if let Some(limit) = self.options.max_size {
+            if self.size > limit {
+                return Err(Error::TooLarge {
+                    limit,
+                    size: self.size,
+                });
+            }
+        }
+        self.crc32 = crc32_update(self.crc32, chunk);
+        let ready = if self.content_type.is_some() {
+            Cow::Borrowed(chunk)
+        } else {
+            self.pending.extend_from_slice(chunk);
+            if self.pending.len() >= SNIFF_LENGTH {
+                Cow::Owned(self.take_pending()?)
+            } else {
+                Cow::Borrowed(&[][..])
+            }
+        };
+        self.report_progress();
+        Ok(ready)
+    }
+
+    /// Finish the download after the whole body is received,
+    /// and get the bytes that are still to be written along with the information about the download
+    pub(crate) fn finish(mut self) -> Result<(Vec<u8>, DownloadedImage), Error> {
+        let remaining = if self.content_type.is_none() {
+            self.take_pending()?
+        } else {
+            Vec::new()
+        };
+        if let Some(expected) = self.options.expected_crc32 {
+            if expected != self.crc32 {
+                return Err(Error::Checksum {
+                    expected,
+                    actual: self.crc32,
+                });
+            }
+        }
+        let downloaded = DownloadedImage {
+            size: self.size,
+            content_type: self.content_type.unwrap_or_default(),
+            crc32: self.crc32,
+        };
+        Ok((remaining, downloaded))
+    }
+
+    /// Check and write a chunk of the body
+    #[cfg(any(feature = "blocking", test))]
+    pub(crate) fn write<W>(&mut self, chunk: &[u8], writer: &mut W) -> Result<(), Error>
+    where
+        W: Write + ?Sized,
+    {
+        writer.write_all(&self.receive(chunk)?)?;
+        Ok(())
+    }
+
+    /// Finish the download and write what is left, after the whole body is written
+    #[cfg(any(feature = "blocking", test))]
+    pub(crate) fn finish_to<W>(self, writer: &mut W) -> Result<DownloadedImage, Error>
+    where
+        W: Write + ?Sized,
+    {
+        let (remaining, downloaded) = self.finish()?;
+        writer.write_all(&remaining)?;
+        writer.flush()?;
+        Ok(downloaded)
+    }
+
+    /// Guess the content type from the first bytes, check it, and take the bytes
+    fn take_pending(&mut self) -> Result<Vec<u8>, Error> {
+        self.check_content_type(guess_mime(&self.pending).to_string())?;
+        Ok(std::mem::take(&mut self.pending))
+    }
+
+    fn check_content_type(&mut self, content_type: String) -> Result<(), Error> {
+        if self.options.verify_content_type {
+            let matches = match self.variant {
+                Variant::Full => content_type == self.image.mime.as_str(),
+                Variant::Thumb => content_type.starts_with("image/"),
+            };
+            if !matches {
+                let expected = match self.variant {
+                    Variant::Full => self.image.mime.as_str(),
+                    Variant::Thumb => "image/*",
+                };
+                return Err(Error::ContentType {
+                    expected: expected.to_string(),
+                    actual: content_type,
+                });
+            }
+        }
+        self.content_type = Some(content_type);
+        Ok(())
+    }
+
+    fn report_progress(&self) {
+        if let Some(progress) = self.options.progress {
+            progress(self.size, self.total);
+        }
+    }
+}
+
+/// The content type the server gives, unless it is missing or does not say what the content is
+fn header_content_type<B>(response: &StreamingResponse<B>) -> Option<String> {
+    response
+        .header("Content-Type")
+        .and_then(|value| value.split(';').next())
+        .map(|value| value.trim().to_ascii_lowercase())
+        .filter(|value| {
+            !value.is_empty()
+                && value != "application/octet-stream"
+                && value != "binary/octet-stream"
+        })
+}
+
+/// The error for a download that failed with the given status, and the start of its body
+#[cfg(any(feature = "async", feature = "blocking"))]
+fn status_error(status: u16, body: &[u8]) -> Error {
+    Error::Status {
+        status,
+        body: body_snippet(body),
+    }
+}
+
+/// Read the body of a download response chunk by chunk, checking it and writing it to the writer.
+#[cfg(feature = "async")]
+pub(crate) async fn stream_download<W>(
+    image: &Image,
+    variant: Variant,
+    mut response: StreamingResponse<Box<dyn BodyStream>>,
+    writer: &mut W,
+    options: &DownloadOptions<'_>,
+) -> Result<DownloadedImage, Error>
+where
+    W: AsyncWrite + Unpin + ?Sized,
+{
+    if !(200..300).contains(&response.status) {
+        let mut body = Vec::new();
+        while body.len() < ERROR_BODY_LENGTH {
+            match response.body.chunk().await? {
+                Some(chunk) => body.extend_from_slice(&chunk),
+                None => break,
+            }
+        }
+        return Err(status_error(response.status, &body));
+    }
+    let mut download = Download::start(image, variant, &response, options)?;
+    while let Some(chunk) = response.body.chunk().await? {
+        writer.write_all(&download.receive(&chunk)?).await?;
+    }
+    let (remaining, downloaded) = download.finish()?;
+    writer.write_all(&remaining).await?;
+    writer.flush().await?;
+    Ok(downloaded)
+}
+
+/// Read the body of a download response in chunks, checking it and writing it to the writer.
+#[cfg(feature = "blocking")]
+pub(crate) fn stream_download_blocking<W>(
+    image: &Image,
+    variant: Variant,
+    mut response: StreamingResponse<Box<dyn Read + Send>>,
+    writer: &mut W,
+    options: &DownloadOptions<'_>,
+) -> Result<DownloadedImage, Error>
+where
+    W: Write + ?Sized,
+{
+    if !(200..300).contains(&response.status) {
+        let mut body = Vec::new();
+        response
+            .body
+            .take(ERROR_BODY_LENGTH as u64)
+            .read_to_end(&mut body)?;
+        return Err(status_error(response.status, &body));
+    }
+    let mut download = Download::start(image, variant, &response, options)?;
+    let mut buffer = vec![0; CHUNK_SIZE];
+    loop {
+        let read = match response.body.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(error) if error.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(error) => return Err(error.into()),
+        };
+        download.write(&buffer[..read], writer)?;
+    }
+    download.finish_to(writer)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::{crc32::crc32, response::Response};
+
+    fn test_image() -> Image {
+        let json = std::fs::read_to_string("testdata/grids/grid_by_id.json").unwrap();
+        let response: Response<Image> = serde_json::from_str(&json).unwrap();
+        response.data.unwrap()
+    }
+
+    fn response(headers: &[(&str, &str)]) -> StreamingResponse<()> {
+        response_with_body(headers, ())
+    }
+
+    fn response_with_body<B>(headers: &[(&str, &str)], body: B) -> StreamingResponse<B> {
+        StreamingResponse {
+            status: 200,
+            headers: headers
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+            body,
+        }
+    }
+
+    fn download_all(
+        image: &Image,
+        variant: Variant,
+        headers: &[(&str, &str)],
+        body: &[u8],
+        options: &DownloadOptions,
+    ) -> Result<DownloadedImage, Error> {
+        let mut download = Download::start(image, variant, &response(headers), options)?;
+        let mut target = Vec::new();
+        download.write(body, &mut target)?;
+        download.finish_to(&mut target)
+    }
+
+    #[test]
+    fn download_content_type_test() {
+        let image = test_image();
+        let options = DownloadOptions::default();
+        let headers = [("Content-Type", "image/png; charset=binary")];
+        let downloaded = download_all(&image, Variant::Full, &headers, b"\x89PNG", &options);
+        assert_eq!("image/png", downloaded.unwrap().content_type);
+    }
+
+    #[test]
+    fn download_guesses_content_type_test() {
+        let image = test_image();
+        let options = DownloadOptions::default();
+        let headers = [("Content-Type", "application/octet-stream")];
+        let downloaded = download_all(&image, Variant::Full, &headers, b"\x89PNG", &options);
+        assert_eq!("image/png", downloaded.unwrap().content_type);
+        let downloaded = download_all(&image, Variant::Full, &[], b"\x89PNG", &options);
+        assert_eq!("image/png", downloaded.unwrap().content_type);
+    }
+
+    #[test]
+    fn download_content_type_mismatch_test() {
+        let image = test_image();
+        let options = DownloadOptions::default();
+        let jpeg = response(&[("Content-Type", "image/jpeg")]);
+        match Download::start(&image, Variant::Full, &jpeg, &options) {
+            Err(Error::ContentType { expected, actual }) => {
+                assert_eq!("image/png", expected);
+                assert_eq!("image/jpeg", actual);
+            }
+            _ => panic!("Expected a content type error"),
+        }
+        assert!(Download::start(&image, Variant::Thumb, &jpeg, &options).is_ok());
+        let html = [("Content-Type", "text/html")];
+        assert!(download_all(&image, Variant::Thumb, &html, b"<html>", &options).is_err());
+        let unchecked = DownloadOptions {
+            verify_content_type: false,
+            ..Default::default()
+        };
+        assert!(download_all(&image, Variant::Full, &html, b"<html>", &unchecked).is_ok());
+    }
+
+    #[test]
+    fn download_too_large_test() {
+        let image = test_image();
+        let options = DownloadOptions {
+            max_size: Some(3),
+            ..Default::default()
+        };
+        // Refused before reading the body, when the server announces the size
+        let announced = response(&[("Content-Length", "100")]);
+        let result = Download::start(&image, Variant::Full, &announced, &options);
+        assert!(matches!(
+            result,
+            Err(Error::TooLarge {
+                limit: 3,
+                size: 100
+            })
+        ));
+        // Stopped while reading, when it does not
+        let result = download_all(&image, Variant::Full, &[], b"\x89PNG", &options);
+        assert!(matches!(result, Err(Error::TooLarge { limit: 3, size: 4 })));
+    }
+
+    #[test]
+    fn download_progress_test() {
+        let image = test_image();
+        let body = vec![7u8; 100];
+        let calls = Mutex::new(Vec::new());
+        let progress =
+            |received: u64, total: Option<u64>| calls.lock().unwrap().push((received, total));
+        let options = DownloadOptions {
+            progress: Some(&progress),
+            verify_content_type: false,
+            ..Default::default()
+        };
+        let headers = response(&[("Content-Type", "image/png"), ("Content-Length", "100")]);
+        let mut download = Download::start(&image, Variant::Full, &headers, &options).unwrap();
+        let mut target = Vec::new();
+        for chunk in body.chunks(40) {
+            download.write(chunk, &mut target).unwrap();
+        }
+        let downloaded = download.finish_to(&mut target).unwrap();
+        assert_eq!(body, target);
+        assert_eq!(100, downloaded.size);
+        assert_eq!(crc32(&body), downloaded.crc32);
+        assert_eq!(
+            vec![
+                (0, Some(100)),
+                (40, Some(100)),
+                (80, Some(100)),
+                (100, Some(100))
+            ],
+            *calls.lock().unwrap()
+        );
+    }
+
+    /// A body of the given chunks, that counts how many were read
+    #[cfg(feature = "async")]
+    struct CountingBody {
+        chunks: Vec<Vec<u8>>,
+        read: std::sync::Arc<Mutex<usize>>,
+    }
+
+    #[cfg(feature = "async")]
+    impl BodyStream for CountingBody {
+        fn chunk(&mut self) -> crate::transport::ChunkFuture<'_> {
+            *self.read.lock().unwrap() += 1;
+            let chunk = if self.chunks.is_empty() {
+                None
+            } else {
+                Some(self.chunks.remove(0))
+            };
+            Box::pin(async move { Ok(chunk) })
+        }
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn stream_download_stops_at_max_size_test() {
+        let image = test_image();
+        let read = std::sync::Arc::new(Mutex::new(0));
+        let body = CountingBody {
+            chunks: vec![b"\x89PNG".to_vec(); 10],
+            read: read.clone(),
+        };
+        let body: Box<dyn BodyStream> = Box::new(body);
+        let response = response_with_body(&[("Content-Type", "image/png")], body);
+        let options = DownloadOptions {
+            max_size: Some(10),
+            ..Default::default()
+        };
+        let mut target = Vec::new();
+        let result = stream_download(&image, Variant::Full, response, &mut target, &options).await;
+        assert!(matches!(
+            result,
+            Err(Error::TooLarge {
+                limit: 10,
+                size: 12
+            })
+        ));
+        assert_eq!(3, *read.lock().unwrap());
+        assert_eq!(8, target.len());
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn stream_download_error_status_test() {
+        let image = test_image();
+        let response = StreamingResponse {
+            status: 404,
+            headers: Vec::new(),
+            body: Box::new(Some(b"Not found".to_vec())) as Box<dyn BodyStream>,
+        };
+        let mut target = Vec::new();
+        let options = DownloadOptions::default();
+        match stream_download(&image, Variant::Full, response, &mut target, &options).await {
+            Err(Error::Status { status, body }) => {
+                assert_eq!(404, status);
+                assert_eq!("Not found", body);
+            }
+            _ => panic!("Expected a status error"),
+        }
+    }
+
+    #[cfg(feature = "blocking")]
+    #[test]
+    fn stream_download_blocking_test() {
+        let image = test_image();
+        let body = b"\x89PNG image".to_vec();
+        let reader: Box<dyn Read + Send> = Box::new(std::io::Cursor::new(body.clone()));
+        let response = response_with_body(&[], reader);
+        let mut target = Vec::new();
+        let options = DownloadOptions::default();
+        let downloaded =
+            stream_download_blocking(&image, Variant::Full, response, &mut target, &options)
+                .unwrap();
+        assert_eq!(body, target);
+        assert_eq!("image/png", downloaded.content_type);
+        assert_eq!(crc32(&body), downloaded.crc32);
+    }
+}
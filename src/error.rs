@@ -0,0 +1,165 @@
+use std::{error, fmt};
+
+use crate::response::SteamGridDbError;
+
+/// The maximum amount of characters of a response body kept in an error.
+const BODY_SNIPPET_LENGTH: usize = 512;
+
+/// Errors that can occur when calling the steamgriddb api.
+#[derive(Debug)]
+pub enum Error {
+    /// The request could not be sent, or the response could not be read.
+    ///
+    /// Contains the error from the transport.
+    Http(Box<dyn error::Error + Send + Sync>),
+    /// The server answered with a non success status code, without an api error body.
+    Status {
+        /// The HTTP status code
+        status: u16,
+        /// The start of the response body
+        body: String,
+    },
+    /// The response body could not be deserialized.
+    Deserialize {
+        /// The underlying serde error
+        source: serde_json::Error,
+        /// The start of the response body that failed to deserialize
+        body: String,
+    },
+    /// The api reported an error, like "Game not found".
+    Api(SteamGridDbError),
+    /// The auth key is invalid, or the user is not allowed to do this, like deleting another users image.
+    PermissionDenied(SteamGridDbError),
+    /// The api reported success, but the response contained no data.
+    EmptyData,
+    /// A file could not be read or written.
+    Io(std::io::Error),
+    /// A download was larger than the allowed size.
+    TooLarge {
+        /// The maximum allowed size in bytes
+        limit: u64,
+        /// The size of the download in bytes
+        size: u64,
+    },
+    /// A download had another content type than expected.
+    ContentType {
+        /// The expected content type, like "image/png"
+        expected: String,
+        /// The content type of the download
+        actual: String,
+    },
+    /// A download had another CRC-32 checksum than expected.
+    Checksum {
+        /// The expected checksum
+        expected: u32,
+        /// The checksum of the download
+        actual: u32,
+    },
+    /// A Steam VDF file could not be parsed.
+    Vdf(String),
+}
+
+impl Error {
+    /// The HTTP or api status code of the error, if known.
+    ///
+    /// ### Examples
+    /// ```
+    /// use steamgriddb_api::Error;
+    /// use steamgriddb_api::response::SteamGridDbError;
+    /// let error = Error::Api(SteamGridDbError {
+    ///     status: Some(404),
+    ///     errors: Some(vec!["Game not found".to_string()]),
+    /// });
+    /// assert_eq!(Some(404), error.status());
+    /// ```
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            Error::Status { status, .. } => Some(*status),
+            Error::Api(error) | Error::PermissionDenied(error) => error.status.map(|s| s as u16),
+            Error::Http(_)
+            | Error::Deserialize { .. }
+            | Error::EmptyData
+            | Error::Io(_)
+            | Error::TooLarge { .. }
+            | Error::ContentType { .. }
+            | Error::Checksum { .. }
+            | Error::Vdf(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Http(error) => write!(f, "HTTP error: {}", error),
+            Error::Status { status, body } => {
+                write!(f, "Server responded with status {}: {}", status, body)
+            }
+            Error::Deserialize { source, body } => {
+                write!(f, "Could not parse response: {} (body: {})", source, body)
+            }
+            Error::Api(error) => error.fmt(f),
+            Error::PermissionDenied(error) => write!(f, "Permission denied: {}", error),
+            Error::EmptyData => write!(f, "Success reported but no data found"),
+            Error::Io(error) => write!(f, "IO error: {}", error),
+            Error::TooLarge { limit, size } => write!(
+                f,
+                "Download of {} bytes is larger than the limit of {} bytes",
+                size, limit
+            ),
+            Error::ContentType { expected, actual } => {
+                write!(f, "Expected content type {}, but got {}", expected, actual)
+            }
+            Error::Checksum { expected, actual } => write!(
+                f,
+                "Expected CRC-32 checksum {:08x}, but got {:08x}",
+                expected, actual
+            ),
+            Error::Vdf(message) => write!(f, "Could not parse VDF: {}", message),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::Http(error) => Some(error.as_ref()),
+            Error::Deserialize { source, .. } => Some(source),
+            Error::Api(error) | Error::PermissionDenied(error) => Some(error),
+            Error::Io(error) => Some(error),
+            Error::Status { .. }
+            | Error::EmptyData
+            | Error::TooLarge { .. }
+            | Error::ContentType { .. }
+            | Error::Checksum { .. }
+            | Error::Vdf(_) => None,
+        }
+    }
+}
+
+#[cfg(feature = "reqwest")]
+impl From<reqwest::Error> for Error {
+    fn from(error: reqwest::Error) -> Self {
+        Error::Http(Box::new(error))
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(error: std::io::Error) -> Self {
+        Error::Io(error)
+    }
+}
+
+impl From<SteamGridDbError> for Error {
+    fn from(error: SteamGridDbError) -> Self {
+        Error::Api(error)
+    }
+}
+
+/// Get the start of a response body, to include in errors.
+pub(crate) fn body_snippet(body: &[u8]) -> String {
+    String::from_utf8_lossy(body)
+        .chars()
+        .take(BODY_SNIPPET_LENGTH)
+        .collect()
+}
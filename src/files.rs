@@ -0,0 +1,99 @@
+//! Helpers for writing files.
+
+use std::{
+    fs::{self, File},
+    path::{Path, PathBuf},
+};
+#[cfg(feature = "async")]
+use std::{
+    io::Write,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use crate::error::Error;
+
+/// A file that is written next to its path with a ".tmp" extension, and renamed to the path when committed,
+/// so the path never contains a half written file.
+///
+/// The temporary file is removed when it is dropped without being committed.
+pub(crate) struct AtomicFile {
+    path: PathBuf,
+    temp: PathBuf,
+    file: File,
+    committed: bool,
+}
+
+impl AtomicFile {
+    pub(crate) fn create(path: &Path) -> Result<Self, Error> {
+        let mut temp = path.as_os_str().to_owned();
+        temp.push(".tmp");
+        let temp = PathBuf::from(temp);
+        let file = File::create(&temp)?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            temp,
+            file,
+            committed: false,
+        })
+    }
+
+    /// The temporary file to write to
+    pub(crate) fn file(&mut self) -> &mut File {
+        &mut self.file
+    }
+
+    /// Sync the temporary file to disk, and rename it to the path
+    pub(crate) fn commit(mut self) -> Result<(), Error> {
+        self.file.sync_all()?;
+        fs::rename(&self.temp, &self.path)?;
+        self.committed = true;
+        Ok(())
+    }
+}
+
+/// Writes go straight to the temporary file, since writing a local file does not wait like the network does.
+#[cfg(feature = "async")]
+impl tokio::io::AsyncWrite for AtomicFile {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        Poll::Ready(self.file.write(buf))
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Poll::Ready(self.file.flush())
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        self.poll_flush(cx)
+    }
+}
+
+impl Drop for AtomicFile {
+    fn drop(&mut self) {
+        if !self.committed {
+            let _ = fs::remove_file(&self.temp);
+        }
+    }
+}
+
+/// Write a file by writing to a temporary file next to it, and renaming it when done,
+/// so the file is never left half written.
+pub(crate) fn write_atomically_with<T, F>(path: &Path, write: F) -> Result<T, Error>
+where
+    F: FnOnce(&mut File) -> Result<T, Error>,
+{
+    let mut file = AtomicFile::create(path)?;
+    let value = write(file.file())?;
+    file.commit()?;
+    Ok(value)
+}
+
+/// Write the content to a file atomically, see [`write_atomically_with`].
+pub(crate) fn write_atomically(path: &Path, content: &[u8]) -> Result<(), Error> {
+    use std::io::Write;
+    write_atomically_with(path, |file| Ok(file.write_all(content)?))
+}
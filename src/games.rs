@@ -1,4 +1,7 @@
-use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
 
 use crate::query_parameters::Platform;
 
@@ -12,6 +15,69 @@ pub fn get_game_by_steam_app_id_url(base_url: &str, steam_app_id: usize) -> Stri
     format!("{}/games/steam/{}", base_url, steam_app_id)
 }
 
+/// Get the URL to get info about a game, given a platform and a platform specific id.
+///
+/// The id is url encoded.
+///
+/// ### Examples
+/// ```
+/// use steamgriddb_api::games::get_game_by_platform_id_url;
+/// use steamgriddb_api::query_parameters::Platform;
+/// let url = get_game_by_platform_id_url("https://www.steamgriddb.com/api/v2", &Platform::GoG, "1207658924");
+/// assert_eq!("https://www.steamgriddb.com/api/v2/games/gog/1207658924", url);
+/// ```
+pub fn get_game_by_platform_id_url(base_url: &str, platform: &Platform, game_id: &str) -> String {
+    format!(
+        "{}/games/{}/{}",
+        base_url,
+        platform,
+        urlencoding::encode(game_id)
+    )
+}
+
+/// The ways to look up a game
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameLookup<'a> {
+    /// By its steamgriddb id
+    Id(usize),
+    /// By its steam app id
+    SteamAppId(usize),
+    /// By a platform and a platform specific id
+    PlatformId(&'a Platform, &'a str),
+}
+
+/// Get the URL to get info about a game, including its ids and store data on the given platforms.
+///
+/// Without platforms, this is the same url as the url builders for each lookup.
+///
+/// ### Examples
+/// ```
+/// use steamgriddb_api::games::{get_game_url, GameLookup};
+/// use steamgriddb_api::query_parameters::Platform;
+/// let url = get_game_url(
+///     "https://www.steamgriddb.com/api/v2",
+///     &GameLookup::Id(13136),
+///     &[Platform::Steam, Platform::GoG],
+/// );
+/// assert_eq!("https://www.steamgriddb.com/api/v2/games/id/13136?platformdata=steam,gog", url);
+/// ```
+pub fn get_game_url(base_url: &str, lookup: &GameLookup, platform_data: &[Platform]) -> String {
+    let url = match lookup {
+        GameLookup::Id(game_id) => get_gameinfo_by_game_id_url(base_url, *game_id),
+        GameLookup::SteamAppId(steam_app_id) => {
+            get_game_by_steam_app_id_url(base_url, *steam_app_id)
+        }
+        GameLookup::PlatformId(platform, game_id) => {
+            get_game_by_platform_id_url(base_url, platform, game_id)
+        }
+    };
+    if platform_data.is_empty() {
+        return url;
+    }
+    let platforms: Vec<String> = platform_data.iter().map(Platform::to_string).collect();
+    format!("{}?platformdata={}", url, platforms.join(","))
+}
+
 /// Information about a game
 #[derive(Serialize, Deserialize, Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub struct GameInfo {
@@ -25,9 +91,105 @@ pub struct GameInfo {
     pub release_date: Option<usize>,
     /// If this game has been verified
     pub verified: bool,
+    /// The ids and store data of the game on other platforms.
+    ///
+    /// Only filled in for the platforms asked for with [`get_game_url`].
+    #[serde(default, deserialize_with = "platform_data")]
+    pub external_platform_data: BTreeMap<Platform, Vec<ExternalPlatformData>>,
+}
+
+impl GameInfo {
+    /// The first id of the game on the platform, if it was requested and is known.
+    ///
+    /// ### Examples
+    /// ```
+    /// # use steamgriddb_api::games::GameInfo;
+    /// use steamgriddb_api::query_parameters::Platform;
+    /// # fn example(game: &GameInfo) {
+    /// if let Some(gog_id) = game.platform_id(&Platform::GoG) {
+    ///     println!("The GOG id is {}", gog_id);
+    /// }
+    /// # }
+    /// ```
+    pub fn platform_id(&self, platform: &Platform) -> Option<&str> {
+        self.external_platform_data
+            .get(platform)
+            .and_then(|entries| entries.first())
+            .map(|entry| entry.id.as_str())
+    }
+}
+
+/// The id and store data of a game on another platform
+#[derive(Serialize, Deserialize, Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ExternalPlatformData {
+    /// The id of the game on the platform
+    #[serde(deserialize_with = "text")]
+    pub id: String,
+    /// The name of the game on the platform
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Store data of the game, like the `clienticon` on Steam.
+    ///
+    /// Values that are not strings are kept as JSON text.
+    #[serde(default, deserialize_with = "text_map")]
+    pub metadata: BTreeMap<String, String>,
+}
+
+/// Turn a JSON value into text, keeping strings as they are
+fn value_to_text(value: Value) -> String {
+    match value {
+        Value::String(text) => text,
+        other => other.to_string(),
+    }
+}
+
+fn text<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Value::deserialize(deserializer).map(value_to_text)
+}
+
+fn text_map<'de, D>(deserializer: D) -> Result<BTreeMap<String, String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let map = Option::<BTreeMap<String, Value>>::deserialize(deserializer)?;
+    Ok(map
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(key, value)| (key, value_to_text(value)))
+        .collect())
+}
+
+/// Keep the entries that can be read, and allow a single entry without a list
+fn platform_data<'de, D>(
+    deserializer: D,
+) -> Result<BTreeMap<Platform, Vec<ExternalPlatformData>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let map = Option::<BTreeMap<String, Value>>::deserialize(deserializer)?;
+    Ok(map
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|(platform, entries)| {
+            let entries = match entries {
+                Value::Array(entries) => entries,
+                entry @ Value::Object(_) => vec![entry],
+                _ => return None,
+            };
+            let entries = entries
+                .into_iter()
+                .filter_map(|entry| serde_json::from_value(entry).ok())
+                .collect();
+            Some((Platform::from(platform), entries))
+        })
+        .collect())
 }
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod tests {
 
     use super::*;
@@ -47,6 +209,19 @@ mod tests {
         let url = get_game_by_steam_app_id_url(base_url, 361420);
         assert_eq!("https://www.steamgriddb.com/api/v2/games/steam/361420", url);
     }
+
+    #[test]
+    fn get_game_by_platform_id_url_test() {
+        let base_url = "https://www.steamgriddb.com/api/v2";
+        let url = get_game_by_platform_id_url(base_url, &Platform::EpicGameStore, "Salt");
+        assert_eq!("https://www.steamgriddb.com/api/v2/games/egs/Salt", url);
+        let url = get_game_by_platform_id_url(base_url, &Platform::Origin, "Origin.OFR/50.0002");
+        assert_eq!(
+            "https://www.steamgriddb.com/api/v2/games/origin/Origin.OFR%2F50.0002",
+            url
+        );
+    }
+
     #[test]
     fn parse_game_response_test() {
         let json = std::fs::read_to_string("testdata/games/game.json").unwrap();
@@ -60,6 +235,60 @@ mod tests {
         assert_eq!("Celeste", data.name);
         assert_eq!(1516867200, data.release_date.unwrap());
         assert_eq!(true, data.verified);
+        assert!(data.external_platform_data.is_empty());
+        assert_eq!(None, data.platform_id(&Platform::Steam));
+    }
+
+    #[test]
+    fn get_game_url_test() {
+        let base_url = "https://www.steamgriddb.com/api/v2";
+        let lookup = GameLookup::PlatformId(&Platform::EpicGameStore, "Salt");
+        assert_eq!(
+            get_game_by_platform_id_url(base_url, &Platform::EpicGameStore, "Salt"),
+            get_game_url(base_url, &lookup, &[])
+        );
+        assert_eq!(
+            "https://www.steamgriddb.com/api/v2/games/steam/504230?platformdata=egs",
+            get_game_url(
+                base_url,
+                &GameLookup::SteamAppId(504230),
+                &[Platform::EpicGameStore]
+            )
+        );
+    }
+
+    #[test]
+    fn parse_game_with_platform_data_test() {
+        let json = std::fs::read_to_string("testdata/games/game_with_platform_data.json").unwrap();
+        let game_response: GameResponse = serde_json::from_str(&json).unwrap();
+        let data = game_response.data.unwrap();
+        let platforms: Vec<&Platform> = data.external_platform_data.keys().collect();
+        assert_eq!(
+            vec![
+                &Platform::Steam,
+                &Platform::EpicGameStore,
+                &Platform::GoG,
+                &Platform::Unknown("xbox".to_string())
+            ],
+            platforms
+        );
+
+        let steam = &data.external_platform_data[&Platform::Steam][0];
+        assert_eq!("504230", steam.id);
+        assert_eq!(Some("Celeste".to_string()), steam.name);
+        assert_eq!(
+            Some(&"1668787357".to_string()),
+            steam.metadata.get("store_asset_mtime")
+        );
+        assert_eq!(Some("1207658924"), data.platform_id(&Platform::GoG));
+        assert!(data.external_platform_data[&Platform::GoG][0]
+            .metadata
+            .is_empty());
+        let epic = &data.external_platform_data[&Platform::EpicGameStore][0];
+        assert_eq!(
+            Some(&r#"["4ea8da2ab2dd4c6e9b8f16c46ec7b3b0"]"#.to_string()),
+            epic.metadata.get("offers")
+        );
     }
 
     #[test]
@@ -70,4 +299,22 @@ mod tests {
         assert_eq!(true, game_response.errors.is_some());
         assert_eq!(vec!["Game not found"], game_response.errors.unwrap());
     }
+
+    #[test]
+    fn parse_game_with_unknown_platform_test() {
+        let json =
+            std::fs::read_to_string("testdata/games/game_with_unknown_platform.json").unwrap();
+        let game_response: GameResponse = serde_json::from_str(&json).unwrap();
+        let data = game_response.data.unwrap();
+        assert_eq!(
+            vec![
+                Platform::Steam,
+                Platform::Unknown("xbox".to_string()),
+                Platform::EpicGameStore
+            ],
+            data.types
+        );
+        let json = serde_json::to_value(&data).unwrap();
+        assert_eq!(serde_json::json!(["steam", "xbox", "egs"]), json["types"]);
+    }
 }
@@ -1,4 +1,4 @@
-use crate::query_parameters::*;
+use crate::{error::Error, query_parameters::*, response::response_to_data};
 
 use serde::{Deserialize, Serialize};
 
@@ -11,17 +11,17 @@ pub struct Image {
     pub score: u32,
     /// The animation style of the image.
     pub style: StyleType,
-    
+
     /// The width of the image
     pub width: u32,
-    
+
     /// The height of the image.
     pub height: u32,
 
     /// Is this image Not Safe For Work?
     pub nsfw: bool,
 
-    /// Is this image humorous? 
+    /// Is this image humorous?
     pub humor: bool,
 
     /// A list of possible notes for the image
@@ -55,13 +55,78 @@ pub struct Image {
     pub author: Author,
 }
 
+impl Image {
+    /// Is this image animated?
+    ///
+    /// The api does not say if an image is animated, so this checks if the thumbnail is a video or a gif,
+    /// since that is how animated images are previewed.
+    pub fn is_animated(&self) -> bool {
+        let extension = |url: &str| {
+            url.rsplit('/')
+                .next()
+                .and_then(|name| name.rsplit_once('.'))
+                .map(|(_, extension)| extension.to_ascii_lowercase())
+        };
+        matches!(
+            extension(&self.thumb).as_deref(),
+            Some("webm") | Some("mp4") | Some("gif")
+        )
+    }
+
+    /// The amount of upvotes minus the amount of downvotes
+    pub fn net_votes(&self) -> i64 {
+        i64::from(self.upvotes) - i64::from(self.downvotes)
+    }
+}
 
 #[derive(Serialize, Deserialize, Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
-#[serde(untagged)]
-pub enum MimeTypes{
+/// The mime type of an image, as the api sends it
+#[serde(from = "String", into = "String")]
+pub enum MimeTypes {
+    /// A mime type of grids and heroes
     Default(MimeType),
+    /// A mime type of logos
     Logo(MimeTypeLogo),
-    Icon(MimeTypeIcon)
+    /// A mime type of icons
+    Icon(MimeTypeIcon),
+    /// A mime type this crate does not know yet, kept as the api sent it
+    Unknown(String),
+}
+
+impl MimeTypes {
+    /// The mime type, like "image/png"
+    pub fn as_str(&self) -> &str {
+        match self {
+            MimeTypes::Default(mime) => mime.as_str(),
+            MimeTypes::Logo(mime) => mime.as_str(),
+            MimeTypes::Icon(mime) => mime.as_str(),
+            MimeTypes::Unknown(value) => value,
+        }
+    }
+}
+
+impl From<String> for MimeTypes {
+    fn from(value: String) -> Self {
+        match MimeType::from(value) {
+            MimeType::Unknown(value) => match MimeTypeLogo::from(value) {
+                MimeTypeLogo::Unknown(value) => match MimeTypeIcon::from(value) {
+                    MimeTypeIcon::Unknown(value) => MimeTypes::Unknown(value),
+                    mime => MimeTypes::Icon(mime),
+                },
+                mime => MimeTypes::Logo(mime),
+            },
+            mime => MimeTypes::Default(mime),
+        }
+    }
+}
+
+impl From<MimeTypes> for String {
+    fn from(value: MimeTypes) -> Self {
+        match value {
+            MimeTypes::Unknown(value) => value,
+            known => known.as_str().to_string(),
+        }
+    }
 }
 
 /// Get an URL to request images for one game given its stemagriddb id.
@@ -77,12 +142,7 @@ pub fn get_images_by_game_ids_url(
 ) -> String {
     let game_id_strings: Vec<String> = game_ids.iter().map(|id| format!("{}", id)).collect();
     let game_ids_str = game_id_strings.join(",");
-    let query_type_str = match config {
-        QueryType::Grid(_) => "grids",
-        QueryType::Hero(_) => "heroes",
-        QueryType::Logo(_) => "logos",
-        QueryType::Icon(_) => "icons",
-    };
+    let query_type_str = config.kind().path();
     let url_without_query = format!("{}/{}/game/{}", base_url, query_type_str, game_ids_str);
     let query_string = config.to_querys();
     if query_string.is_empty() {
@@ -92,7 +152,6 @@ pub fn get_images_by_game_ids_url(
     }
 }
 
-
 /// Get an URL to request an images for a game given a platform and a platform specific id.
 pub fn get_images_by_platform_id_url(
     base_url: &str,
@@ -118,18 +177,10 @@ pub fn get_images_by_platform_ids_url(
     config: &QueryType,
 ) -> String {
     let game_ids_str = game_ids.join(",");
-    let query_type_str = match config {
-        QueryType::Grid(_) => "grids",
-        QueryType::Hero(_) => "heroes",
-        QueryType::Logo(_) => "logos",
-        QueryType::Icon(_) => "icons",
-    };
+    let query_type_str = config.kind().path();
     let url_without_query = format!(
         "{}/{}/{}/{}",
-        base_url,
-        query_type_str,
-        platform.to_string(),
-        game_ids_str
+        base_url, query_type_str, platform, game_ids_str
     );
     let query_string = config.to_querys();
     if query_string.is_empty() {
@@ -139,11 +190,161 @@ pub fn get_images_by_platform_ids_url(
     }
 }
 
+/// Get an URL to request a single image given its steamgriddb id.
+///
+/// ### Examples
+/// ```
+/// use steamgriddb_api::images::*;
+/// use steamgriddb_api::query_parameters::*;
+/// let url = get_image_by_id_url("https://www.steamgriddb.com/api/v2", &ImageKind::Grid, 80200);
+/// assert_eq!("https://www.steamgriddb.com/api/v2/grids/80200", url);
+/// ```
+pub fn get_image_by_id_url(base_url: &str, kind: &ImageKind, image_id: u32) -> String {
+    format!("{}/{}/{}", base_url, kind.path(), image_id)
+}
+
+/// A vote on an image
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Vote {
+    /// Upvote the image
+    Up,
+    /// Downvote the image
+    Down,
+}
+
+/// Get an URL to delete the given images.
+///
+/// ### Examples
+/// ```
+/// use steamgriddb_api::images::*;
+/// use steamgriddb_api::query_parameters::*;
+/// let url = get_delete_images_url("https://www.steamgriddb.com/api/v2", &ImageKind::Grid, &[80200, 80201]);
+/// assert_eq!("https://www.steamgriddb.com/api/v2/grids/80200,80201", url);
+/// ```
+pub fn get_delete_images_url(base_url: &str, kind: &ImageKind, image_ids: &[u32]) -> String {
+    let image_id_strings: Vec<String> = image_ids.iter().map(|id| format!("{}", id)).collect();
+    format!(
+        "{}/{}/{}",
+        base_url,
+        kind.path(),
+        image_id_strings.join(",")
+    )
+}
+
+/// Get an URL to vote on an image.
+///
+/// ### Examples
+/// ```
+/// use steamgriddb_api::images::*;
+/// use steamgriddb_api::query_parameters::*;
+/// let url = get_vote_url("https://www.steamgriddb.com/api/v2", &ImageKind::Hero, 25973, &Vote::Up);
+/// assert_eq!("https://www.steamgriddb.com/api/v2/heroes/25973/vote/up", url);
+/// ```
+pub fn get_vote_url(base_url: &str, kind: &ImageKind, image_id: u32, vote: &Vote) -> String {
+    let vote_str = match vote {
+        Vote::Up => "up",
+        Vote::Down => "down",
+    };
+    format!(
+        "{}/{}/{}/vote/{}",
+        base_url,
+        kind.path(),
+        image_id,
+        vote_str
+    )
+}
+
 pub(crate) type InnerImagesMultipleIdsResponse =
     crate::response::Response<Vec<crate::response::Response<Vec<Image>>>>;
 
 pub(crate) type InnerImagesSingleIdResponse = crate::response::Response<Vec<Image>>;
 
+pub(crate) type InnerImageResponse = crate::response::Response<Image>;
+
+#[cfg(any(feature = "async", feature = "blocking"))]
+pub(crate) type InnerEmptyResponse = crate::response::Response<serde::de::IgnoredAny>;
+
+/// The game a paged image request is made for.
+#[derive(Clone, Copy)]
+pub(crate) enum PagedImagesTarget<'a> {
+    GameId(usize),
+    PlatformId(&'a Platform, &'a str),
+}
+
+/// Keeps track of which page to request next, when walking through all pages of images.
+pub(crate) struct PagedImages<'a> {
+    target: PagedImagesTarget<'a>,
+    query: QueryType<'a>,
+    next_page: usize,
+    seen: usize,
+    done: bool,
+}
+
+impl<'a> PagedImages<'a> {
+    pub(crate) fn new(target: PagedImagesTarget<'a>, query: &QueryType<'a>) -> Self {
+        Self {
+            target,
+            query: *query,
+            next_page: query.page().unwrap_or(0),
+            seen: 0,
+            done: false,
+        }
+    }
+
+    /// The url of the next page to request, or None if all pages have been fetched.
+    pub(crate) fn next_url(&self, base_url: &str) -> Option<String> {
+        if self.done {
+            return None;
+        }
+        let query = self.query.with_page(self.next_page);
+        Some(match self.target {
+            PagedImagesTarget::GameId(game_id) => {
+                get_images_by_game_id_url(base_url, game_id, &query)
+            }
+            PagedImagesTarget::PlatformId(platform, game_id) => {
+                get_images_by_platform_id_url(base_url, platform, game_id, &query)
+            }
+        })
+    }
+
+    /// Registers the result of requesting the last page.
+    ///
+    /// Returns the images of the page, or None if there were no more images.
+    pub(crate) fn handle_response(
+        &mut self,
+        response: Result<InnerImagesSingleIdResponse, Error>,
+    ) -> Option<Result<Vec<Image>, Error>> {
+        let response = match response {
+            Ok(response) => response,
+            Err(error) => {
+                self.done = true;
+                return Some(Err(error));
+            }
+        };
+        let total = response.total;
+        let limit = response.limit.or_else(|| self.query.limit());
+        let images = match response_to_data(response) {
+            Ok(images) => images,
+            Err(error) => {
+                self.done = true;
+                return Some(Err(error));
+            }
+        };
+        if images.is_empty() {
+            self.done = true;
+            return None;
+        }
+        self.seen += images.len();
+        self.next_page += 1;
+        self.done = match (total, limit) {
+            (Some(total), _) => self.seen >= total,
+            (None, Some(limit)) => images.len() < limit,
+            (None, None) => false,
+        };
+        Some(Ok(images))
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
 /// Author of the image
 ///
@@ -158,11 +359,19 @@ pub struct Author {
 }
 
 #[cfg(test)]
+#[allow(
+    clippy::bool_assert_comparison,
+    clippy::field_reassign_with_default,
+    clippy::iter_next_slice
+)]
 mod tests {
 
     use crate::{
         query_parameters::{GridQueryParameters, HeroQueryParameters},
-        response::{response_to_result, response_to_result_flat, SteamGridDbError},
+        response::{
+            response_to_data, response_to_result, response_to_result_all, response_to_result_flat,
+            SteamGridDbError,
+        },
     };
 
     use super::*;
@@ -256,6 +465,60 @@ mod tests {
         );
     }
 
+    #[test]
+    fn get_grids_by_game_id_url_test_epilepsy_and_tags() {
+        let base_url = "https://www.steamgriddb.com/api/v2";
+        let config = GridQueryParameters {
+            epilepsy: Some(&Epilepsy::False),
+            oneoftag: Some(&["humor", "pixel art"]),
+            ..Default::default()
+        };
+        let url = get_images_by_game_id_url(base_url, 13136, &Grid(Some(config)));
+        assert_eq!(
+            "https://www.steamgriddb.com/api/v2/grids/game/13136?epilepsy=false&oneoftag=humor,pixel%20art",
+            url
+        );
+    }
+
+    #[test]
+    fn get_logos_by_game_ids_url_test_full_config() {
+        let base_url = "https://www.steamgriddb.com/api/v2";
+        let config = LogoQueryParameters {
+            styles: Some(&[StyleLogo::Official, StyleLogo::White]),
+            mimes: Some(&[MimeTypeLogo::Png, MimeTypeLogo::Webp]),
+            types: Some(&[AnimtionType::Static]),
+            nsfw: Some(&Nsfw::False),
+            humor: Some(&Humor::False),
+            epilepsy: Some(&Epilepsy::Any),
+            oneoftag: Some(&["text"]),
+            ..Default::default()
+        };
+        let url = get_images_by_game_ids_url(base_url, &[13136, 14065], &Logo(Some(config)));
+        assert_eq!(
+            "https://www.steamgriddb.com/api/v2/logos/game/13136,14065?styles=official,white&mimes=image/png,image/webp&types=static&nsfw=false&humor=false&epilepsy=any&oneoftag=text",
+            url
+        );
+    }
+
+    #[test]
+    fn get_icons_by_game_ids_url_test_full_config() {
+        let base_url = "https://www.steamgriddb.com/api/v2";
+        let config = IconQueryParameters {
+            dimentions: Some(&[IconDimensions::D32x32, IconDimensions::D256x256]),
+            mimes: Some(&[MimeTypeIcon::Png, MimeTypeIcon::Icon]),
+            types: Some(&[AnimtionType::Animated]),
+            nsfw: Some(&Nsfw::Any),
+            humor: Some(&Humor::True),
+            epilepsy: Some(&Epilepsy::True),
+            ..Default::default()
+        };
+        let url = get_images_by_game_ids_url(base_url, &[13136], &Icon(Some(config)));
+        assert_eq!(
+            "https://www.steamgriddb.com/api/v2/icons/game/13136?dimensions=32x32,256x256&mimes=image/png,image/vnd.microsoft.icon&types=animated&nsfw=any&humor=true&epilepsy=true",
+            url
+        );
+    }
+
     #[test]
     fn get_grids_by_platform_ids_url_test() {
         let base_url = "https://www.steamgriddb.com/api/v2";
@@ -410,6 +673,41 @@ mod tests {
         }
     }
 
+    #[test]
+    fn inner_response_multiple_to_all_test() {
+        let json = std::fs::read_to_string("testdata/grids/grids_error.json").unwrap();
+        let game_response: InnerImagesMultipleIdsResponse = serde_json::from_str(&json).unwrap();
+        let grids = response_to_result_all(game_response).unwrap();
+        assert_eq!(grids.len(), 2);
+        let first = grids[0].as_ref().unwrap();
+        assert_eq!(first.len(), 1);
+        assert_eq!(first[0].id, 80200);
+        assert_eq!(
+            grids[1],
+            Err(SteamGridDbError {
+                status: Some(404),
+                errors: Some(vec!["Game not found".to_string()]),
+            })
+        );
+    }
+
+    #[test]
+    fn get_image_by_id_url_test() {
+        let base_url = "https://www.steamgriddb.com/api/v2";
+        let url = get_image_by_id_url(base_url, &ImageKind::Logo, 1234);
+        assert_eq!("https://www.steamgriddb.com/api/v2/logos/1234", url);
+    }
+
+    #[test]
+    fn parse_image_by_id_test() {
+        let json = std::fs::read_to_string("testdata/grids/grid_by_id.json").unwrap();
+        let response: InnerImageResponse = serde_json::from_str(&json).unwrap();
+        let image = response_to_data(response).unwrap();
+        assert_eq!(80200, image.id);
+        assert_eq!(342, image.width);
+        assert_eq!("QuiGonJinnah", image.author.name);
+    }
+
     #[test]
     fn parse_single_id_icon() {
         let json = std::fs::read_to_string("testdata/icons/icons_for_single_id.json").unwrap();
@@ -420,4 +718,162 @@ mod tests {
             assert_eq!(data.len(), 2);
         }
     }
+
+    #[test]
+    fn get_grids_by_game_id_url_test_page_and_limit() {
+        let base_url = "https://www.steamgriddb.com/api/v2";
+        let config = GridQueryParameters {
+            nsfw: Some(&Nsfw::False),
+            page: Some(1),
+            limit: Some(50),
+            ..Default::default()
+        };
+        let url = get_images_by_game_id_url(base_url, 13136, &Grid(Some(config)));
+        assert_eq!(
+            "https://www.steamgriddb.com/api/v2/grids/game/13136?nsfw=false&page=1&limit=50",
+            url
+        );
+    }
+
+    fn paged_response(ids: &[u32], total: Option<usize>) -> InnerImagesSingleIdResponse {
+        let json = std::fs::read_to_string("testdata/heroes/heroes.json").unwrap();
+        let mut response: InnerImagesSingleIdResponse = serde_json::from_str(&json).unwrap();
+        let image = response.data.as_ref().unwrap()[0].clone();
+        response.data = Some(
+            ids.iter()
+                .map(|id| Image {
+                    id: *id,
+                    ..image.clone()
+                })
+                .collect(),
+        );
+        response.total = total;
+        response
+    }
+
+    #[test]
+    fn paged_images_walks_pages_until_total_test() {
+        let base_url = "https://www.steamgriddb.com/api/v2";
+        let mut pages = PagedImages::new(PagedImagesTarget::GameId(13136), &Hero(None));
+        assert_eq!(
+            Some("https://www.steamgriddb.com/api/v2/heroes/game/13136?page=0".to_string()),
+            pages.next_url(base_url)
+        );
+        let first = pages.handle_response(Ok(paged_response(&[1, 2], Some(3))));
+        assert_eq!(2, first.unwrap().unwrap().len());
+        assert_eq!(
+            Some("https://www.steamgriddb.com/api/v2/heroes/game/13136?page=1".to_string()),
+            pages.next_url(base_url)
+        );
+        let second = pages.handle_response(Ok(paged_response(&[3], Some(3))));
+        assert_eq!(3, second.unwrap().unwrap()[0].id);
+        assert_eq!(None, pages.next_url(base_url));
+    }
+
+    #[test]
+    fn paged_images_stops_on_short_page_test() {
+        let base_url = "https://www.steamgriddb.com/api/v2";
+        let config = HeroQueryParameters {
+            limit: Some(2),
+            ..Default::default()
+        };
+        let platform = Platform::Steam;
+        let mut pages = PagedImages::new(
+            PagedImagesTarget::PlatformId(&platform, "361420"),
+            &Hero(Some(config)),
+        );
+        assert_eq!(
+            Some(
+                "https://www.steamgriddb.com/api/v2/heroes/steam/361420?page=0&limit=2".to_string()
+            ),
+            pages.next_url(base_url)
+        );
+        assert!(pages
+            .handle_response(Ok(paged_response(&[1, 2], None)))
+            .is_some());
+        assert!(pages.next_url(base_url).is_some());
+        assert!(pages
+            .handle_response(Ok(paged_response(&[3], None)))
+            .is_some());
+        assert_eq!(None, pages.next_url(base_url));
+    }
+
+    #[test]
+    fn paged_images_stops_on_empty_page_test() {
+        let base_url = "https://www.steamgriddb.com/api/v2";
+        let mut pages = PagedImages::new(PagedImagesTarget::GameId(13136), &Grid(None));
+        assert!(pages
+            .handle_response(Ok(paged_response(&[1], None)))
+            .is_some());
+        assert!(pages.next_url(base_url).is_some());
+        assert!(pages
+            .handle_response(Ok(paged_response(&[], None)))
+            .is_none());
+        assert_eq!(None, pages.next_url(base_url));
+    }
+
+    #[test]
+    fn paged_images_stops_on_error_test() {
+        let base_url = "https://www.steamgriddb.com/api/v2";
+        let mut pages = PagedImages::new(PagedImagesTarget::GameId(13136), &Grid(None));
+        let json = std::fs::read_to_string("testdata/games/error.json").unwrap();
+        let response: InnerImagesSingleIdResponse = serde_json::from_str(&json).unwrap();
+        assert!(matches!(
+            pages.handle_response(Ok(response)),
+            Some(Err(Error::Api(_)))
+        ));
+        assert_eq!(None, pages.next_url(base_url));
+    }
+
+    #[test]
+    fn animated_and_net_votes_test() {
+        let json = std::fs::read_to_string("testdata/grids/grid_by_id.json").unwrap();
+        let response: crate::response::Response<Image> = serde_json::from_str(&json).unwrap();
+        let mut image = response.data.unwrap();
+        assert!(!image.is_animated());
+        image.thumb =
+            "https://cdn2.steamgriddb.com/thumb/0d6728955057895546f6b7c31404c138.webm".to_string();
+        assert!(image.is_animated());
+        image.upvotes = 2;
+        image.downvotes = 5;
+        assert_eq!(-3, image.net_votes());
+    }
+
+    #[test]
+    fn parse_unknown_values_test() {
+        let json =
+            std::fs::read_to_string("testdata/grids/grids_with_unknown_values.json").unwrap();
+        let response: InnerImagesSingleIdResponse = serde_json::from_str(&json).unwrap();
+        let images = response_to_result(response).unwrap();
+        let styles: Vec<&StyleType> = images.iter().map(|image| &image.style).collect();
+        assert_eq!(
+            vec![
+                &StyleType::Unknown("pixel_art".to_string()),
+                &StyleType::Logo(StyleLogo::Official),
+                &StyleType::Normal(Style::Alternate),
+                &StyleType::Unknown("monochrome".to_string()),
+            ],
+            styles
+        );
+        let mimes: Vec<&MimeTypes> = images.iter().map(|image| &image.mime).collect();
+        assert_eq!(
+            vec![
+                &MimeTypes::Unknown("image/avif".to_string()),
+                &MimeTypes::Icon(MimeTypeIcon::Icon),
+                &MimeTypes::Default(MimeType::Jpeg),
+                &MimeTypes::Default(MimeType::Webp),
+            ],
+            mimes
+        );
+        assert_eq!("image/avif", images[0].mime.as_str());
+
+        // Serializing gives back the values the api sent
+        let json = serde_json::to_value(&images).unwrap();
+        assert_eq!("pixel_art", json[0]["style"]);
+        assert_eq!("image/avif", json[0]["mime"]);
+        assert_eq!("official", json[1]["style"]);
+        assert_eq!("image/vnd.microsoft.icon", json[1]["mime"]);
+        let parsed: Vec<Image> = serde_json::from_value(json).unwrap();
+        assert_eq!(images, parsed);
+    }
 }
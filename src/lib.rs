@@ -1,11 +1,11 @@
 //! # Steamgriddb API
 //!
 //! This project is a wrapper for the steamgriddb api, written in Rust.
-//! 
+//!
 //! ## Getting started
-//! 
+//!
 //! The easiest way to get started is using the Client.
-//! 
+//!
 //! ```no_run
 //! use steamgriddb_api::Client;
 //! use steamgriddb_api::QueryType::Grid;
@@ -20,15 +20,57 @@
 //!     Ok(())
 //!  }
 //! ```
+//!
+//! ## Features
+//!
+//! The `async` feature (enabled by default) provides the async [`Client`].
+//! The `blocking` feature provides [`blocking::Client`], with the same methods as blocking calls.
+//! Both features can be enabled at the same time.
+//!
+//! The `reqwest` feature (enabled by default) makes the clients send requests using [reqwest](https://crates.io/crates/reqwest).
+//! Without it, a client is created with `with_transport`, given any implementation of [`transport::Transport`] or [`transport::BlockingTransport`].
 
+// Without a client, the request handling shared by the clients is only used by the tests.
+#![cfg_attr(
+    not(any(feature = "async", feature = "blocking", test)),
+    allow(dead_code)
+)]
 
+#[cfg(feature = "blocking")]
+pub mod blocking;
+pub mod cache;
+#[cfg(feature = "async")]
 pub mod client;
+mod crc32;
+pub mod download;
+pub mod error;
+mod files;
 pub mod games;
 pub mod images;
+pub mod matcher;
 pub mod query_parameters;
+pub mod rate_limit;
+pub mod resolver;
 pub mod response;
+pub mod retry;
 pub mod search;
+pub mod selector;
+pub mod steam;
 pub mod steam_static;
+#[cfg(all(
+    test,
+    feature = "reqwest",
+    any(feature = "async", feature = "blocking")
+))]
+mod test_server;
+pub mod transport;
+pub mod upload;
 
+#[cfg(feature = "async")]
 pub use client::Client;
-pub use query_parameters::QueryType;
\ No newline at end of file
+pub use error::Error;
+pub use query_parameters::QueryType;
+
+/// The default base url of the steamgriddb api.
+#[cfg(any(feature = "async", feature = "blocking"))]
+pub(crate) const DEFAULT_BASE_URL: &str = "https://www.steamgriddb.com/api/v2";
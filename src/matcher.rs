@@ -0,0 +1,471 @@
+//! Matching a game title to search results.
+//!
+//! The search api returns results in the order the server picks, which is not always the game that was meant.
+//! A [`TitleMatcher`] normalizes the titles, so "Assassins Creed 3" and "Assassin's Creed® III" are the same title,
+//! scores each result with a confidence between 0 and 1, and only picks a result that is confident enough.
+
+use crate::search::SearchResult;
+
+/// The phrases at the end of a title that name an edition of a game, not a different game.
+/// They are removed from the end of the title until none is left.
+const EDITION_SUFFIXES: [&[&str]; 19] = [
+    &["game", "of", "the", "year", "edition"],
+    &["game", "of", "the", "year"],
+    &["goty", "edition"],
+    &["goty"],
+    &["digital", "deluxe", "edition"],
+    &["deluxe", "edition"],
+    &["deluxe"],
+    &["definitive", "edition"],
+    &["complete", "edition"],
+    &["gold", "edition"],
+    &["enhanced", "edition"],
+    &["ultimate", "edition"],
+    &["special", "edition"],
+    &["collectors", "edition"],
+    &["anniversary", "edition"],
+    &["standard", "edition"],
+    &["directors", "cut"],
+    &["remastered"],
+    &["remaster"],
+];
+
+/// Multiplies the confidence when the numbers in the titles differ, like in "Assassin's Creed II" and "Assassin's Creed III"
+const OTHER_NUMBER: f64 = 0.5;
+/// Multiplies the confidence when the release year is more than a year off
+const OTHER_YEAR: f64 = 0.75;
+/// Multiplies the confidence when the release year is a year off
+const NEAR_YEAR: f64 = 0.95;
+/// Multiplies the confidence when a year is expected, but the release date is unknown
+const UNKNOWN_YEAR: f64 = 0.9;
+/// Multiplies the confidence for games that are not verified
+const UNVERIFIED: f64 = 0.9;
+
+/// A search result with the confidence that it is the game that was searched for
+#[derive(Debug, Clone, PartialEq)]
+pub struct TitleMatch {
+    /// The search result
+    pub result: SearchResult,
+    /// The confidence, between 0 and 1
+    pub confidence: f64,
+}
+
+/// Scores search results by how well they match a title, and picks the best match above a threshold.
+///
+/// The confidence is mostly how similar the normalized titles are. Editions count a little, so
+/// "Assassin's Creed III" is preferred over "Assassin's Creed 3 - Deluxe" when searching for "Assassins Creed 3".
+/// A different number in the title, a release year that does not match and unverified games lower the confidence.
+///
+/// ### Examples
+/// ```
+/// # use steamgriddb_api::search::SearchResult;
+/// use steamgriddb_api::matcher::TitleMatcher;
+///
+/// # fn example(results: &[SearchResult]) {
+/// let matcher = TitleMatcher::new().threshold(0.9).year(2012);
+/// if let Some(found) = matcher.best_match("Assassins Creed 3", results) {
+///     println!("{} ({})", found.result.name, found.confidence);
+/// }
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TitleMatcher {
+    threshold: f64,
+    year: Option<i32>,
+}
+
+impl Default for TitleMatcher {
+    /// A threshold of 0.85, and no expected release year
+    fn default() -> Self {
+        Self {
+            threshold: 0.85,
+            year: None,
+        }
+    }
+}
+
+impl TitleMatcher {
+    /// A matcher with a threshold of 0.85, and no expected release year
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only pick a best match with at least this confidence
+    pub fn threshold(mut self, threshold: f64) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    /// Expect the game to be released in this year.
+    ///
+    /// Without it, a year at the end of the title like "Doom (2016)" is used.
+    pub fn year(mut self, year: i32) -> Self {
+        self.year = Some(year);
+        self
+    }
+
+    /// The confidence that the search result is the game with the given title, between 0 and 1
+    ///
+    /// ### Examples
+    /// ```
+    /// use steamgriddb_api::matcher::TitleMatcher;
+    /// use steamgriddb_api::search::SearchResult;
+    ///
+    /// let result = SearchResult {
+    ///     name: "Assassin's Creed® III".to_string(),
+    ///     release_date: Some(1353441600),
+    ///     verified: true,
+    ///     id: 1937,
+    ///     types: vec!["steam".to_string()],
+    /// };
+    /// let matcher = TitleMatcher::new();
+    /// assert_eq!(1.0, matcher.score("Assassins Creed 3", &result));
+    /// assert!(matcher.score("Assassin's Creed II", &result) < 0.85);
+    /// ```
+    pub fn score(&self, title: &str, result: &SearchResult) -> f64 {
+        self.score_title(&Title::parse(title), result)
+    }
+
+    /// All search results with their confidence, the most confident first.
+    ///
+    /// Results with the same confidence keep the order the api returned them in.
+    pub fn rank(&self, title: &str, results: &[SearchResult]) -> Vec<TitleMatch> {
+        let title = Title::parse(title);
+        let mut matches: Vec<TitleMatch> = results
+            .iter()
+            .map(|result| TitleMatch {
+                result: result.clone(),
+                confidence: self.score_title(&title, result),
+            })
+            .collect();
+        matches.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+        matches
+    }
+
+    /// The most confident search result, if its confidence is at least the threshold
+    pub fn best_match(&self, title: &str, results: &[SearchResult]) -> Option<TitleMatch> {
+        self.rank(title, results)
+            .into_iter()
+            .next()
+            .filter(|found| found.confidence >= self.threshold)
+    }
+
+    fn score_title(&self, title: &Title, result: &SearchResult) -> f64 {
+        let other = Title::parse(&result.name);
+        let mut confidence =
+            0.9 * similarity(&title.name, &other.name) + 0.1 * similarity(&title.full, &other.full);
+        if numbers(&title.name) != numbers(&other.name) {
+            confidence *= OTHER_NUMBER;
+        }
+        if let Some(year) = self.year.or(title.year) {
+            confidence *= match result.release_date.map(release_year) {
+                Some(released) if released == year => 1.0,
+                Some(released) if (released - year).abs() == 1 => NEAR_YEAR,
+                Some(_) => OTHER_YEAR,
+                None => UNKNOWN_YEAR,
+            };
+        }
+        if !result.verified {
+            confidence *= UNVERIFIED;
+        }
+        confidence
+    }
+}
+
+/// Normalize a title, so the ways the same game is written give the same text.
+///
+/// The title is lowercased, trademark symbols and punctuation are removed, roman numerals become numbers,
+/// and edition suffixes like "GOTY" and "Definitive Edition" are removed, as is a year at the end like "(2016)".
+///
+/// ### Examples
+/// ```
+/// use steamgriddb_api::matcher::normalize_title;
+/// assert_eq!("assassins creed 3", normalize_title("Assassin's Creed® III"));
+/// assert_eq!("assassins creed 3", normalize_title("Assassins Creed 3 - Deluxe Edition"));
+/// assert_eq!("the witcher 3 wild hunt", normalize_title("The Witcher® 3: Wild Hunt GOTY"));
+/// ```
+pub fn normalize_title(title: &str) -> String {
+    Title::parse(title).name.join(" ")
+}
+
+/// A title split into normalized words
+struct Title {
+    /// The words without edition suffixes
+    name: Vec<String>,
+    /// All words
+    full: Vec<String>,
+    /// The year at the end of the title
+    year: Option<i32>,
+}
+
+impl Title {
+    fn parse(title: &str) -> Self {
+        let (title, year) = split_year(title);
+        let full = words(title);
+        let mut name = full.as_slice();
+        while let Some(suffix) = EDITION_SUFFIXES
+            .iter()
+            .find(|suffix| suffix.len() < name.len() && ends_with_words(name, suffix))
+        {
+            name = &name[..name.len() - suffix.len()];
+        }
+        Self {
+            name: name.to_vec(),
+            full,
+            year,
+        }
+    }
+}
+
+/// Do the words end with the suffix
+fn ends_with_words(words: &[String], suffix: &[&str]) -> bool {
+    words.len() >= suffix.len()
+        && words[words.len() - suffix.len()..]
+            .iter()
+            .zip(suffix)
+            .all(|(word, suffix)| word == suffix)
+}
+
+/// Split a year in parentheses off the end of a title, like "Doom (2016)"
+fn split_year(title: &str) -> (&str, Option<i32>) {
+    let trimmed = title.trim_end();
+    let year = trimmed
+        .strip_suffix(')')
+        .and_then(|rest| rest.get(rest.len().saturating_sub(5)..))
+        .and_then(|year| year.strip_prefix('('))
+        .filter(|year| year.len() == 4 && year.bytes().all(|b| b.is_ascii_digit()));
+    match year {
+        Some(year) => (&trimmed[..trimmed.len() - 6], year.parse().ok()),
+        None => (title, None),
+    }
+}
+
+/// Lowercase the title and split it into words, without punctuation, and with roman numerals as numbers
+fn words(title: &str) -> Vec<String> {
+    let mut cleaned = String::with_capacity(title.len());
+    for c in title.chars() {
+        match c {
+            '\'' | '’' | '`' | '™' | '®' | '©' | '℠' => {}
+            '&' => cleaned.push_str(" and "),
+            c if c.is_alphanumeric() => cleaned.extend(c.to_lowercase()),
+            _ => cleaned.push(' '),
+        }
+    }
+    cleaned
+        .split_whitespace()
+        .enumerate()
+        .map(|(i, word)| match roman_numeral(word) {
+            // A title rarely starts with a number, "X-Men" is not "10 Men"
+            Some(number) if i > 0 => number.to_string(),
+            _ => word.to_string(),
+        })
+        .collect()
+}
+
+/// The value of a roman numeral below 40, like "iii" or "xiv"
+fn roman_numeral(word: &str) -> Option<u32> {
+    const TENS: [&str; 4] = ["", "x", "xx", "xxx"];
+    const ONES: [&str; 10] = ["", "i", "ii", "iii", "iv", "v", "vi", "vii", "viii", "ix"];
+    let tens = TENS
+        .iter()
+        .rposition(|tens| word.starts_with(tens))
+        .unwrap_or(0);
+    let ones = ONES
+        .iter()
+        .position(|ones| *ones == &word[TENS[tens].len()..])?;
+    let number = tens * 10 + ones;
+    if number == 0 {
+        return None;
+    }
+    Some(number as u32)
+}
+
+/// The numbers in a title, in order
+fn numbers(words: &[String]) -> Vec<&str> {
+    words
+        .iter()
+        .filter(|word| word.bytes().all(|b| b.is_ascii_digit()))
+        .map(String::as_str)
+        .collect()
+}
+
+/// How similar two titles are, between 0 and 1, using the letter pairs they have in common
+fn similarity(a: &[String], b: &[String]) -> f64 {
+    let a: Vec<char> = a.iter().flat_map(|word| word.chars()).collect();
+    let b: Vec<char> = b.iter().flat_map(|word| word.chars()).collect();
+    if a == b {
+        return 1.0;
+    }
+    if a.len() < 2 || b.len() < 2 {
+        return 0.0;
+    }
+    let pairs = |chars: &[char]| {
+        let mut pairs: Vec<(char, char)> = chars.windows(2).map(|w| (w[0], w[1])).collect();
+        pairs.sort_unstable();
+        pairs
+    };
+    let (a, b) = (pairs(&a), pairs(&b));
+    let (mut i, mut j, mut common) = (0, 0, 0);
+    while i < a.len() && j < b.len() {
+        match a[i].cmp(&b[j]) {
+            std::cmp::Ordering::Less => i += 1,
+            std::cmp::Ordering::Greater => j += 1,
+            std::cmp::Ordering::Equal => {
+                common += 1;
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+    2.0 * common as f64 / (a.len() + b.len()) as f64
+}
+
+/// The year of a release date, given in seconds since 1970 in UTC
+fn release_year(release_date: usize) -> i32 {
+    // Days since 1970 to a civil year, from Howard Hinnant's `civil_from_days`
+    let days = (release_date / 86_400) as i64 + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month = (5 * day_of_year + 2) / 153;
+    let year = year_of_era + era * 400 + if month >= 10 { 1 } else { 0 };
+    year as i32
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::search::InnerSearchResult;
+
+    fn search_results() -> Vec<SearchResult> {
+        let json = std::fs::read_to_string("testdata/search/search.json").unwrap();
+        let response: InnerSearchResult = serde_json::from_str(&json).unwrap();
+        response.data.unwrap()
+    }
+
+    #[test]
+    fn normalize_title_test() {
+        assert_eq!(
+            "assassins creed 3",
+            normalize_title("Assassin's Creed® III")
+        );
+        assert_eq!("assassins creed 3", normalize_title("Assassins Creed 3"));
+        assert_eq!(
+            "assassins creed 3",
+            normalize_title("Assassin's Creed 3 - Deluxe")
+        );
+        assert_eq!(
+            "the witcher 3 wild hunt",
+            normalize_title("The Witcher 3: Wild Hunt – Game of the Year Edition")
+        );
+        assert_eq!("doom", normalize_title("DOOM (2016)"));
+        assert_eq!(
+            "final fantasy 7",
+            normalize_title("FINAL FANTASY VII Remastered")
+        );
+        assert_eq!("x men", normalize_title("X-Men"));
+        assert_eq!("ratchet and clank", normalize_title("Ratchet & Clank™"));
+        assert_eq!(
+            "sid meiers civilization 6",
+            normalize_title("Sid Meier's Civilization® VI")
+        );
+        // An edition alone is kept, there is nothing else to match on
+        assert_eq!("deluxe", normalize_title("Deluxe"));
+    }
+
+    #[test]
+    fn roman_numeral_test() {
+        assert_eq!(Some(3), roman_numeral("iii"));
+        assert_eq!(Some(14), roman_numeral("xiv"));
+        assert_eq!(Some(39), roman_numeral("xxxix"));
+        assert_eq!(None, roman_numeral("iiii"));
+        assert_eq!(None, roman_numeral("civ"));
+        assert_eq!(None, roman_numeral("mix"));
+        assert_eq!(None, roman_numeral(""));
+    }
+
+    #[test]
+    fn release_year_test() {
+        assert_eq!(1970, release_year(0));
+        assert_eq!(2008, release_year(1207724400));
+        assert_eq!(2012, release_year(1353441600));
+        assert_eq!(2000, release_year(951_782_400));
+        assert_eq!(2016, release_year(1_483_228_799));
+        assert_eq!(2017, release_year(1_483_228_800));
+    }
+
+    #[test]
+    fn best_match_test() {
+        let results = search_results();
+        let matcher = TitleMatcher::new();
+        let best = |title: &str| matcher.best_match(title, &results).map(|m| m.result.id);
+
+        assert_eq!(Some(1937), best("Assassins Creed 3"));
+        assert_eq!(Some(1937), best("ASSASSIN'S CREED III"));
+        assert_eq!(Some(1451), best("Assassin's Creed"));
+        assert_eq!(Some(5820), best("Assassins Creed II - Deluxe Edition"));
+        assert_eq!(Some(1760), best("Assassin's Creed Revelation"));
+        assert_eq!(None, best("Assassin's Creed 4"));
+        assert_eq!(None, best("Call of Duty"));
+    }
+
+    #[test]
+    fn rank_test() {
+        let results = search_results();
+        let ranked = TitleMatcher::new().rank("Assassins Creed 3", &results);
+        assert_eq!(results.len(), ranked.len());
+        assert_eq!(1937, ranked[0].result.id);
+        assert_eq!(1.0, ranked[0].confidence);
+        assert_eq!(1938, ranked[1].result.id);
+        assert!(ranked[1].confidence > 0.85);
+        assert!(ranked
+            .windows(2)
+            .all(|pair| pair[0].confidence >= pair[1].confidence));
+    }
+
+    #[test]
+    fn year_and_verified_test() {
+        let results = search_results();
+        let unity = results.iter().find(|r| r.id == 4174).unwrap();
+        let matcher = TitleMatcher::new();
+        assert_eq!(1.0, matcher.score("Assassin's Creed Unity (2014)", unity));
+        assert_eq!(
+            NEAR_YEAR,
+            matcher.score("Assassin's Creed Unity (2015)", unity)
+        );
+        assert_eq!(
+            OTHER_YEAR,
+            matcher.year(2020).score("Assassin's Creed Unity", unity)
+        );
+        assert_eq!(
+            None,
+            matcher
+                .year(2020)
+                .best_match("Assassin's Creed Unity", &results)
+        );
+
+        let valhalla = results.iter().find(|r| r.id == 5259692).unwrap();
+        assert_eq!(
+            UNKNOWN_YEAR,
+            matcher
+                .year(2020)
+                .score("Assassin's Creed Valhalla", valhalla)
+        );
+
+        let mut unverified = unity.clone();
+        unverified.verified = false;
+        assert_eq!(
+            UNVERIFIED,
+            matcher.score("Assassin's Creed Unity", &unverified)
+        );
+        assert_eq!(
+            None,
+            matcher
+                .threshold(0.95)
+                .best_match("Assassin's Creed Unity", &[unverified])
+        );
+    }
+}
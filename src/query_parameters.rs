@@ -1,6 +1,9 @@
+use std::fmt;
+
 use serde::{Deserialize, Serialize};
 
 /// Query type for each image type
+#[derive(Clone, Copy)]
 pub enum QueryType<'a> {
     /// Query for grid images
     Grid(Option<GridQueryParameters<'a>>),
@@ -27,7 +30,95 @@ impl ToQuerys for QueryType<'_> {
     }
 }
 
-#[derive(Default)]
+impl<'a> QueryType<'a> {
+    /// The kind of images this query is for.
+    pub fn kind(&self) -> ImageKind {
+        match self {
+            QueryType::Grid(_) => ImageKind::Grid,
+            QueryType::Hero(_) => ImageKind::Hero,
+            QueryType::Logo(_) => ImageKind::Logo,
+            QueryType::Icon(_) => ImageKind::Icon,
+        }
+    }
+
+    /// Get a copy of this query, that requests the given page.
+    ///
+    /// ### Examples
+    /// ```
+    /// use steamgriddb_api::images::get_images_by_game_id_url;
+    /// use steamgriddb_api::query_parameters::QueryType::Grid;
+    /// let query = Grid(None).with_page(2);
+    /// let url = get_images_by_game_id_url("https://www.steamgriddb.com/api/v2", 13136, &query);
+    /// assert_eq!("https://www.steamgriddb.com/api/v2/grids/game/13136?page=2", url);
+    /// ```
+    pub fn with_page(&self, page: usize) -> QueryType<'a> {
+        match *self {
+            QueryType::Grid(parameters) => QueryType::Grid(Some(GridQueryParameters {
+                page: Some(page),
+                ..parameters.unwrap_or_default()
+            })),
+            QueryType::Hero(parameters) => QueryType::Hero(Some(HeroQueryParameters {
+                page: Some(page),
+                ..parameters.unwrap_or_default()
+            })),
+            QueryType::Logo(parameters) => QueryType::Logo(Some(LogoQueryParameters {
+                page: Some(page),
+                ..parameters.unwrap_or_default()
+            })),
+            QueryType::Icon(parameters) => QueryType::Icon(Some(IconQueryParameters {
+                page: Some(page),
+                ..parameters.unwrap_or_default()
+            })),
+        }
+    }
+
+    /// The page requested by this query, if any.
+    pub fn page(&self) -> Option<usize> {
+        match self {
+            QueryType::Grid(parameters) => parameters.and_then(|p| p.page),
+            QueryType::Hero(parameters) => parameters.and_then(|p| p.page),
+            QueryType::Logo(parameters) => parameters.and_then(|p| p.page),
+            QueryType::Icon(parameters) => parameters.and_then(|p| p.page),
+        }
+    }
+
+    /// The maximum amount of images per page requested by this query, if any.
+    pub fn limit(&self) -> Option<usize> {
+        match self {
+            QueryType::Grid(parameters) => parameters.and_then(|p| p.limit),
+            QueryType::Hero(parameters) => parameters.and_then(|p| p.limit),
+            QueryType::Logo(parameters) => parameters.and_then(|p| p.limit),
+            QueryType::Icon(parameters) => parameters.and_then(|p| p.limit),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+/// The kinds of images on steamgriddb
+pub enum ImageKind {
+    /// Grid images
+    Grid,
+    /// Hero images
+    Hero,
+    /// Logo images
+    Logo,
+    /// Icon images
+    Icon,
+}
+
+impl ImageKind {
+    /// The path used for this kind of images in the api urls
+    pub fn path(&self) -> &'static str {
+        match self {
+            ImageKind::Grid => "grids",
+            ImageKind::Hero => "heroes",
+            ImageKind::Logo => "logos",
+            ImageKind::Icon => "icons",
+        }
+    }
+}
+
+#[derive(Default, Clone, Copy)]
 /// Qeury parameters for hero images
 pub struct HeroQueryParameters<'a> {
     /// The hero styles
@@ -42,6 +133,14 @@ pub struct HeroQueryParameters<'a> {
     pub nsfw: Option<&'a Nsfw>,
     /// If humor images are allowed
     pub humor: Option<&'a Humor>,
+    /// If epilepsy triggering images are allowed
+    pub epilepsy: Option<&'a Epilepsy>,
+    /// Only include images with at least one of these tags
+    pub oneoftag: Option<&'a [&'a str]>,
+    /// The page to fetch, starting from 0
+    pub page: Option<usize>,
+    /// The maximum amount of images per page
+    pub limit: Option<usize>,
 }
 
 impl ToQuerys for HeroQueryParameters<'_> {
@@ -53,11 +152,15 @@ impl ToQuerys for HeroQueryParameters<'_> {
             to_qeury_string(self.types),
             to_qeury_string_single(self.nsfw),
             to_qeury_string_single(self.humor),
+            to_qeury_string_single(self.epilepsy),
+            to_qeury_string_tags("oneoftag", self.oneoftag),
+            to_qeury_string_number("page", self.page),
+            to_qeury_string_number("limit", self.limit),
         ])
     }
 }
 
-#[derive(Default)]
+#[derive(Default, Clone, Copy)]
 /// Qeury parameters for grid images
 pub struct GridQueryParameters<'a> {
     /// The grid styles
@@ -72,6 +175,14 @@ pub struct GridQueryParameters<'a> {
     pub nsfw: Option<&'a Nsfw>,
     /// If humor images are allowed
     pub humor: Option<&'a Humor>,
+    /// If epilepsy triggering images are allowed
+    pub epilepsy: Option<&'a Epilepsy>,
+    /// Only include images with at least one of these tags
+    pub oneoftag: Option<&'a [&'a str]>,
+    /// The page to fetch, starting from 0
+    pub page: Option<usize>,
+    /// The maximum amount of images per page
+    pub limit: Option<usize>,
 }
 
 impl ToQuerys for GridQueryParameters<'_> {
@@ -83,16 +194,20 @@ impl ToQuerys for GridQueryParameters<'_> {
             to_qeury_string(self.types),
             to_qeury_string_single(self.nsfw),
             to_qeury_string_single(self.humor),
+            to_qeury_string_single(self.epilepsy),
+            to_qeury_string_tags("oneoftag", self.oneoftag),
+            to_qeury_string_number("page", self.page),
+            to_qeury_string_number("limit", self.limit),
         ])
     }
 }
 
-#[derive(Default)]
+#[derive(Default, Clone, Copy)]
 /// Qeury parameters for logo images
 pub struct LogoQueryParameters<'a> {
     /// The logo styles
-    pub styles: Option<&'a [Style]>,
-    // The logo dimentions
+    pub styles: Option<&'a [StyleLogo]>,
+    // The mime types
     pub mimes: Option<&'a [MimeTypeLogo]>,
     // The image animation type
     pub types: Option<&'a [AnimtionType]>,
@@ -100,6 +215,14 @@ pub struct LogoQueryParameters<'a> {
     pub nsfw: Option<&'a Nsfw>,
     /// If humor images are allowed
     pub humor: Option<&'a Humor>,
+    /// If epilepsy triggering images are allowed
+    pub epilepsy: Option<&'a Epilepsy>,
+    /// Only include images with at least one of these tags
+    pub oneoftag: Option<&'a [&'a str]>,
+    /// The page to fetch, starting from 0
+    pub page: Option<usize>,
+    /// The maximum amount of images per page
+    pub limit: Option<usize>,
 }
 
 impl ToQuerys for LogoQueryParameters<'_> {
@@ -110,16 +233,22 @@ impl ToQuerys for LogoQueryParameters<'_> {
             to_qeury_string(self.types),
             to_qeury_string_single(self.nsfw),
             to_qeury_string_single(self.humor),
+            to_qeury_string_single(self.epilepsy),
+            to_qeury_string_tags("oneoftag", self.oneoftag),
+            to_qeury_string_number("page", self.page),
+            to_qeury_string_number("limit", self.limit),
         ])
     }
 }
 
-#[derive(Default)]
+#[derive(Default, Clone, Copy)]
 /// Qeury parameters for icon images
 pub struct IconQueryParameters<'a> {
     /// The icon styles
     pub styles: Option<&'a [Style]>,
     // The icon dimentions
+    pub dimentions: Option<&'a [IconDimensions]>,
+    // The mime types
     pub mimes: Option<&'a [MimeTypeIcon]>,
     // The image animation type
     pub types: Option<&'a [AnimtionType]>,
@@ -127,16 +256,29 @@ pub struct IconQueryParameters<'a> {
     pub nsfw: Option<&'a Nsfw>,
     /// If humor images are allowed
     pub humor: Option<&'a Humor>,
+    /// If epilepsy triggering images are allowed
+    pub epilepsy: Option<&'a Epilepsy>,
+    /// Only include images with at least one of these tags
+    pub oneoftag: Option<&'a [&'a str]>,
+    /// The page to fetch, starting from 0
+    pub page: Option<usize>,
+    /// The maximum amount of images per page
+    pub limit: Option<usize>,
 }
 
 impl ToQuerys for IconQueryParameters<'_> {
     fn to_querys(&self) -> String {
         parameters_to_qeury(&[
             to_qeury_string(self.styles),
+            to_qeury_string(self.dimentions),
             to_qeury_string(self.mimes),
             to_qeury_string(self.types),
             to_qeury_string_single(self.nsfw),
             to_qeury_string_single(self.humor),
+            to_qeury_string_single(self.epilepsy),
+            to_qeury_string_tags("oneoftag", self.oneoftag),
+            to_qeury_string_number("page", self.page),
+            to_qeury_string_number("limit", self.limit),
         ])
     }
 }
@@ -305,6 +447,25 @@ where
     })
 }
 
+pub(crate) fn to_qeury_string_number(name: &str, item: Option<usize>) -> Option<String> {
+    item.map(|item| format!("{}={}", name, item))
+}
+
+pub(crate) fn to_qeury_string_tags(name: &str, items: Option<&[&str]>) -> Option<String> {
+    use urlencoding::encode;
+    match items {
+        Some(items) if !items.is_empty() => {
+            let value = items
+                .iter()
+                .map(|item| encode(item).into_owned())
+                .collect::<Vec<String>>()
+                .join(",");
+            Some(format!("{}={}", name, value))
+        }
+        _ => None,
+    }
+}
+
 pub(crate) fn to_qeury_string<T>(items: Option<&[T]>) -> Option<String>
 where
     T: ToQueryValue,
@@ -403,42 +564,69 @@ impl ToQueryValue for IconDimensions {
 
 #[derive(Serialize, Deserialize, Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
 /// Game platforms/stores
+#[serde(from = "String", into = "String")]
 pub enum Platform {
-    #[serde(rename = "steam")]
     /// steam
     Steam,
-    #[serde(rename = "origin")]
     /// origin
     Origin,
-    #[serde(rename = "egs")]
     /// egs
     EpicGameStore,
-    #[serde(rename = "bnet")]
     /// bnet
     BattleNet,
-    #[serde(rename = "uplay")]
     /// uplay
     Uplay,
-    #[serde(rename = "flashpoint")]
     /// flashpoint
     Flashpoint,
-    #[serde(rename = "gog")]
     /// gog
     GoG,
+    /// A platform this crate does not know yet, kept as the api sent it
+    Unknown(String),
 }
 
-impl ToString for Platform {
-    fn to_string(&self) -> String {
+impl Platform {
+    /// The name the api uses for this platform, like "steam"
+    pub fn as_str(&self) -> &str {
         match self {
             Platform::Steam => "steam",
             Platform::Origin => "origin",
             Platform::EpicGameStore => "egs",
             Platform::BattleNet => "bnet",
             Platform::Uplay => "uplay",
-            Platform::GoG => "gog",
             Platform::Flashpoint => "flashpoint",
+            Platform::GoG => "gog",
+            Platform::Unknown(value) => value,
+        }
+    }
+}
+
+impl From<String> for Platform {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "steam" => Platform::Steam,
+            "origin" => Platform::Origin,
+            "egs" => Platform::EpicGameStore,
+            "bnet" => Platform::BattleNet,
+            "uplay" => Platform::Uplay,
+            "flashpoint" => Platform::Flashpoint,
+            "gog" => Platform::GoG,
+            _ => Platform::Unknown(value),
         }
-        .to_string()
+    }
+}
+
+impl From<Platform> for String {
+    fn from(value: Platform) -> Self {
+        match value {
+            Platform::Unknown(value) => value,
+            known => known.as_str().to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Platform {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
     }
 }
 
@@ -446,12 +634,7 @@ impl ToQueryValue for MimeType {
     fn to_query_value(&self) -> QeuryValue {
         QeuryValue {
             name: "mimes".to_string(),
-            value: match self {
-                MimeType::Png => "image/png",
-                MimeType::Jpeg => "image/jpeg",
-                MimeType::Webp => "image/webp",
-            }
-            .to_string(),
+            value: self.as_str().to_string(),
         }
     }
 }
@@ -460,11 +643,7 @@ impl ToQueryValue for MimeTypeIcon {
     fn to_query_value(&self) -> QeuryValue {
         QeuryValue {
             name: "mimes".to_string(),
-            value: match self {
-                MimeTypeIcon::Png => "image/png",
-                MimeTypeIcon::Icon => "image/vnd.microsoft.icon",
-            }
-            .to_string(),
+            value: self.as_str().to_string(),
         }
     }
 }
@@ -473,11 +652,7 @@ impl ToQueryValue for MimeTypeLogo {
     fn to_query_value(&self) -> QeuryValue {
         QeuryValue {
             name: "mimes".to_string(),
-            value: match self {
-                MimeTypeLogo::Png => "image/png",
-                MimeTypeLogo::Webp => "image/webp",
-            }
-            .to_string(),
+            value: self.as_str().to_string(),
         }
     }
 }
@@ -524,38 +699,132 @@ impl ToQueryValue for AnimtionType {
 
 #[derive(Serialize, Deserialize, Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
 /// Mime types
+#[serde(from = "String", into = "String")]
 pub enum MimeTypeIcon {
-    #[serde(rename = "image/png")]
     /// image/png
     Png,
-    #[serde(rename = "image/vnd.microsoft.icon")]
-    /// image/webp
+    /// image/vnd.microsoft.icon
     Icon,
+    /// A mime type this crate does not know yet, kept as the api sent it
+    Unknown(String),
+}
+
+impl MimeTypeIcon {
+    /// The name the api uses for this mime type, like "image/png"
+    pub fn as_str(&self) -> &str {
+        match self {
+            MimeTypeIcon::Png => "image/png",
+            MimeTypeIcon::Icon => "image/vnd.microsoft.icon",
+            MimeTypeIcon::Unknown(value) => value,
+        }
+    }
+}
+
+impl From<String> for MimeTypeIcon {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "image/png" => MimeTypeIcon::Png,
+            "image/vnd.microsoft.icon" => MimeTypeIcon::Icon,
+            _ => MimeTypeIcon::Unknown(value),
+        }
+    }
+}
+
+impl From<MimeTypeIcon> for String {
+    fn from(value: MimeTypeIcon) -> Self {
+        match value {
+            MimeTypeIcon::Unknown(value) => value,
+            known => known.as_str().to_string(),
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
 /// Mime types
+#[serde(from = "String", into = "String")]
 pub enum MimeType {
-    #[serde(rename = "image/png")]
     /// image/png
     Png,
-    #[serde(rename = "image/jpeg")]
     /// image/jpeg
     Jpeg,
-    #[serde(rename = "image/webp")]
     /// image/webp
     Webp,
+    /// A mime type this crate does not know yet, kept as the api sent it
+    Unknown(String),
+}
+
+impl MimeType {
+    /// The name the api uses for this mime type, like "image/png"
+    pub fn as_str(&self) -> &str {
+        match self {
+            MimeType::Png => "image/png",
+            MimeType::Jpeg => "image/jpeg",
+            MimeType::Webp => "image/webp",
+            MimeType::Unknown(value) => value,
+        }
+    }
+}
+
+impl From<String> for MimeType {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "image/png" => MimeType::Png,
+            "image/jpeg" => MimeType::Jpeg,
+            "image/webp" => MimeType::Webp,
+            _ => MimeType::Unknown(value),
+        }
+    }
+}
+
+impl From<MimeType> for String {
+    fn from(value: MimeType) -> Self {
+        match value {
+            MimeType::Unknown(value) => value,
+            known => known.as_str().to_string(),
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
 /// Mime types for logos
+#[serde(from = "String", into = "String")]
 pub enum MimeTypeLogo {
-    #[serde(rename = "image/png")]
     /// image/png
     Png,
-    #[serde(rename = "image/webp")]
     /// image/webp
     Webp,
+    /// A mime type this crate does not know yet, kept as the api sent it
+    Unknown(String),
+}
+
+impl MimeTypeLogo {
+    /// The name the api uses for this mime type, like "image/png"
+    pub fn as_str(&self) -> &str {
+        match self {
+            MimeTypeLogo::Png => "image/png",
+            MimeTypeLogo::Webp => "image/webp",
+            MimeTypeLogo::Unknown(value) => value,
+        }
+    }
+}
+
+impl From<String> for MimeTypeLogo {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "image/png" => MimeTypeLogo::Png,
+            "image/webp" => MimeTypeLogo::Webp,
+            _ => MimeTypeLogo::Unknown(value),
+        }
+    }
+}
+
+impl From<MimeTypeLogo> for String {
+    fn from(value: MimeTypeLogo) -> Self {
+        match value {
+            MimeTypeLogo::Unknown(value) => value,
+            known => known.as_str().to_string(),
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -573,6 +842,28 @@ pub enum Nsfw {
     Any,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
+/// Is the image epilepsy triggering
+pub enum Epilepsy {
+    True,
+    False,
+    Any,
+}
+
+impl ToQueryValue for Epilepsy {
+    fn to_query_value(&self) -> QeuryValue {
+        QeuryValue {
+            name: "epilepsy".to_string(),
+            value: match self {
+                Epilepsy::True => "true",
+                Epilepsy::False => "false",
+                Epilepsy::Any => "any",
+            }
+            .to_string(),
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
 /// Is the image houmerous
 pub enum Humor {
@@ -603,75 +894,261 @@ pub enum AnimtionType {
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
-#[serde(untagged)]
+/// The style of an image, as the api sends it.
+///
+/// Logos and icons use the logo styles, other images the normal styles.
+#[serde(from = "String", into = "String")]
 pub enum StyleType {
+    /// A style of grids, heroes and icons
     Normal(Style),
+    /// A style of logos and icons
     Logo(StyleLogo),
+    /// A style this crate does not know yet, kept as the api sent it
+    Unknown(String),
+}
+
+impl StyleType {
+    /// The name the api uses for this style, like "alternate"
+    pub fn as_str(&self) -> &str {
+        match self {
+            StyleType::Normal(style) => style.as_str(),
+            StyleType::Logo(style) => style.as_str(),
+            StyleType::Unknown(value) => value,
+        }
+    }
+}
+
+impl From<String> for StyleType {
+    fn from(value: String) -> Self {
+        match Style::from(value) {
+            Style::Unknown(value) => match StyleLogo::from(value) {
+                StyleLogo::Unknown(value) => StyleType::Unknown(value),
+                style => StyleType::Logo(style),
+            },
+            style => StyleType::Normal(style),
+        }
+    }
+}
+
+impl From<StyleType> for String {
+    fn from(value: StyleType) -> Self {
+        match value {
+            StyleType::Unknown(value) => value,
+            known => known.as_str().to_string(),
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
 /// Image style
+#[serde(from = "String", into = "String")]
 pub enum Style {
-    #[serde(rename = "alternate")]
     /// alternate
     Alternate,
-    #[serde(rename = "blurred")]
     /// blurred
     Blurred,
-    #[serde(rename = "white_logo")]
     /// white_logo
     WhiteLogo,
-    #[serde(rename = "material")]
     /// material
     Material,
-    #[serde(rename = "no_logo")]
     /// no_logo
     NoLogo,
+    /// A style this crate does not know yet, kept as the api sent it
+    Unknown(String),
+}
+
+impl Style {
+    /// The name the api uses for this style, like "alternate"
+    pub fn as_str(&self) -> &str {
+        match self {
+            Style::Alternate => "alternate",
+            Style::Blurred => "blurred",
+            Style::WhiteLogo => "white_logo",
+            Style::Material => "material",
+            Style::NoLogo => "no_logo",
+            Style::Unknown(value) => value,
+        }
+    }
+}
+
+impl From<String> for Style {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "alternate" => Style::Alternate,
+            "blurred" => Style::Blurred,
+            "white_logo" => Style::WhiteLogo,
+            "material" => Style::Material,
+            "no_logo" => Style::NoLogo,
+            _ => Style::Unknown(value),
+        }
+    }
+}
+
+impl From<Style> for String {
+    fn from(value: Style) -> Self {
+        match value {
+            Style::Unknown(value) => value,
+            known => known.as_str().to_string(),
+        }
+    }
 }
 
 impl ToQueryValue for Style {
     fn to_query_value(&self) -> QeuryValue {
         QeuryValue {
             name: "styles".to_string(),
-            value: match self {
-                Style::Alternate => "alternate",
-                Style::Blurred => "blurred",
-                Style::WhiteLogo => "white_logo",
-                Style::Material => "material",
-                Style::NoLogo => "no_logo",
-            }
-            .to_string(),
+            value: self.as_str().to_string(),
         }
     }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(from = "String", into = "String")]
 pub enum StyleLogo {
-    #[serde(rename = "official")]
     /// official
     Official,
-    #[serde(rename = "white")]
     /// white
     White,
-    #[serde(rename = "black")]
     /// black
     Black,
-    #[serde(rename = "custom")]
     /// custom
     Custom,
+    /// A style this crate does not know yet, kept as the api sent it
+    Unknown(String),
+}
+
+impl StyleLogo {
+    /// The name the api uses for this style, like "official"
+    pub fn as_str(&self) -> &str {
+        match self {
+            StyleLogo::Official => "official",
+            StyleLogo::White => "white",
+            StyleLogo::Black => "black",
+            StyleLogo::Custom => "custom",
+            StyleLogo::Unknown(value) => value,
+        }
+    }
+}
+
+impl From<String> for StyleLogo {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "official" => StyleLogo::Official,
+            "white" => StyleLogo::White,
+            "black" => StyleLogo::Black,
+            "custom" => StyleLogo::Custom,
+            _ => StyleLogo::Unknown(value),
+        }
+    }
+}
+
+impl From<StyleLogo> for String {
+    fn from(value: StyleLogo) -> Self {
+        match value {
+            StyleLogo::Unknown(value) => value,
+            known => known.as_str().to_string(),
+        }
+    }
 }
 
 impl ToQueryValue for StyleLogo {
     fn to_query_value(&self) -> QeuryValue {
         QeuryValue {
             name: "styles".to_string(),
-            value: match self {
-                StyleLogo::Official => "official",
-                StyleLogo::White => "white",
-                StyleLogo::Black => "black",
-                StyleLogo::Custom => "custom",
-            }
-            .to_string(),
+            value: self.as_str().to_string(),
         }
     }
 }
+
+#[derive(Serialize, Deserialize, Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
+/// Icon style
+#[serde(from = "String", into = "String")]
+pub enum StyleIcon {
+    /// official
+    Official,
+    /// custom
+    Custom,
+    /// A style this crate does not know yet, kept as the api sent it
+    Unknown(String),
+}
+
+impl StyleIcon {
+    /// The name the api uses for this style, like "official"
+    pub fn as_str(&self) -> &str {
+        match self {
+            StyleIcon::Official => "official",
+            StyleIcon::Custom => "custom",
+            StyleIcon::Unknown(value) => value,
+        }
+    }
+}
+
+impl From<String> for StyleIcon {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "official" => StyleIcon::Official,
+            "custom" => StyleIcon::Custom,
+            _ => StyleIcon::Unknown(value),
+        }
+    }
+}
+
+impl From<StyleIcon> for String {
+    fn from(value: StyleIcon) -> Self {
+        match value {
+            StyleIcon::Unknown(value) => value,
+            known => known.as_str().to_string(),
+        }
+    }
+}
+
+impl ToQueryValue for StyleIcon {
+    fn to_query_value(&self) -> QeuryValue {
+        QeuryValue {
+            name: "styles".to_string(),
+            value: self.as_str().to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn known_values_round_trip_test() {
+        for platform in [Platform::Steam, Platform::EpicGameStore, Platform::GoG] {
+            assert_eq!(platform, Platform::from(String::from(platform.clone())));
+        }
+        assert_eq!(Style::WhiteLogo, Style::from("white_logo".to_string()));
+        assert_eq!(StyleLogo::Custom, StyleLogo::from("custom".to_string()));
+        assert_eq!(StyleIcon::Official, StyleIcon::from("official".to_string()));
+        assert_eq!(
+            MimeTypeLogo::Webp,
+            MimeTypeLogo::from("image/webp".to_string())
+        );
+        assert_eq!("egs", Platform::EpicGameStore.to_string());
+    }
+
+    #[test]
+    fn unknown_values_test() {
+        let platform: Platform = serde_json::from_str("\"xbox\"").unwrap();
+        assert_eq!(Platform::Unknown("xbox".to_string()), platform);
+        assert_eq!("xbox", platform.to_string());
+        assert_eq!("\"xbox\"", serde_json::to_string(&platform).unwrap());
+
+        let style: StyleType = serde_json::from_str("\"pixel_art\"").unwrap();
+        assert_eq!(StyleType::Unknown("pixel_art".to_string()), style);
+        let style: StyleType = serde_json::from_str("\"black\"").unwrap();
+        assert_eq!(StyleType::Logo(StyleLogo::Black), style);
+
+        let mime = MimeType::Unknown("image/avif".to_string());
+        assert_eq!("image/avif", mime.to_query_value().value);
+        let styles = [Style::Alternate, Style::Unknown("pixel_art".to_string())];
+        assert_eq!(
+            Some("styles=alternate,pixel_art".to_string()),
+            to_qeury_string(Some(&styles[..]))
+        );
+    }
+}
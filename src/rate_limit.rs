@@ -0,0 +1,221 @@
+//! Limiting the rate of requests sent to the api.
+//!
+//! A [`RateLimiter`] is a token bucket shared by every clone of it,
+//! so a client and all its clones, across tasks and threads, stay below the same limit.
+
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+/// A token bucket that limits how many requests are sent per second.
+///
+/// The bucket holds up to `burst` tokens, and refills with `requests_per_second` tokens each second.
+/// Each request takes a token, and waits for one when the bucket is empty.
+///
+/// ### Examples
+/// ```
+/// use steamgriddb_api::client::Client;
+/// use steamgriddb_api::rate_limit::RateLimiter;
+/// # fn main() {
+/// let mut client = Client::new("my_auth_key");
+/// client.set_rate_limiter(RateLimiter::new(5.0, 10));
+/// let clone = client.clone();
+/// // Both clients share the same limit of 5 requests per second.
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    bucket: Arc<Mutex<Bucket>>,
+}
+
+/// Statistics about the requests that went through a [`RateLimiter`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RateLimiterStats {
+    /// The amount of requests that went through the limiter
+    pub requests: u64,
+    /// The amount of requests that had to wait
+    pub throttled: u64,
+    /// The total time requests have spent waiting
+    pub total_wait: Duration,
+    /// The longest time a single request has waited
+    pub max_wait: Duration,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    requests_per_second: f64,
+    burst: f64,
+    /// The available tokens, negative when requests are already waiting for tokens
+    tokens: f64,
+    last_refill: Instant,
+    stats: RateLimiterStats,
+}
+
+impl RateLimiter {
+    /// Create a rate limiter allowing the given amount of requests per second,
+    /// with bursts of up to `burst` requests at once.
+    ///
+    /// # Panics
+    /// Panics if `requests_per_second` is not a positive number.
+    pub fn new(requests_per_second: f64, burst: u32) -> Self {
+        assert!(
+            requests_per_second > 0.0 && requests_per_second.is_finite(),
+            "requests_per_second must be a positive number"
+        );
+        let burst = f64::from(burst.max(1));
+        Self {
+            bucket: Arc::new(Mutex::new(Bucket {
+                requests_per_second,
+                burst,
+                tokens: burst,
+                last_refill: Instant::now(),
+                stats: RateLimiterStats::default(),
+            })),
+        }
+    }
+
+    /// Get the statistics of the requests that went through this limiter, and all its clones.
+    pub fn stats(&self) -> RateLimiterStats {
+        self.bucket.lock().unwrap().stats
+    }
+
+    /// Take a token for a request, and get how long the request has to wait before it is sent.
+    ///
+    /// The token is given back if the reservation is dropped before [`Reservation::sent`] is called,
+    /// like when the request is cancelled while it waits.
+    #[cfg(any(feature = "async", feature = "blocking"))]
+    pub(crate) fn reserve(&self) -> Reservation<'_> {
+        Reservation {
+            limiter: self,
+            wait: self.reserve_at(Instant::now()),
+            sent: false,
+        }
+    }
+
+    fn reserve_at(&self, now: Instant) -> Duration {
+        let mut bucket = self.bucket.lock().unwrap();
+        let elapsed = now.saturating_duration_since(bucket.last_refill);
+        bucket.tokens =
+            (bucket.tokens + elapsed.as_secs_f64() * bucket.requests_per_second).min(bucket.burst);
+        bucket.last_refill = bucket.last_refill.max(now);
+        bucket.tokens -= 1.0;
+        let wait = if bucket.tokens < 0.0 {
+            Duration::from_secs_f64(-bucket.tokens / bucket.requests_per_second)
+        } else {
+            Duration::ZERO
+        };
+        let stats = &mut bucket.stats;
+        stats.requests += 1;
+        if !wait.is_zero() {
+            stats.throttled += 1;
+            stats.total_wait += wait;
+            stats.max_wait = stats.max_wait.max(wait);
+        }
+        wait
+    }
+
+    /// Give back a token taken by [`RateLimiter::reserve_at`], for a request that was never sent
+    fn release(&self, wait: Duration) {
+        let mut bucket = self.bucket.lock().unwrap();
+        bucket.tokens = (bucket.tokens + 1.0).min(bucket.burst);
+        let stats = &mut bucket.stats;
+        stats.requests = stats.requests.saturating_sub(1);
+        if !wait.is_zero() {
+            stats.throttled = stats.throttled.saturating_sub(1);
+            stats.total_wait = stats.total_wait.saturating_sub(wait);
+        }
+    }
+}
+
+/// A token taken from a [`RateLimiter`] for a request that has not been sent yet.
+///
+/// The token is given back to the limiter when this is dropped without calling [`Reservation::sent`].
+#[cfg(any(feature = "async", feature = "blocking"))]
+pub(crate) struct Reservation<'a> {
+    limiter: &'a RateLimiter,
+    wait: Duration,
+    sent: bool,
+}
+
+#[cfg(any(feature = "async", feature = "blocking"))]
+impl Reservation<'_> {
+    /// How long the request has to wait before it is sent
+    pub(crate) fn wait(&self) -> Duration {
+        self.wait
+    }
+
+    /// Keep the token, because the request is being sent
+    pub(crate) fn sent(mut self) {
+        self.sent = true;
+    }
+}
+
+#[cfg(any(feature = "async", feature = "blocking"))]
+impl Drop for Reservation<'_> {
+    fn drop(&mut self) {
+        if !self.sent {
+            self.limiter.release(self.wait);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn reserve_allows_burst_test() {
+        let limiter = RateLimiter::new(2.0, 3);
+        let now = Instant::now();
+        for _ in 0..3 {
+            assert_eq!(Duration::ZERO, limiter.reserve_at(now));
+        }
+        assert_eq!(Duration::from_millis(500), limiter.reserve_at(now));
+        assert_eq!(Duration::from_millis(1000), limiter.reserve_at(now));
+    }
+
+    #[test]
+    fn reserve_refills_over_time_test() {
+        let limiter = RateLimiter::new(10.0, 1);
+        let now = Instant::now();
+        assert_eq!(Duration::ZERO, limiter.reserve_at(now));
+        assert_eq!(Duration::from_millis(100), limiter.reserve_at(now));
+        let later = now + Duration::from_millis(300);
+        assert_eq!(Duration::ZERO, limiter.reserve_at(later));
+        // The bucket never holds more than the burst
+        let much_later = later + Duration::from_secs(10);
+        assert_eq!(Duration::ZERO, limiter.reserve_at(much_later));
+        assert_eq!(Duration::from_millis(100), limiter.reserve_at(much_later));
+    }
+
+    #[test]
+    fn clones_share_the_bucket_test() {
+        let limiter = RateLimiter::new(1.0, 1);
+        let clone = limiter.clone();
+        let now = Instant::now();
+        assert_eq!(Duration::ZERO, limiter.reserve_at(now));
+        assert_eq!(Duration::from_secs(1), clone.reserve_at(now));
+        let stats = limiter.stats();
+        assert_eq!(2, stats.requests);
+        assert_eq!(1, stats.throttled);
+        assert_eq!(Duration::from_secs(1), stats.total_wait);
+        assert_eq!(Duration::from_secs(1), stats.max_wait);
+    }
+
+    #[test]
+    fn release_gives_back_the_token_test() {
+        let limiter = RateLimiter::new(1.0, 1);
+        let now = Instant::now();
+        assert_eq!(Duration::ZERO, limiter.reserve_at(now));
+        let wait = limiter.reserve_at(now);
+        assert_eq!(Duration::from_secs(1), wait);
+        limiter.release(wait);
+        assert_eq!(Duration::from_secs(1), limiter.reserve_at(now));
+        let stats = limiter.stats();
+        assert_eq!(2, stats.requests);
+        assert_eq!(1, stats.throttled);
+        assert_eq!(Duration::from_secs(1), stats.total_wait);
+    }
+}
@@ -0,0 +1,264 @@
+//! Picking the best artwork for a game, falling back to the official Steam images.
+//!
+//! The resolvers themselves live next to the clients, see `client::ArtworkResolver` and `blocking::ArtworkResolver`.
+
+#[cfg(any(feature = "async", feature = "blocking"))]
+use crate::images::PagedImagesTarget;
+use crate::{
+    error::Error,
+    images::Image,
+    query_parameters::{Platform, QueryType},
+    selector::ImageSelector,
+    steam::grid::ArtworkKind,
+    steam_static::SteamAsset,
+};
+
+/// The ways a game can be identified, when resolving its artwork
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GameIdentity {
+    /// The id of the game on SteamGridDB
+    SteamGridDbId(usize),
+    /// The app id of the game on Steam
+    SteamAppId(usize),
+    /// The id of the game on a platform, like `Platform::EpicGameStore` and "Salt"
+    PlatformId(Platform, String),
+    /// The name of the game, the first search result is used
+    Name(String),
+}
+
+impl GameIdentity {
+    /// The Steam app id of the game, if it is known from the identity.
+    ///
+    /// ### Examples
+    /// ```
+    /// use steamgriddb_api::query_parameters::Platform;
+    /// use steamgriddb_api::resolver::GameIdentity;
+    /// let identity = GameIdentity::PlatformId(Platform::Steam, "361420".to_string());
+    /// assert_eq!(Some("361420".to_string()), identity.steam_app_id());
+    /// assert_eq!(None, GameIdentity::SteamGridDbId(13136).steam_app_id());
+    /// ```
+    pub fn steam_app_id(&self) -> Option<String> {
+        match self {
+            GameIdentity::SteamAppId(app_id) => Some(app_id.to_string()),
+            GameIdentity::PlatformId(Platform::Steam, app_id) => Some(app_id.clone()),
+            _ => None,
+        }
+    }
+}
+
+/// Which images to prefer for each kind of artwork.
+///
+/// For each kind the queries are tried in order, and the best image found by the selector is used.
+/// When no query finds an image, the official Steam image is used if allowed and the Steam app id is known.
+///
+/// ### Examples
+/// ```
+/// use steamgriddb_api::query_parameters::{GridDimentions, GridQueryParameters, QueryType, Style};
+/// use steamgriddb_api::resolver::ArtworkPolicy;
+///
+/// let mut policy = ArtworkPolicy::default();
+/// // Prefer alternate portrait grids, then any portrait grid
+/// let alternate = GridQueryParameters {
+///     styles: Some(&[Style::Alternate]),
+///     dimentions: Some(&[GridDimentions::D600x900]),
+///     ..Default::default()
+/// };
+/// let alternate = QueryType::Grid(Some(alternate));
+/// policy.grid.insert(0, alternate);
+/// policy.official_fallback = false;
+/// ```
+#[derive(Clone)]
+pub struct ArtworkPolicy<'a> {
+    /// The queries for portrait grids
+    pub grid: Vec<QueryType<'a>>,
+    /// The queries for wide grids
+    pub wide_grid: Vec<QueryType<'a>>,
+    /// The queries for heroes
+    pub hero: Vec<QueryType<'a>>,
+    /// The queries for logos
+    pub logo: Vec<QueryType<'a>>,
+    /// The queries for icons
+    pub icon: Vec<QueryType<'a>>,
+    /// Picks the image to use from the images a query found, and narrows the queries where possible
+    pub selector: ImageSelector,
+    /// Use the official Steam image when no image is found
+    pub official_fallback: bool,
+}
+
+impl<'a> ArtworkPolicy<'a> {
+    /// The queries for the given kind of artwork, in order of preference
+    pub fn queries(&self, kind: ArtworkKind) -> &[QueryType<'a>] {
+        match kind {
+            ArtworkKind::GridPortrait => &self.grid,
+            ArtworkKind::GridWide => &self.wide_grid,
+            ArtworkKind::Hero => &self.hero,
+            ArtworkKind::Logo => &self.logo,
+            ArtworkKind::Icon => &self.icon,
+        }
+    }
+}
+
+impl Default for ArtworkPolicy<'_> {
+    /// Grids and heroes in the sizes the Steam library uses, any hero, logo and icon, the first image found, and the official Steam images as fallback.
+    fn default() -> Self {
+        let query = |asset: SteamAsset| asset.query_type().into_iter().collect::<Vec<_>>();
+        let mut hero = query(SteamAsset::Hero);
+        hero.push(QueryType::Hero(None));
+        Self {
+            grid: query(SteamAsset::Capsule),
+            wide_grid: query(SteamAsset::Header),
+            hero,
+            logo: vec![QueryType::Logo(None)],
+            icon: vec![QueryType::Icon(None)],
+            selector: ImageSelector::default(),
+            official_fallback: true,
+        }
+    }
+}
+
+/// The artwork picked for a kind
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResolvedArtwork {
+    /// An image from SteamGridDB
+    SteamGridDb(Box<Image>),
+    /// An official Steam image, that is known to exist
+    Official {
+        /// Which of the official images this is
+        asset: SteamAsset,
+        /// The url of the image
+        url: String,
+    },
+}
+
+impl ResolvedArtwork {
+    /// The url of the full image
+    pub fn url(&self) -> &str {
+        match self {
+            ResolvedArtwork::SteamGridDb(image) => &image.url,
+            ResolvedArtwork::Official { url, .. } => url,
+        }
+    }
+}
+
+/// The artwork picked for each kind, None for the kinds where nothing was found
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ResolvedArtworkSet {
+    /// The SteamGridDB id of the game, if it was looked up
+    pub game_id: Option<usize>,
+    /// The portrait grid
+    pub grid: Option<ResolvedArtwork>,
+    /// The wide grid
+    pub wide_grid: Option<ResolvedArtwork>,
+    /// The hero
+    pub hero: Option<ResolvedArtwork>,
+    /// The logo
+    pub logo: Option<ResolvedArtwork>,
+    /// The icon
+    pub icon: Option<ResolvedArtwork>,
+}
+
+impl ResolvedArtworkSet {
+    /// The artwork for the given kind
+    pub fn get(&self, kind: ArtworkKind) -> Option<&ResolvedArtwork> {
+        match kind {
+            ArtworkKind::GridPortrait => self.grid.as_ref(),
+            ArtworkKind::GridWide => self.wide_grid.as_ref(),
+            ArtworkKind::Hero => self.hero.as_ref(),
+            ArtworkKind::Logo => self.logo.as_ref(),
+            ArtworkKind::Icon => self.icon.as_ref(),
+        }
+    }
+
+    pub(crate) fn set(&mut self, kind: ArtworkKind, artwork: Option<ResolvedArtwork>) {
+        let slot = match kind {
+            ArtworkKind::GridPortrait => &mut self.grid,
+            ArtworkKind::GridWide => &mut self.wide_grid,
+            ArtworkKind::Hero => &mut self.hero,
+            ArtworkKind::Logo => &mut self.logo,
+            ArtworkKind::Icon => &mut self.icon,
+        };
+        *slot = artwork;
+    }
+}
+
+/// Where to fetch the images of a game from, without a lookup first
+#[cfg(any(feature = "async", feature = "blocking"))]
+pub(crate) fn direct_target(identity: &GameIdentity) -> Option<PagedImagesTarget<'_>> {
+    match identity {
+        GameIdentity::SteamGridDbId(game_id) => Some(PagedImagesTarget::GameId(*game_id)),
+        GameIdentity::PlatformId(platform, game_id) => {
+            Some(PagedImagesTarget::PlatformId(platform, game_id.as_str()))
+        }
+        GameIdentity::SteamAppId(_) | GameIdentity::Name(_) => None,
+    }
+}
+
+/// The official Steam image used in place of a kind of artwork
+#[cfg(any(feature = "async", feature = "blocking"))]
+pub(crate) fn official_asset(kind: ArtworkKind) -> Option<SteamAsset> {
+    match kind {
+        ArtworkKind::GridPortrait => Some(SteamAsset::Capsule),
+        ArtworkKind::GridWide => Some(SteamAsset::Header),
+        ArtworkKind::Hero => Some(SteamAsset::Hero),
+        ArtworkKind::Logo => Some(SteamAsset::Logo),
+        ArtworkKind::Icon => None,
+    }
+}
+
+/// Turns the errors the api gives for missing games and images into None
+pub(crate) fn found<T>(result: Result<T, Error>) -> Result<Option<T>, Error> {
+    match result {
+        Ok(value) => Ok(Some(value)),
+        Err(Error::EmptyData) => Ok(None),
+        Err(error) if error.status() == Some(404) => Ok(None),
+        Err(error) => Err(error),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::response::SteamGridDbError;
+
+    #[test]
+    fn default_policy_test() {
+        let policy = ArtworkPolicy::default();
+        let kinds: Vec<_> = ArtworkKind::ALL
+            .iter()
+            .map(|kind| policy.queries(*kind).len())
+            .collect();
+        assert_eq!(vec![1, 1, 2, 1, 1], kinds);
+        assert!(policy.official_fallback);
+    }
+
+    #[test]
+    fn resolved_artwork_set_test() {
+        let mut set = ResolvedArtworkSet::default();
+        let official = ResolvedArtwork::Official {
+            asset: SteamAsset::Hero,
+            url: "https://example.com/library_hero.jpg".to_string(),
+        };
+        set.set(ArtworkKind::Hero, Some(official.clone()));
+        assert_eq!(Some(&official), set.get(ArtworkKind::Hero));
+        assert_eq!(Some(&official), set.hero.as_ref());
+        assert_eq!(None, set.get(ArtworkKind::Logo));
+        assert_eq!("https://example.com/library_hero.jpg", official.url());
+    }
+
+    #[test]
+    fn found_test() {
+        let not_found = Error::Api(SteamGridDbError {
+            status: Some(404),
+            errors: Some(vec!["Game not found".to_string()]),
+        });
+        assert_eq!(None, found::<u32>(Err(not_found)).unwrap());
+        assert_eq!(None, found::<u32>(Err(Error::EmptyData)).unwrap());
+        assert_eq!(Some(1), found(Ok(1)).unwrap());
+        let server_error = Error::Status {
+            status: 500,
+            body: String::new(),
+        };
+        assert!(found::<u32>(Err(server_error)).is_err());
+    }
+}
@@ -1,6 +1,11 @@
 use std::{error, fmt};
 
-use serde::{Deserialize, Serialize};
+use serde::{
+    de::{DeserializeOwned, IgnoredAny},
+    Deserialize, Serialize,
+};
+
+use crate::error::{body_snippet, Error};
 #[derive(Serialize, Deserialize, Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
 /// Errors from the server
 pub struct SteamGridDbError {
@@ -32,6 +37,12 @@ pub struct Response<T> {
     pub status: Option<u32>,
     /// Any errors that occurred
     pub errors: Option<Vec<String>>,
+    /// The page of a paged response
+    pub page: Option<usize>,
+    /// The total amount of items available, for paged responses
+    pub total: Option<usize>,
+    /// The maximum amount of items per page, for paged responses
+    pub limit: Option<usize>,
 }
 
 pub type SteamGridDbResult<T> = std::result::Result<T, SteamGridDbError>;
@@ -54,57 +65,217 @@ pub fn response_to_result<T>(inner: Response<Vec<T>>) -> SteamGridDbResult<Vec<T
     }
 }
 
+/// Converts the reponse to a result with the [`Error`] of the clients, for any kind of data.
+pub(crate) fn response_to_data<T>(inner: Response<T>) -> Result<T, Error> {
+    if !inner.success.unwrap_or(false) {
+        Err(Error::Api(SteamGridDbError {
+            errors: inner.errors,
+            status: inner.status,
+        }))
+    } else {
+        inner.data.ok_or(Error::EmptyData)
+    }
+}
+
+/// Converts a reponse without any relevant data to a result.
+///
+/// This is used for requests like deleting images, where only the success of the request matters.
+pub fn response_to_success<T>(inner: Response<T>) -> Result<(), Error> {
+    if !inner.success.unwrap_or(false) {
+        Err(Error::Api(SteamGridDbError {
+            errors: inner.errors,
+            status: inner.status,
+        }))
+    } else {
+        Ok(())
+    }
+}
+
 /// Converts the reponse to a result, that is easier to work with.
-/// This will also return a single list of resutls instead of a list of lists 
+/// This will also return a single list of resutls instead of a list of lists
 /// (since there is only one element in the list anyways)
 pub fn response_to_result_flat<T>(
     inner: Response<Vec<Response<Vec<T>>>>,
-) -> SteamGridDbResult<Vec<SteamGridDbResult<T>>>
-where
-    T: Clone,
-{
+) -> SteamGridDbResult<Vec<SteamGridDbResult<T>>> {
+    response_to_data_flat(inner).map_err(|error| match error {
+        Error::Api(error) => error,
+        _ => SteamGridDbError {
+            status: None,
+            errors: None,
+        },
+    })
+}
+
+/// Like [`response_to_result_flat`], with the [`Error`] of the clients.
+pub(crate) fn response_to_data_flat<T>(
+    inner: Response<Vec<Response<Vec<T>>>>,
+) -> Result<Vec<SteamGridDbResult<T>>, Error> {
+    let results = response_to_result_all(inner)?;
+    Ok(results
+        .into_iter()
+        .map(|result| {
+            result.and_then(|data| {
+                data.into_iter().next().ok_or_else(|| SteamGridDbError {
+                    status: None,
+                    errors: Some(vec!["Succes reported but no grids found".to_string()]),
+                })
+            })
+        })
+        .collect())
+}
+
+/// Converts the reponse to a result, that is easier to work with.
+/// This keeps every item in the response, with one result for each requested id,
+/// in the same order as the ids were requested.
+pub fn response_to_result_all<T>(
+    inner: Response<Vec<Response<Vec<T>>>>,
+) -> Result<Vec<SteamGridDbResult<Vec<T>>>, Error> {
     if !inner.success.unwrap_or(false) {
-        std::result::Result::Err(SteamGridDbError {
+        return Err(Error::Api(SteamGridDbError {
             errors: inner.errors,
             status: None,
-        })
-    } else {
-        match inner.data {
-            Some(data) => {
-                let inner = data.iter().map(|i| {
-                    if !i.success.unwrap_or(false) {
-                        std::result::Result::Err(SteamGridDbError {
-                            errors: i.errors.clone(),
-                            status: i.status,
-                        })
-                    } else {
-                        match &i.data {
-                            Some(data) => {
-                                let first = data.iter().next();
-                                match first {
-                                    Some(first) => Ok(first.clone()),
-                                    None => std::result::Result::Err(SteamGridDbError {
-                                        status: None,
-                                        errors: Some(vec![
-                                            "Succes reported but no grids found".to_string()
-                                        ]),
-                                    }),
-                                }
-                            }
-                            None => std::result::Result::Err(SteamGridDbError {
-                                status: None,
-                                errors: i.errors.clone(),
-                            }),
-                        }
-                    }
-                });
-                let inner_res = inner.collect();
-                Ok(inner_res)
+        }));
+    }
+    let data = inner.data.ok_or(Error::EmptyData)?;
+    Ok(data
+        .into_iter()
+        .map(|i| {
+            if !i.success.unwrap_or(false) {
+                Err(SteamGridDbError {
+                    errors: i.errors,
+                    status: i.status,
+                })
+            } else {
+                i.data.ok_or(SteamGridDbError {
+                    status: None,
+                    errors: i.errors,
+                })
             }
-            None => std::result::Result::Err(SteamGridDbError {
-                status: None,
-                errors: inner.errors,
+        })
+        .collect())
+}
+
+/// Wraps the result of a request for a single id, in the format of [`response_to_result_all`].
+///
+/// Api errors for the id are kept in the list, like they are for requests with multiple ids.
+#[cfg(any(feature = "async", feature = "blocking"))]
+pub(crate) fn single_result_to_result_all<T>(
+    result: Result<T, Error>,
+) -> Result<Vec<SteamGridDbResult<T>>, Error> {
+    match result {
+        Ok(data) => Ok(vec![Ok(data)]),
+        Err(Error::Api(error)) => Ok(vec![Err(error)]),
+        Err(error) => Err(error),
+    }
+}
+
+/// Parses a raw response body, given the HTTP status code it was received with.
+///
+/// The statuses 401 and 403 are turned into [`Error::PermissionDenied`].
+/// Other error statuses are turned into [`Error::Api`] if the body contains the api error messages,
+/// and into [`Error::Status`] otherwise.
+pub(crate) fn parse_response<T>(status: u16, body: &[u8]) -> Result<T, Error>
+where
+    T: DeserializeOwned,
+{
+    if !(200..300).contains(&status) {
+        let errors = serde_json::from_slice::<Response<IgnoredAny>>(body)
+            .ok()
+            .and_then(|response| response.errors);
+        return Err(match errors {
+            _ if status == 401 || status == 403 => Error::PermissionDenied(SteamGridDbError {
+                status: Some(status as u32),
+                errors,
+            }),
+            Some(errors) => Error::Api(SteamGridDbError {
+                status: Some(status as u32),
+                errors: Some(errors),
             }),
+            None => Error::Status {
+                status,
+                body: body_snippet(body),
+            },
+        });
+    }
+    serde_json::from_slice(body).map_err(|source| Error::Deserialize {
+        source,
+        body: body_snippet(body),
+    })
+}
+
+/// Is the response a successful api response, that parses and reports success
+pub(crate) fn is_success_response(status: u16, body: &[u8]) -> bool {
+    parse_response::<Response<IgnoredAny>>(status, body)
+        .map(|response| response.success.unwrap_or(false))
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn parse_response_api_error_test() {
+        let json = std::fs::read_to_string("testdata/games/error.json").unwrap();
+        let result = parse_response::<Response<IgnoredAny>>(404, json.as_bytes());
+        match result {
+            Err(Error::Api(SteamGridDbError { status, errors })) => {
+                assert_eq!(Some(404), status);
+                assert_eq!(Some(vec!["Game not found".to_string()]), errors);
+            }
+            _ => panic!("Expected an api error"),
+        }
+    }
+
+    #[test]
+    fn parse_response_permission_error_test() {
+        let body = br#"{"success":false,"errors":["You do not have permission to do this"]}"#;
+        let result = parse_response::<Response<IgnoredAny>>(403, body);
+        match result {
+            Err(Error::PermissionDenied(SteamGridDbError { status, errors })) => {
+                assert_eq!(Some(403), status);
+                assert_eq!(
+                    Some(vec!["You do not have permission to do this".to_string()]),
+                    errors
+                );
+            }
+            _ => panic!("Expected a permission error"),
+        }
+    }
+
+    #[test]
+    fn parse_response_status_error_test() {
+        let result = parse_response::<Response<IgnoredAny>>(502, b"Bad Gateway");
+        match result {
+            Err(Error::Status { status, body }) => {
+                assert_eq!(502, status);
+                assert_eq!("Bad Gateway", body);
+            }
+            _ => panic!("Expected a status error"),
+        }
+    }
+
+    #[test]
+    fn parse_response_deserialize_error_test() {
+        let result = parse_response::<Response<IgnoredAny>>(200, b"<html>");
+        match result {
+            Err(Error::Deserialize { body, .. }) => assert_eq!("<html>", body),
+            _ => panic!("Expected a deserialize error"),
         }
     }
+
+    #[test]
+    fn response_to_result_empty_data_test() {
+        let response: Response<Vec<u32>> = Response {
+            success: Some(true),
+            data: None,
+            status: None,
+            errors: None,
+            page: None,
+            total: None,
+            limit: None,
+        };
+        assert!(matches!(response_to_data(response), Err(Error::EmptyData)));
+    }
 }
@@ -0,0 +1,339 @@
+//! Retrying failed requests.
+//!
+//! The clients retry requests that failed because of rate limiting (status 429),
+//! server errors (status 5xx) or connection problems, as configured by a [`RetryPolicy`].
+
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    error::Error,
+    transport::{Method, ResponseHead},
+};
+
+/// Decides if and when failed requests are retried.
+///
+/// Reads (GET and HEAD requests) are retried on connection errors, status 429 and status 5xx.
+/// Uploads, votes and deletes are only retried on status 429,
+/// since the other failures could happen after the server has handled the request.
+///
+/// ### Examples
+/// ```
+/// use std::time::Duration;
+/// use steamgriddb_api::retry::RetryPolicy;
+/// let policy = RetryPolicy {
+///     max_attempts: 5,
+///     base_delay: Duration::from_secs(1),
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// The maximum amount of attempts for a request, including the first one
+    pub max_attempts: u32,
+    /// The delay before the first retry, doubled for each following retry
+    pub base_delay: Duration,
+    /// The maximum delay between two attempts
+    pub max_delay: Duration,
+    /// Randomize each delay to between half and all of it, so that many clients do not retry at the same time
+    pub jitter: bool,
+    /// Wait as long as the server asks for in the Retry-After header, when it is given, in seconds or as an HTTP date.
+    ///
+    /// When the server asks to wait longer than `max_delay`, the request is not retried.
+    pub respect_retry_after: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            jitter: true,
+            respect_retry_after: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy that never retries
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Default::default()
+        }
+    }
+
+    /// Get the delay before retrying a request, or None if it should not be retried.
+    ///
+    /// The attempt is the number of the attempt that gave the result, starting from 1.
+    pub(crate) fn retry_delay<R>(
+        &self,
+        attempt: u32,
+        method: Method,
+        result: &Result<R, Error>,
+    ) -> Option<Duration>
+    where
+        R: ResponseHead,
+    {
+        if attempt >= self.max_attempts || !should_retry(method, result) {
+            return None;
+        }
+        if self.respect_retry_after {
+            let retry_after = result
+                .as_ref()
+                .ok()
+                .and_then(|response| response.header("Retry-After"))
+                .and_then(|value| parse_retry_after(value, SystemTime::now()));
+            if let Some(delay) = retry_after {
+                // Retrying earlier than asked would only be rate limited again
+                return Some(delay).filter(|delay| *delay <= self.max_delay);
+            }
+        }
+        let factor = 2u32.saturating_pow(attempt - 1);
+        let delay = self
+            .base_delay
+            .checked_mul(factor)
+            .unwrap_or(self.max_delay)
+            .min(self.max_delay);
+        if self.jitter {
+            Some(delay.mul_f64(0.5 + random_fraction() / 2.0))
+        } else {
+            Some(delay)
+        }
+    }
+}
+
+fn should_retry<R>(method: Method, result: &Result<R, Error>) -> bool
+where
+    R: ResponseHead,
+{
+    let idempotent = matches!(method, Method::Get | Method::Head);
+    match result {
+        Ok(response) if response.status() == 429 => true,
+        Ok(response) => idempotent && (500..600).contains(&response.status()),
+        Err(Error::Http(_)) => idempotent,
+        Err(_) => false,
+    }
+}
+
+/// Parse a Retry-After header, given as seconds or as an HTTP date like "Wed, 21 Oct 2015 07:28:00 GMT"
+fn parse_retry_after(value: &str, now: SystemTime) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date = parse_http_date(value)?;
+    // A date in the past means the request can be retried right away
+    Some(date.duration_since(now).unwrap_or(Duration::ZERO))
+}
+
+/// Parse an HTTP date in the preferred format of RFC 7231, like "Wed, 21 Oct 2015 07:28:00 GMT"
+fn parse_http_date(value: &str) -> Option<SystemTime> {
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+    let mut parts = value.split_whitespace();
+    let _weekday = parts.next()?.strip_suffix(',')?;
+    let day: u64 = parts.next()?.parse().ok()?;
+    let month = parts.next()?;
+    let month = MONTHS.iter().position(|name| *name == month)? as u64 + 1;
+    let year: u64 = parts.next()?.parse().ok()?;
+    let mut time = parts
+        .next()?
+        .split(':')
+        .map(|part| part.parse::<u64>().ok());
+    let (hour, minute, second) = (time.next()??, time.next()??, time.next()??);
+    if parts.next()? != "GMT" || parts.next().is_some() || time.next().is_some() {
+        return None;
+    }
+    if year < 1970 || !(1..=31).contains(&day) || hour > 23 || minute > 59 || second > 60 {
+        return None;
+    }
+    // Days since 1970 of a civil date, from Howard Hinnant's `days_from_civil`
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year / 400;
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = (era * 146_097 + day_of_era).checked_sub(719_468)?;
+    let seconds = days * 86_400 + hour * 3600 + minute * 60 + second;
+    Some(UNIX_EPOCH + Duration::from_secs(seconds))
+}
+
+/// A random number in [0, 1)
+fn random_fraction() -> f64 {
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u64(0);
+    (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::transport::HttpResponse;
+
+    fn response(status: u16, headers: &[(&str, &str)]) -> Result<HttpResponse, Error> {
+        Ok(HttpResponse {
+            status,
+            headers: headers
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+            body: Vec::new(),
+        })
+    }
+
+    fn policy() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 4,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(1),
+            jitter: false,
+            respect_retry_after: true,
+        }
+    }
+
+    #[test]
+    fn retry_delay_backs_off_exponentially_test() {
+        let policy = policy();
+        let result = response(503, &[]);
+        assert_eq!(
+            Some(Duration::from_millis(100)),
+            policy.retry_delay(1, Method::Get, &result)
+        );
+        assert_eq!(
+            Some(Duration::from_millis(200)),
+            policy.retry_delay(2, Method::Get, &result)
+        );
+        assert_eq!(
+            Some(Duration::from_millis(400)),
+            policy.retry_delay(3, Method::Get, &result)
+        );
+        assert_eq!(None, policy.retry_delay(4, Method::Get, &result));
+    }
+
+    #[test]
+    fn retry_delay_caps_at_max_delay_test() {
+        let policy = RetryPolicy {
+            max_attempts: 20,
+            ..policy()
+        };
+        let result = response(500, &[]);
+        assert_eq!(
+            Some(Duration::from_secs(1)),
+            policy.retry_delay(10, Method::Get, &result)
+        );
+    }
+
+    #[test]
+    fn retry_delay_respects_retry_after_test() {
+        let policy = RetryPolicy {
+            max_delay: Duration::from_secs(10),
+            ..policy()
+        };
+        let result = response(429, &[("retry-after", "3")]);
+        assert_eq!(
+            Some(Duration::from_secs(3)),
+            policy.retry_delay(1, Method::Get, &result)
+        );
+        // Waiting longer than the max delay gives up, instead of retrying too early
+        let longer = response(429, &[("retry-after", "60")]);
+        assert_eq!(None, policy.retry_delay(1, Method::Get, &longer));
+        let past_date = response(429, &[("retry-after", "Wed, 21 Oct 2015 07:28:00 GMT")]);
+        assert_eq!(
+            Some(Duration::ZERO),
+            policy.retry_delay(1, Method::Get, &past_date)
+        );
+        let ignoring = RetryPolicy {
+            respect_retry_after: false,
+            ..policy.clone()
+        };
+        assert_eq!(
+            Some(Duration::from_millis(100)),
+            ignoring.retry_delay(1, Method::Get, &result)
+        );
+    }
+
+    #[test]
+    fn retry_delay_with_jitter_test() {
+        let policy = RetryPolicy {
+            jitter: true,
+            ..policy()
+        };
+        let delay = policy
+            .retry_delay(2, Method::Get, &response(502, &[]))
+            .unwrap();
+        assert!(delay >= Duration::from_millis(100));
+        assert!(delay <= Duration::from_millis(200));
+    }
+
+    #[test]
+    fn retry_delay_only_retries_writes_when_rate_limited_test() {
+        let policy = policy();
+        assert_eq!(
+            None,
+            policy.retry_delay(1, Method::Post, &response(503, &[]))
+        );
+        assert_eq!(
+            None,
+            policy.retry_delay(1, Method::Delete, &response(500, &[]))
+        );
+        let transport_error: Result<HttpResponse, Error> =
+            Err(Error::Http("connection reset".into()));
+        assert_eq!(None, policy.retry_delay(1, Method::Post, &transport_error));
+        assert!(policy
+            .retry_delay(1, Method::Get, &transport_error)
+            .is_some());
+        assert!(policy
+            .retry_delay(1, Method::Post, &response(429, &[]))
+            .is_some());
+    }
+
+    #[test]
+    fn retry_delay_does_not_retry_client_errors_test() {
+        let policy = policy();
+        assert_eq!(
+            None,
+            policy.retry_delay(1, Method::Get, &response(404, &[]))
+        );
+        assert_eq!(
+            None,
+            policy.retry_delay(1, Method::Get, &response(200, &[]))
+        );
+        assert_eq!(
+            None,
+            RetryPolicy::none().retry_delay(1, Method::Get, &response(503, &[]))
+        );
+    }
+
+    #[test]
+    fn parse_retry_after_test() {
+        let now = UNIX_EPOCH + Duration::from_secs(1_445_412_480);
+        assert_eq!(
+            Some(Duration::from_secs(120)),
+            parse_retry_after(" 120 ", now)
+        );
+        assert_eq!(
+            Some(Duration::from_secs(3600)),
+            parse_retry_after("Wed, 21 Oct 2015 08:28:00 GMT", now)
+        );
+        assert_eq!(
+            Some(Duration::ZERO),
+            parse_retry_after("Wed, 21 Oct 2015 06:28:00 GMT", now)
+        );
+        assert_eq!(
+            Some(UNIX_EPOCH + Duration::from_secs(951_782_400)),
+            parse_http_date("Tue, 29 Feb 2000 00:00:00 GMT")
+        );
+        assert_eq!(None, parse_retry_after("soon", now));
+        assert_eq!(None, parse_retry_after("-5", now));
+        assert_eq!(None, parse_http_date("Wed, 21 Oct 2015 07:28:00 CET"));
+        assert_eq!(None, parse_http_date("Wed, 21 Foo 2015 07:28:00 GMT"));
+        assert_eq!(None, parse_http_date("Wed, 21 Oct 2015 07:28 GMT"));
+    }
+}
@@ -16,7 +16,7 @@ pub struct SearchResult {
     /// The release date of the game
     pub release_date: Option<usize>,
     /// Is this game verified?
-    pub verified:bool,    
+    pub verified: bool,
     /// The id of the game
     pub id: usize,
     /// The platform types of this game
@@ -24,6 +24,7 @@ pub struct SearchResult {
 }
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod tests {
 
     use super::*;
@@ -46,7 +47,6 @@ mod tests {
         assert_eq!(game_response.data.is_some(), true);
         assert_eq!(game_response.errors.is_none(), true);
 
-
         if let Some(data) = game_response.data {
             assert_eq!(data.len(), 15);
 
@@ -0,0 +1,539 @@
+//! Picking the best image from a list of images.
+//!
+//! An [`ImageSelector`] leaves out the images that do not pass its filters, scores the rest by its weighted preferences,
+//! and breaks ties with its tie breakers. Images that are still equal keep the order the api returned them in.
+
+use std::{cmp::Ordering, fmt, sync::Arc};
+
+use crate::{
+    images::Image,
+    query_parameters::{
+        AnimtionType, Epilepsy, GridQueryParameters, HeroQueryParameters, Humor,
+        IconQueryParameters, LogoQueryParameters, Nsfw, QueryType, Style, StyleLogo, StyleType,
+    },
+};
+
+const STATIC: &[AnimtionType] = &[AnimtionType::Static];
+const ANIMATED: &[AnimtionType] = &[AnimtionType::Animated];
+
+/// A custom filter, that can capture the values it filters on
+#[derive(Clone)]
+struct Filter(Arc<dyn Fn(&Image) -> bool + Send + Sync>);
+
+impl fmt::Debug for Filter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Filter")
+    }
+}
+
+/// Something an image can be preferred for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Preference {
+    /// Images in the language, like "en"
+    Language(String),
+    /// Static or animated images
+    Animation(AnimtionType),
+    /// Images with one of the styles, where earlier styles count more
+    Styles(Vec<StyleType>),
+    /// Images with a higher score
+    Score,
+    /// Images with more upvotes than downvotes
+    NetVotes,
+    /// Images with more pixels
+    Size,
+}
+
+impl Preference {
+    /// How well each image matches the preference, from 0 to 1
+    fn values(&self, images: &[&Image]) -> Vec<f64> {
+        match self {
+            Preference::Language(language) => images
+                .iter()
+                .map(|image| flag(image.language.eq_ignore_ascii_case(language)))
+                .collect(),
+            Preference::Animation(animation) => images
+                .iter()
+                .map(|image| flag(image.is_animated() == is_animated(animation)))
+                .collect(),
+            Preference::Styles(styles) => images
+                .iter()
+                .map(
+                    |image| match styles.iter().position(|style| *style == image.style) {
+                        Some(index) => 1.0 - index as f64 / styles.len() as f64,
+                        None => 0.0,
+                    },
+                )
+                .collect(),
+            Preference::Score => normalized(images.iter().map(|image| i64::from(image.score))),
+            Preference::NetVotes => normalized(images.iter().map(|image| image.net_votes())),
+            Preference::Size => normalized(
+                images
+                    .iter()
+                    .map(|image| i64::from(image.width) * i64::from(image.height)),
+            ),
+        }
+    }
+}
+
+/// How to order images that are scored the same
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TieBreaker {
+    /// The image with the higher score first
+    HigherScore,
+    /// The image with more upvotes than downvotes first
+    MoreNetVotes,
+    /// The image with more upvotes first
+    MoreUpvotes,
+    /// The image with more pixels first
+    Larger,
+    /// The most recently uploaded image first, the one with the highest id
+    Newer,
+    /// The first uploaded image first, the one with the lowest id
+    Older,
+}
+
+impl TieBreaker {
+    fn compare(&self, a: &Image, b: &Image) -> Ordering {
+        let size = |image: &Image| u64::from(image.width) * u64::from(image.height);
+        match self {
+            TieBreaker::HigherScore => b.score.cmp(&a.score),
+            TieBreaker::MoreNetVotes => b.net_votes().cmp(&a.net_votes()),
+            TieBreaker::MoreUpvotes => b.upvotes.cmp(&a.upvotes),
+            TieBreaker::Larger => size(b).cmp(&size(a)),
+            TieBreaker::Newer => b.id.cmp(&a.id),
+            TieBreaker::Older => a.id.cmp(&b.id),
+        }
+    }
+}
+
+/// Picks the best image from a list of images, with filters, weighted preferences and tie breakers.
+///
+/// ### Examples
+/// ```
+/// # use steamgriddb_api::images::Image;
+/// use steamgriddb_api::query_parameters::{AnimtionType, Style, StyleType};
+/// use steamgriddb_api::selector::{ImageSelector, Preference, TieBreaker};
+///
+/// # fn example(images: Vec<Image>) {
+/// let selector = ImageSelector::new()
+///     .exclude_nsfw()
+///     .exclude_epilepsy()
+///     .only_animation(AnimtionType::Static)
+///     .prefer(Preference::Language("en".to_string()), 2.0)
+///     .prefer(Preference::Styles(vec![StyleType::Normal(Style::Alternate)]), 1.0)
+///     .prefer(Preference::NetVotes, 0.5)
+///     .tie_breaker(TieBreaker::HigherScore);
+/// let best = selector.select(&images);
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ImageSelector {
+    exclude_nsfw: bool,
+    exclude_humor: bool,
+    exclude_epilepsy: bool,
+    animation: Option<AnimtionType>,
+    styles: Vec<Style>,
+    logo_styles: Vec<StyleLogo>,
+    languages: Vec<String>,
+    filters: Vec<Filter>,
+    preferences: Vec<(Preference, f64)>,
+    tie_breakers: Vec<TieBreaker>,
+}
+
+impl ImageSelector {
+    /// Creates a selector that keeps all images in the order they are given.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Leave out images that are Not Safe For Work.
+    pub fn exclude_nsfw(mut self) -> Self {
+        self.exclude_nsfw = true;
+        self
+    }
+
+    /// Leave out humorous images.
+    pub fn exclude_humor(mut self) -> Self {
+        self.exclude_humor = true;
+        self
+    }
+
+    /// Leave out images that can trigger epilepsy.
+    pub fn exclude_epilepsy(mut self) -> Self {
+        self.exclude_epilepsy = true;
+        self
+    }
+
+    /// Only keep static or only keep animated images.
+    ///
+    /// See [`Image::is_animated`] for how animated images are recognized.
+    pub fn only_animation(mut self, animation: AnimtionType) -> Self {
+        self.animation = Some(animation);
+        self
+    }
+
+    /// Only keep grid, hero and icon images with one of the styles.
+    pub fn only_styles(mut self, styles: &[Style]) -> Self {
+        self.styles = styles.to_vec();
+        self
+    }
+
+    /// Only keep logos with one of the styles.
+    pub fn only_logo_styles(mut self, styles: &[StyleLogo]) -> Self {
+        self.logo_styles = styles.to_vec();
+        self
+    }
+
+    /// Only keep images in one of the languages, like "en".
+    pub fn only_languages(mut self, languages: &[&str]) -> Self {
+        self.languages = languages
+            .iter()
+            .map(|language| language.to_string())
+            .collect();
+        self
+    }
+
+    /// Only keep images the filter returns true for.
+    ///
+    /// ### Examples
+    /// ```
+    /// use steamgriddb_api::selector::ImageSelector;
+    /// let min_width = 600;
+    /// let selector = ImageSelector::new().filter(move |image| !image.lock && image.width >= min_width);
+    /// ```
+    pub fn filter<F>(mut self, filter: F) -> Self
+    where
+        F: Fn(&Image) -> bool + Send + Sync + 'static,
+    {
+        self.filters.push(Filter(Arc::new(filter)));
+        self
+    }
+
+    /// Prefer images with the given weight.
+    ///
+    /// Each image gets a value from 0 to 1 for each preference, and the images with the highest weighted sum come first.
+    /// Scores, votes and sizes are scaled between the lowest and highest of the images being compared.
+    pub fn prefer(mut self, preference: Preference, weight: f64) -> Self {
+        self.preferences.push((preference, weight));
+        self
+    }
+
+    /// Order images with the same weighted sum, after the tie breakers added before.
+    pub fn tie_breaker(mut self, tie_breaker: TieBreaker) -> Self {
+        self.tie_breakers.push(tie_breaker);
+        self
+    }
+
+    /// Does the image pass all filters?
+    pub fn matches(&self, image: &Image) -> bool {
+        let style_allowed = match &image.style {
+            StyleType::Normal(style) => self.styles.is_empty() || self.styles.contains(style),
+            StyleType::Logo(style) => {
+                self.logo_styles.is_empty() || self.logo_styles.contains(style)
+            }
+            StyleType::Unknown(_) => self.styles.is_empty() && self.logo_styles.is_empty(),
+        };
+        let excluded = (self.exclude_nsfw && image.nsfw)
+            || (self.exclude_humor && image.humor)
+            || (self.exclude_epilepsy && image.epilepsy);
+        let animation_allowed = match &self.animation {
+            Some(animation) => image.is_animated() == is_animated(animation),
+            None => true,
+        };
+        let language_allowed = self.languages.is_empty()
+            || self
+                .languages
+                .iter()
+                .any(|language| language.eq_ignore_ascii_case(&image.language));
+        !excluded
+            && animation_allowed
+            && style_allowed
+            && language_allowed
+            && self.filters.iter().all(|filter| (filter.0)(image))
+    }
+
+    /// The images that pass the filters, best first.
+    pub fn rank<'i>(&self, images: &'i [Image]) -> Vec<&'i Image> {
+        let candidates: Vec<&Image> = images.iter().filter(|image| self.matches(image)).collect();
+        let mut totals = vec![0.0; candidates.len()];
+        for (preference, weight) in self.preferences.iter() {
+            for (total, value) in totals.iter_mut().zip(preference.values(&candidates)) {
+                *total += weight * value;
+            }
+        }
+        // Rounded, so that sums that only differ by floating point errors are tied
+        let totals: Vec<i64> = totals
+            .iter()
+            .map(|total| (total * 1_000_000.0).round() as i64)
+            .collect();
+        let mut ranked: Vec<(i64, &Image)> = totals.into_iter().zip(candidates).collect();
+        ranked.sort_by(|(total_a, a), (total_b, b)| {
+            total_b.cmp(total_a).then_with(|| {
+                self.tie_breakers
+                    .iter()
+                    .map(|tie_breaker| tie_breaker.compare(a, b))
+                    .find(|ordering| *ordering != Ordering::Equal)
+                    .unwrap_or(Ordering::Equal)
+            })
+        });
+        ranked.into_iter().map(|(_, image)| image).collect()
+    }
+
+    /// The best image that passes the filters.
+    pub fn select<'i>(&self, images: &'i [Image]) -> Option<&'i Image> {
+        self.rank(images).into_iter().next()
+    }
+
+    /// The best image that passes the filters, taken out of the list.
+    pub fn select_owned(&self, images: Vec<Image>) -> Option<Image> {
+        let best = self.select(&images)?;
+        let index = images.iter().position(|image| std::ptr::eq(image, best))?;
+        images.into_iter().nth(index)
+    }
+
+    /// Add the filters that the api can apply to the parameters of the query.
+    ///
+    /// Parameters that are already set in the query are kept. Languages and custom filters can only be applied with [`ImageSelector::rank`],
+    /// so the images should still be ranked after fetching them.
+    ///
+    /// ### Examples
+    /// ```
+    /// use steamgriddb_api::images::get_images_by_game_id_url;
+    /// use steamgriddb_api::query_parameters::QueryType;
+    /// use steamgriddb_api::selector::ImageSelector;
+    ///
+    /// let selector = ImageSelector::new().exclude_nsfw().exclude_humor();
+    /// let query = selector.narrow_query(QueryType::Hero(None));
+    /// let url = get_images_by_game_id_url("https://www.steamgriddb.com/api/v2", 13136, &query);
+    /// assert_eq!(
+    ///     "https://www.steamgriddb.com/api/v2/heroes/game/13136?nsfw=false&humor=false",
+    ///     url
+    /// );
+    /// ```
+    pub fn narrow_query<'a>(&'a self, query: QueryType<'a>) -> QueryType<'a> {
+        let nsfw = self.exclude_nsfw.then_some(&Nsfw::False);
+        let humor = self.exclude_humor.then_some(&Humor::False);
+        let epilepsy = self.exclude_epilepsy.then_some(&Epilepsy::False);
+        let types = self.animation.as_ref().map(|animation| match animation {
+            AnimtionType::Static => STATIC,
+            AnimtionType::Animated => ANIMATED,
+        });
+        let styles = Some(self.styles.as_slice()).filter(|styles| !styles.is_empty());
+        let logo_styles = Some(self.logo_styles.as_slice()).filter(|styles| !styles.is_empty());
+        match query {
+            QueryType::Grid(parameters) => {
+                let mut parameters = parameters.unwrap_or_else(GridQueryParameters::default);
+                parameters.nsfw = parameters.nsfw.or(nsfw);
+                parameters.humor = parameters.humor.or(humor);
+                parameters.epilepsy = parameters.epilepsy.or(epilepsy);
+                parameters.types = parameters.types.or(types);
+                parameters.styles = parameters.styles.or(styles);
+                QueryType::Grid(Some(parameters))
+            }
+            QueryType::Hero(parameters) => {
+                let mut parameters = parameters.unwrap_or_else(HeroQueryParameters::default);
+                parameters.nsfw = parameters.nsfw.or(nsfw);
+                parameters.humor = parameters.humor.or(humor);
+                parameters.epilepsy = parameters.epilepsy.or(epilepsy);
+                parameters.types = parameters.types.or(types);
+                parameters.styles = parameters.styles.or(styles);
+                QueryType::Hero(Some(parameters))
+            }
+            QueryType::Logo(parameters) => {
+                let mut parameters = parameters.unwrap_or_else(LogoQueryParameters::default);
+                parameters.nsfw = parameters.nsfw.or(nsfw);
+                parameters.humor = parameters.humor.or(humor);
+                parameters.epilepsy = parameters.epilepsy.or(epilepsy);
+                parameters.types = parameters.types.or(types);
+                parameters.styles = parameters.styles.or(logo_styles);
+                QueryType::Logo(Some(parameters))
+            }
+            QueryType::Icon(parameters) => {
+                let mut parameters = parameters.unwrap_or_else(IconQueryParameters::default);
+                parameters.nsfw = parameters.nsfw.or(nsfw);
+                parameters.humor = parameters.humor.or(humor);
+                parameters.epilepsy = parameters.epilepsy.or(epilepsy);
+                parameters.types = parameters.types.or(types);
+                parameters.styles = parameters.styles.or(styles);
+                QueryType::Icon(Some(parameters))
+            }
+        }
+    }
+}
+
+fn is_animated(animation: &AnimtionType) -> bool {
+    *animation == AnimtionType::Animated
+}
+
+fn flag(value: bool) -> f64 {
+    if value {
+        1.0
+    } else {
+        0.0
+    }
+}
+
+/// Scale the values between 0 for the lowest and 1 for the highest
+fn normalized<I>(values: I) -> Vec<f64>
+where
+    I: Iterator<Item = i64>,
+{
+    let values: Vec<i64> = values.collect();
+    let min = values.iter().copied().min().unwrap_or_default();
+    let max = values.iter().copied().max().unwrap_or_default();
+    values
+        .iter()
+        .map(|value| {
+            if max == min {
+                0.0
+            } else {
+                (value - min) as f64 / (max - min) as f64
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::{images::get_images_by_game_id_url, response::Response};
+
+    fn test_images() -> Vec<Image> {
+        let json = std::fs::read_to_string("testdata/grids/grids_for_single_id.json").unwrap();
+        let response: Response<Vec<Image>> = serde_json::from_str(&json).unwrap();
+        response.data.unwrap()
+    }
+
+    fn ids(images: &[&Image]) -> Vec<u32> {
+        images.iter().map(|image| image.id).collect()
+    }
+
+    #[test]
+    fn default_keeps_order_test() {
+        let images = test_images();
+        let ranked = ImageSelector::new().rank(&images);
+        let expected: Vec<u32> = images.iter().map(|image| image.id).collect();
+        assert_eq!(expected, ids(&ranked));
+        assert_eq!(Some(&images[0]), ImageSelector::new().select(&images));
+        assert_eq!(None, ImageSelector::new().select(&[]));
+    }
+
+    #[test]
+    fn filters_test() {
+        let mut images = test_images();
+        images[0].nsfw = true;
+        images[1].humor = true;
+        images[2].epilepsy = true;
+        images[3].language = "de".to_string();
+        images[4].thumb =
+            "https://cdn2.steamgriddb.com/file/sgdb-cdn/thumb/animated.webm".to_string();
+        let selector = ImageSelector::new()
+            .exclude_nsfw()
+            .exclude_humor()
+            .exclude_epilepsy()
+            .only_languages(&["EN"])
+            .only_animation(AnimtionType::Static)
+            .only_styles(&[Style::Alternate, Style::WhiteLogo]);
+        let ranked = selector.rank(&images);
+        assert!(ranked.iter().all(|image| selector.matches(image)));
+        assert_eq!(Some(104129), ranked.first().map(|image| image.id));
+        assert!(ranked
+            .iter()
+            .all(|image| image.style != StyleType::Normal(Style::Blurred)));
+
+        let animated = ImageSelector::new().only_animation(AnimtionType::Animated);
+        assert_eq!(vec![41544], ids(&animated.rank(&images)));
+        let wide = ImageSelector::new().filter(|image| image.width > image.height);
+        assert!(wide
+            .rank(&images)
+            .iter()
+            .all(|image| image.width > image.height));
+        let excluded_author = images[0].author.steam64.clone();
+        let others =
+            ImageSelector::new().filter(move |image| image.author.steam64 != excluded_author);
+        assert!(!others.rank(&images).is_empty());
+        assert!(others
+            .rank(&images)
+            .iter()
+            .all(|image| image.author.steam64 != images[0].author.steam64));
+    }
+
+    #[test]
+    fn weighted_preferences_test() {
+        let mut images = test_images();
+        images[5].upvotes = 10;
+        images[6].score = 5;
+        images[7].language = "de".to_string();
+        let by_votes = ImageSelector::new().prefer(Preference::NetVotes, 1.0);
+        assert_eq!(Some(104129), by_votes.select(&images).map(|image| image.id));
+
+        // The score counts more than the votes
+        let by_score = by_votes.clone().prefer(Preference::Score, 2.0);
+        assert_eq!(Some(148935), by_score.select(&images).map(|image| image.id));
+
+        let german = ImageSelector::new()
+            .prefer(Preference::Language("de".to_string()), 1.0)
+            .prefer(Preference::NetVotes, 0.5);
+        assert_eq!(Some(108407), german.select(&images).map(|image| image.id));
+
+        let styles = ImageSelector::new().prefer(
+            Preference::Styles(vec![
+                StyleType::Normal(Style::NoLogo),
+                StyleType::Normal(Style::Blurred),
+            ]),
+            1.0,
+        );
+        let ranked = styles.rank(&images);
+        assert_eq!(vec![50912, 50911, 94734, 119666], ids(&ranked[..4]));
+    }
+
+    #[test]
+    fn tie_breakers_test() {
+        let images = test_images();
+        let selector = ImageSelector::new()
+            .prefer(Preference::Size, 1.0)
+            .tie_breaker(TieBreaker::Newer);
+        let ranked = selector.rank(&images);
+        assert_eq!(vec![151231, 148935, 148159, 144094], ids(&ranked[..4]));
+
+        let older = ImageSelector::new().tie_breaker(TieBreaker::Older);
+        assert_eq!(Some(30792), older.select(&images).map(|image| image.id));
+        assert_eq!(
+            Some(30792),
+            older.select_owned(images).map(|image| image.id)
+        );
+    }
+
+    #[test]
+    fn narrow_query_test() {
+        let url = |query: &QueryType| get_images_by_game_id_url("", 1, query);
+        let selector = ImageSelector::new()
+            .exclude_nsfw()
+            .exclude_epilepsy()
+            .only_animation(AnimtionType::Animated)
+            .only_styles(&[Style::Alternate])
+            .only_logo_styles(&[StyleLogo::Official]);
+        assert_eq!(
+            "/grids/game/1?styles=alternate&types=animated&nsfw=false&epilepsy=false",
+            url(&selector.narrow_query(QueryType::Grid(None)))
+        );
+        assert_eq!(
+            "/logos/game/1?styles=official&types=animated&nsfw=false&epilepsy=false",
+            url(&selector.narrow_query(QueryType::Logo(None)))
+        );
+
+        let any_nsfw = GridQueryParameters {
+            nsfw: Some(&Nsfw::Any),
+            ..Default::default()
+        };
+        assert_eq!(
+            "/grids/game/1?styles=alternate&types=animated&nsfw=any&epilepsy=false",
+            url(&selector.narrow_query(QueryType::Grid(Some(any_nsfw))))
+        );
+        assert_eq!(
+            "/icons/game/1",
+            url(&ImageSelector::new().narrow_query(QueryType::Icon(None)))
+        );
+    }
+}
@@ -0,0 +1,278 @@
+//! Reading and writing the binary VDF format Steam uses for files like `shortcuts.vdf`.
+//!
+//! A binary VDF file is a list of named values, where each value is a type byte, a null terminated name, and the data.
+//! Maps contain more named values, and end with a 0x08 byte.
+
+use crate::error::Error;
+
+const MAP: u8 = 0x00;
+const STRING: u8 = 0x01;
+const INT: u8 = 0x02;
+const FLOAT: u8 = 0x03;
+const UINT64: u8 = 0x07;
+/// Ends a map
+const END: u8 = 0x08;
+
+/// How deeply maps can be nested, so a corrupt file can not overflow the stack
+const MAX_DEPTH: usize = 64;
+
+/// A named value in a binary VDF file
+pub type Entry = (String, Value);
+
+/// A value in a binary VDF file
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    /// A map of named values, in the order they appear in the file
+    Map(Vec<Entry>),
+    /// A string
+    String(String),
+    /// A signed 32-bit number
+    Int(i32),
+    /// A 32-bit floating point number
+    Float(f32),
+    /// An unsigned 64-bit number
+    UInt64(u64),
+}
+
+impl Value {
+    /// Get the entries, if this is a map
+    pub fn as_map(&self) -> Option<&[Entry]> {
+        match self {
+            Value::Map(entries) => Some(entries),
+            _ => None,
+        }
+    }
+
+    /// Get the string, if this is a string
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Get the number, if this is an int
+    pub fn as_int(&self) -> Option<i32> {
+        match self {
+            Value::Int(value) => Some(*value),
+            _ => None,
+        }
+    }
+}
+
+/// Parse the entries of a binary VDF file.
+///
+/// ### Examples
+/// ```
+/// use steamgriddb_api::steam::binary_vdf::{parse, Value};
+/// let entries = parse(b"\x00shortcuts\x00\x08\x08").unwrap();
+/// assert_eq!(vec![("shortcuts".to_string(), Value::Map(Vec::new()))], entries);
+/// ```
+pub fn parse(data: &[u8]) -> Result<Vec<Entry>, Error> {
+    let mut reader = Reader { data, position: 0 };
+    let entries = reader.read_map(0)?;
+    if reader.position < data.len() {
+        return Err(reader.error("data after the end of the file"));
+    }
+    Ok(entries)
+}
+
+/// Write entries in the binary VDF format.
+///
+/// Names and strings can not contain null bytes, since they end strings in the file.
+///
+/// ### Examples
+/// ```
+/// use steamgriddb_api::steam::binary_vdf::{to_bytes, Value};
+/// let entries = vec![("shortcuts".to_string(), Value::Map(Vec::new()))];
+/// assert_eq!(b"\x00shortcuts\x00\x08\x08".to_vec(), to_bytes(&entries).unwrap());
+/// ```
+pub fn to_bytes(entries: &[Entry]) -> Result<Vec<u8>, Error> {
+    let mut data = Vec::new();
+    write_map(&mut data, entries)?;
+    Ok(data)
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl Reader<'_> {
+    fn error(&self, message: &str) -> Error {
+        self.error_at(self.position, message)
+    }
+
+    fn error_at(&self, position: usize, message: &str) -> Error {
+        Error::Vdf(format!("{} at byte {}", message, position))
+    }
+
+    fn read_byte(&mut self) -> Result<u8, Error> {
+        let byte = *self
+            .data
+            .get(self.position)
+            .ok_or_else(|| self.error("unexpected end of file"))?;
+        self.position += 1;
+        Ok(byte)
+    }
+
+    fn read_bytes<const N: usize>(&mut self) -> Result<[u8; N], Error> {
+        let end = self.position + N;
+        let bytes = self
+            .data
+            .get(self.position..end)
+            .ok_or_else(|| self.error("unexpected end of file"))?;
+        self.position = end;
+        let mut array = [0; N];
+        array.copy_from_slice(bytes);
+        Ok(array)
+    }
+
+    fn read_string(&mut self) -> Result<String, Error> {
+        let rest = &self.data[self.position..];
+        let length = rest
+            .iter()
+            .position(|byte| *byte == 0)
+            .ok_or_else(|| self.error("unterminated string"))?;
+        let value = String::from_utf8(rest[..length].to_vec())
+            .map_err(|_| self.error("string is not valid UTF-8"))?;
+        self.position += length + 1;
+        Ok(value)
+    }
+
+    /// Read entries until the end of the map, that is nested in `depth` other maps
+    fn read_map(&mut self, depth: usize) -> Result<Vec<Entry>, Error> {
+        let mut entries = Vec::new();
+        loop {
+            let start = self.position;
+            let kind = self.read_byte()?;
+            if kind == END {
+                return Ok(entries);
+            }
+            let name = self.read_string()?;
+            let value = match kind {
+                MAP if depth >= MAX_DEPTH => {
+                    return Err(self.error_at(start, "maps nested too deeply"));
+                }
+                MAP => Value::Map(self.read_map(depth + 1)?),
+                STRING => Value::String(self.read_string()?),
+                INT => Value::Int(i32::from_le_bytes(self.read_bytes()?)),
+                FLOAT => Value::Float(f32::from_le_bytes(self.read_bytes()?)),
+                UINT64 => Value::UInt64(u64::from_le_bytes(self.read_bytes()?)),
+                _ => {
+                    let message = format!("unknown value type {:#04x}", kind);
+                    return Err(self.error_at(start, &message));
+                }
+            };
+            entries.push((name, value));
+        }
+    }
+}
+
+fn write_map(data: &mut Vec<u8>, entries: &[Entry]) -> Result<(), Error> {
+    for (name, value) in entries {
+        let kind = match value {
+            Value::Map(_) => MAP,
+            Value::String(_) => STRING,
+            Value::Int(_) => INT,
+            Value::Float(_) => FLOAT,
+            Value::UInt64(_) => UINT64,
+        };
+        data.push(kind);
+        write_string(data, name)?;
+        match value {
+            Value::Map(entries) => write_map(data, entries)?,
+            Value::String(value) => write_string(data, value)?,
+            Value::Int(value) => data.extend_from_slice(&value.to_le_bytes()),
+            Value::Float(value) => data.extend_from_slice(&value.to_le_bytes()),
+            Value::UInt64(value) => data.extend_from_slice(&value.to_le_bytes()),
+        }
+    }
+    data.push(END);
+    Ok(())
+}
+
+fn write_string(data: &mut Vec<u8>, value: &str) -> Result<(), Error> {
+    if value.contains('\0') {
+        return Err(Error::Vdf(format!("{:?} contains a null byte", value)));
+    }
+    data.extend_from_slice(value.as_bytes());
+    data.push(0);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn parse_values_test() {
+        let mut data = b"\x00root\x00\x01name\x00value\x00\x02int\x00".to_vec();
+        data.extend_from_slice(&(-2i32).to_le_bytes());
+        data.extend_from_slice(b"\x03float\x00");
+        data.extend_from_slice(&1.5f32.to_le_bytes());
+        data.extend_from_slice(b"\x07big\x00");
+        data.extend_from_slice(&u64::MAX.to_le_bytes());
+        data.extend_from_slice(b"\x08\x08");
+        let entries = parse(&data).unwrap();
+        let expected = vec![(
+            "root".to_string(),
+            Value::Map(vec![
+                ("name".to_string(), Value::String("value".to_string())),
+                ("int".to_string(), Value::Int(-2)),
+                ("float".to_string(), Value::Float(1.5)),
+                ("big".to_string(), Value::UInt64(u64::MAX)),
+            ]),
+        )];
+        assert_eq!(expected, entries);
+        assert_eq!(data, to_bytes(&entries).unwrap());
+    }
+
+    #[test]
+    fn parse_errors_test() {
+        assert!(matches!(parse(b""), Err(Error::Vdf(_))));
+        assert!(matches!(parse(b"\x00root\x00\x08"), Err(Error::Vdf(_))));
+        assert!(matches!(parse(b"\x01name\x00value"), Err(Error::Vdf(_))));
+        assert!(matches!(
+            parse(b"\x02int\x00\x01\x00\x08"),
+            Err(Error::Vdf(_))
+        ));
+        assert!(matches!(parse(b"\x08extra"), Err(Error::Vdf(_))));
+        match parse(b"\x09name\x00\x08") {
+            Err(Error::Vdf(message)) => assert_eq!("unknown value type 0x09 at byte 0", message),
+            _ => panic!("Expected a vdf error"),
+        }
+        match parse(b"\x01a\x00b\x00\x01\xff\x00\x00\x08") {
+            Err(Error::Vdf(message)) => assert_eq!("string is not valid UTF-8 at byte 6", message),
+            _ => panic!("Expected a vdf error"),
+        }
+        assert!(matches!(parse(b"\x09\xff\x00"), Err(Error::Vdf(_))));
+    }
+
+    #[test]
+    fn parse_nested_too_deeply_test() {
+        let mut data = Vec::new();
+        for _ in 0..=MAX_DEPTH {
+            data.extend_from_slice(b"\x00m\x00");
+        }
+        data.resize(data.len() + MAX_DEPTH + 2, END);
+        match parse(&data) {
+            Err(Error::Vdf(message)) => assert_eq!(
+                format!("maps nested too deeply at byte {}", MAX_DEPTH * 3),
+                message
+            ),
+            _ => panic!("Expected a vdf error"),
+        }
+        let allowed = &data[3..data.len() - 1];
+        assert!(parse(allowed).is_ok());
+    }
+
+    #[test]
+    fn write_null_byte_test() {
+        let entries = vec![("name".to_string(), Value::String("a\0b".to_string()))];
+        assert!(matches!(to_bytes(&entries), Err(Error::Vdf(_))));
+        let entries = vec![("na\0me".to_string(), Value::Int(1))];
+        assert!(matches!(to_bytes(&entries), Err(Error::Vdf(_))));
+    }
+}
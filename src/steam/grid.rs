@@ -0,0 +1,366 @@
+//! The folder where Steam keeps custom artwork for a user.
+
+use std::{
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use crate::{error::Error, images::Image, query_parameters::ImageKind};
+
+/// The file extensions of artwork, in the order they are preferred when several are installed
+const EXTENSIONS: [&str; 5] = ["png", "jpg", "jpeg", "webp", "ico"];
+
+/// The kinds of artwork Steam shows for a game
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ArtworkKind {
+    /// The portrait grid shown in the library, saved as `<app id>p.png`
+    GridPortrait,
+    /// The wide grid shown in recent games, saved as `<app id>.png`
+    GridWide,
+    /// The hero banner at the top of the game page, saved as `<app id>_hero.png`
+    Hero,
+    /// The logo on top of the hero, saved as `<app id>_logo.png`
+    Logo,
+    /// The icon of the game, saved as `<app id>_icon.png`
+    Icon,
+}
+
+impl ArtworkKind {
+    /// All the kinds of artwork
+    pub const ALL: [ArtworkKind; 5] = [
+        ArtworkKind::GridPortrait,
+        ArtworkKind::GridWide,
+        ArtworkKind::Hero,
+        ArtworkKind::Logo,
+        ArtworkKind::Icon,
+    ];
+
+    /// Get the kind of artwork for an image of the given kind.
+    ///
+    /// Grids taller than they are wide are portrait grids, other grids are wide grids.
+    ///
+    /// ### Examples
+    /// ```
+    /// # use steamgriddb_api::images::Image;
+    /// use steamgriddb_api::query_parameters::ImageKind;
+    /// use steamgriddb_api::steam::grid::ArtworkKind;
+    /// # fn example(image: &Image) {
+    /// let kind = ArtworkKind::for_image(&ImageKind::Grid, image);
+    /// # }
+    /// ```
+    pub fn for_image(kind: &ImageKind, image: &Image) -> Self {
+        match kind {
+            ImageKind::Grid if image.height > image.width => ArtworkKind::GridPortrait,
+            ImageKind::Grid => ArtworkKind::GridWide,
+            ImageKind::Hero => ArtworkKind::Hero,
+            ImageKind::Logo => ArtworkKind::Logo,
+            ImageKind::Icon => ArtworkKind::Icon,
+        }
+    }
+
+    /// The suffix added to the app id in the file name
+    fn suffix(&self) -> &'static str {
+        match self {
+            ArtworkKind::GridPortrait => "p",
+            ArtworkKind::GridWide => "",
+            ArtworkKind::Hero => "_hero",
+            ArtworkKind::Logo => "_logo",
+            ArtworkKind::Icon => "_icon",
+        }
+    }
+
+    /// The file name without extension, for the given app id
+    ///
+    /// ### Examples
+    /// ```
+    /// use steamgriddb_api::steam::grid::ArtworkKind;
+    /// assert_eq!("361420p", ArtworkKind::GridPortrait.file_stem(361420));
+    /// assert_eq!("361420_hero", ArtworkKind::Hero.file_stem(361420));
+    /// ```
+    pub fn file_stem(&self, app_id: u32) -> String {
+        format!("{}{}", app_id, self.suffix())
+    }
+}
+
+/// An artwork file installed in a grid folder
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct InstalledArtwork {
+    /// The app id the artwork is for
+    pub app_id: u32,
+    /// The kind of artwork
+    pub kind: ArtworkKind,
+    /// The path of the file
+    pub path: PathBuf,
+}
+
+/// The grid folder of a Steam user, `userdata/<user id>/config/grid`.
+///
+/// ### Examples
+/// ```
+/// use steamgriddb_api::steam::grid::{ArtworkKind, GridFolder};
+/// let folder = GridFolder::new("/home/me/.steam/steam/userdata/12345678");
+/// let path = folder.file_path(361420, ArtworkKind::Logo, "png");
+/// assert!(path.ends_with("config/grid/361420_logo.png"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GridFolder {
+    path: PathBuf,
+}
+
+impl GridFolder {
+    /// Get the grid folder of a user, given the userdata directory of that user, like `userdata/12345678`
+    pub fn new<P>(user_dir: P) -> Self
+    where
+        P: AsRef<Path>,
+    {
+        Self {
+            path: user_dir.as_ref().join("config").join("grid"),
+        }
+    }
+
+    /// Use the given path as the grid folder
+    pub fn from_path<P>(path: P) -> Self
+    where
+        P: Into<PathBuf>,
+    {
+        Self { path: path.into() }
+    }
+
+    /// The path of the grid folder
+    pub fn path(&self) -> &Path {
+        self.path.as_path()
+    }
+
+    /// Get the path of an artwork file with the given extension
+    pub fn file_path(&self, app_id: u32, kind: ArtworkKind, extension: &str) -> PathBuf {
+        self.path
+            .join(format!("{}.{}", kind.file_stem(app_id), extension))
+    }
+
+    /// Get the path the image should be installed at, with the extension of its mime type.
+    ///
+    /// Steam does not show webp artwork, so webp images give an [`Error::ContentType`].
+    pub fn path_for_image(
+        &self,
+        app_id: u32,
+        kind: ArtworkKind,
+        image: &Image,
+    ) -> Result<PathBuf, Error> {
+        let extension = extension_for_mime(image.mime.as_str())?;
+        Ok(self.file_path(app_id, kind, extension))
+    }
+
+    /// Create the grid folder, if it does not exist
+    pub fn create(&self) -> Result<(), Error> {
+        Ok(fs::create_dir_all(&self.path)?)
+    }
+
+    /// List all artwork installed in the folder.
+    ///
+    /// Returns an empty list if the folder does not exist.
+    pub fn installed(&self) -> Result<Vec<InstalledArtwork>, Error> {
+        let entries = match fs::read_dir(&self.path) {
+            Ok(entries) => entries,
+            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(error) => return Err(error.into()),
+        };
+        let mut installed = Vec::new();
+        for entry in entries {
+            let path = entry?.path();
+            if let Some((app_id, kind)) = parse_file_name(&path) {
+                installed.push(InstalledArtwork { app_id, kind, path });
+            }
+        }
+        installed.sort();
+        Ok(installed)
+    }
+
+    /// List the artwork installed for an app
+    pub fn installed_for(&self, app_id: u32) -> Result<Vec<InstalledArtwork>, Error> {
+        let mut installed = self.installed()?;
+        installed.retain(|artwork| artwork.app_id == app_id);
+        Ok(installed)
+    }
+
+    /// Remove the installed artwork of a kind for an app, in any extension.
+    ///
+    /// Returns the amount of removed files.
+    pub fn remove(&self, app_id: u32, kind: ArtworkKind) -> Result<usize, Error> {
+        self.remove_except(app_id, kind, None)
+    }
+
+    /// Remove the installed artwork of a kind for an app, except for the file at `keep`
+    pub(crate) fn remove_except(
+        &self,
+        app_id: u32,
+        kind: ArtworkKind,
+        keep: Option<&Path>,
+    ) -> Result<usize, Error> {
+        let mut removed = 0;
+        // The files found by listing the folder, so extensions in any case are removed
+        for artwork in self.installed_for(app_id)? {
+            if artwork.kind != kind || Some(artwork.path.as_path()) == keep {
+                continue;
+            }
+            match fs::remove_file(&artwork.path) {
+                Ok(()) => removed += 1,
+                Err(error) if error.kind() == ErrorKind::NotFound => {}
+                Err(error) => return Err(error.into()),
+            }
+        }
+        Ok(removed)
+    }
+}
+
+/// The file extension Steam expects for a mime type, or an error for webp which Steam does not show
+fn extension_for_mime(mime: &str) -> Result<&'static str, Error> {
+    match mime {
+        "image/jpeg" => Ok("jpg"),
+        "image/webp" => Err(Error::ContentType {
+            expected: "image/png or image/jpeg".to_string(),
+            actual: mime.to_string(),
+        }),
+        "image/vnd.microsoft.icon" => Ok("ico"),
+        _ => Ok("png"),
+    }
+}
+
+/// Parse an artwork file name like `361420p.png` into its app id and kind
+fn parse_file_name(path: &Path) -> Option<(u32, ArtworkKind)> {
+    let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+    if !EXTENSIONS.contains(&extension.as_str()) {
+        return None;
+    }
+    let stem = path.file_stem()?.to_str()?;
+    let kinds = [
+        ArtworkKind::Hero,
+        ArtworkKind::Logo,
+        ArtworkKind::Icon,
+        ArtworkKind::GridPortrait,
+        ArtworkKind::GridWide,
+    ];
+    kinds.iter().find_map(|kind| {
+        let app_id = stem.strip_suffix(kind.suffix())?;
+        if app_id.is_empty() || !app_id.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        Some((app_id.parse().ok()?, *kind))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn temp_folder(name: &str) -> GridFolder {
+        let path = std::env::temp_dir().join(format!(
+            "steamgriddb_api_grid_{}_{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&path);
+        let folder = GridFolder::from_path(path);
+        folder.create().unwrap();
+        folder
+    }
+
+    #[test]
+    fn parse_file_name_test() {
+        let parse = |name: &str| parse_file_name(Path::new(name));
+        assert_eq!(
+            Some((361420, ArtworkKind::GridPortrait)),
+            parse("361420p.png")
+        );
+        assert_eq!(Some((361420, ArtworkKind::GridWide)), parse("361420.jpg"));
+        assert_eq!(Some((361420, ArtworkKind::Hero)), parse("361420_hero.PNG"));
+        assert_eq!(
+            Some((3_000_000_000, ArtworkKind::Logo)),
+            parse("3000000000_logo.webp")
+        );
+        assert_eq!(Some((7, ArtworkKind::Icon)), parse("7_icon.ico"));
+        assert_eq!(None, parse("361420.json"));
+        assert_eq!(None, parse("p.png"));
+        assert_eq!(None, parse("abc_hero.png"));
+        assert_eq!(None, parse("361420_cover.png"));
+    }
+
+    #[test]
+    fn file_path_test() {
+        let folder = GridFolder::new("userdata/1234");
+        assert_eq!(
+            Path::new("userdata/1234/config/grid/10p.jpg"),
+            folder.file_path(10, ArtworkKind::GridPortrait, "jpg")
+        );
+    }
+
+    #[test]
+    fn extension_for_mime_test() {
+        assert_eq!("png", extension_for_mime("image/png").unwrap());
+        assert_eq!("jpg", extension_for_mime("image/jpeg").unwrap());
+        assert_eq!(
+            "ico",
+            extension_for_mime("image/vnd.microsoft.icon").unwrap()
+        );
+        assert!(matches!(
+            extension_for_mime("image/webp"),
+            Err(Error::ContentType { .. })
+        ));
+    }
+
+    #[test]
+    fn installed_and_remove_test() {
+        let folder = temp_folder("installed");
+        for name in ["10p.png", "10p.jpg", "10_hero.png", "20.png", "10.json"].iter() {
+            fs::write(folder.path().join(name), b"").unwrap();
+        }
+        let installed = folder.installed().unwrap();
+        assert_eq!(4, installed.len());
+        let for_app: Vec<ArtworkKind> = folder
+            .installed_for(10)
+            .unwrap()
+            .into_iter()
+            .map(|artwork| artwork.kind)
+            .collect();
+        assert_eq!(
+            vec![
+                ArtworkKind::GridPortrait,
+                ArtworkKind::GridPortrait,
+                ArtworkKind::Hero
+            ],
+            for_app
+        );
+
+        assert_eq!(2, folder.remove(10, ArtworkKind::GridPortrait).unwrap());
+        assert_eq!(1, folder.installed_for(10).unwrap().len());
+        assert!(folder.path().join("10.json").exists());
+        fs::remove_dir_all(folder.path()).unwrap();
+    }
+
+    #[test]
+    fn installed_in_missing_folder_test() {
+        let folder = GridFolder::from_path("testdata/missing/grid");
+        assert!(folder.installed().unwrap().is_empty());
+    }
+
+    #[test]
+    fn remove_with_uppercase_extension_test() {
+        let folder = temp_folder("uppercase");
+        for name in ["10p.PNG", "10p.Jpg", "10_hero.JPG"].iter() {
+            fs::write(folder.path().join(name), b"").unwrap();
+        }
+        let keep = folder.path().join("10p.Jpg");
+        assert_eq!(
+            1,
+            folder
+                .remove_except(10, ArtworkKind::GridPortrait, Some(&keep))
+                .unwrap()
+        );
+        assert!(!folder.path().join("10p.PNG").exists());
+        assert!(keep.exists());
+        assert_eq!(1, folder.remove(10, ArtworkKind::Hero).unwrap());
+        assert_eq!(1, folder.installed().unwrap().len());
+        fs::remove_dir_all(folder.path()).unwrap();
+    }
+}
@@ -0,0 +1,242 @@
+//! Finding the games installed in a Steam installation.
+
+use std::{
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    error::Error,
+    steam::text_vdf::{self, Value},
+};
+
+/// A game installed by Steam, as described by its `appmanifest_<app id>.acf` file
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct InstalledGame {
+    /// The Steam app id
+    pub app_id: u32,
+    /// The name of the game
+    pub name: String,
+    /// The directory the game is installed in
+    pub install_dir: PathBuf,
+    /// The size of the installed game in bytes
+    pub size_on_disk: u64,
+    /// The library folder the game is installed in
+    pub library_folder: PathBuf,
+}
+
+/// A Steam installation, and the library folders it knows about.
+///
+/// ### Examples
+/// ```no_run
+/// use steamgriddb_api::steam::library::SteamLibrary;
+///
+/// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let library = SteamLibrary::new("/home/me/.steam/steam");
+/// for game in library.games()? {
+///     println!("{} {}", game.app_id, game.name);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SteamLibrary {
+    root: PathBuf,
+}
+
+impl SteamLibrary {
+    /// Use the Steam installation at the given root directory, the directory containing `steamapps`
+    pub fn new<P>(root: P) -> Self
+    where
+        P: Into<PathBuf>,
+    {
+        Self { root: root.into() }
+    }
+
+    /// The root directory of the Steam installation
+    pub fn root(&self) -> &Path {
+        self.root.as_path()
+    }
+
+    /// List the library folders, starting with the root.
+    ///
+    /// The folders are read from `steamapps/libraryfolders.vdf`, in both the current and the old format.
+    /// Folders that are listed but do not exist, like those on unplugged drives, are left out.
+    pub fn library_folders(&self) -> Result<Vec<PathBuf>, Error> {
+        let mut folders = vec![self.root.clone()];
+        let path = self.root.join("steamapps").join("libraryfolders.vdf");
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(folders),
+            Err(error) => return Err(error.into()),
+        };
+        let entries = text_vdf::parse(&text)?;
+        let listed = entries
+            .iter()
+            .filter_map(|(_, value)| value.as_map())
+            .flatten()
+            .filter(|(key, _)| key.bytes().all(|b| b.is_ascii_digit()))
+            .filter_map(|(_, folder)| match folder {
+                // The current format has a map for each folder, the old format only the path
+                Value::Map(_) => folder.get("path").and_then(Value::as_str),
+                Value::String(path) => Some(path.as_str()),
+            });
+        for folder in listed {
+            let folder = PathBuf::from(folder);
+            if folder.is_dir() && !folders.iter().any(|known| same_dir(known, &folder)) {
+                folders.push(folder);
+            }
+        }
+        Ok(folders)
+    }
+
+    /// List the games installed in all library folders, sorted by app id.
+    ///
+    /// Manifests that can not be read or parsed, like those of games being installed, are left out.
+    pub fn games(&self) -> Result<Vec<InstalledGame>, Error> {
+        let mut games = Vec::new();
+        for folder in self.library_folders()? {
+            games.extend(games_in_folder(&folder)?);
+        }
+        games.sort();
+        Ok(games)
+    }
+
+    /// Find an installed game by its app id
+    pub fn game(&self, app_id: u32) -> Result<Option<InstalledGame>, Error> {
+        Ok(self.games()?.into_iter().find(|game| game.app_id == app_id))
+    }
+}
+
+/// Read the manifests in the `steamapps` directory of a library folder
+fn games_in_folder(folder: &Path) -> Result<Vec<InstalledGame>, Error> {
+    let steamapps = folder.join("steamapps");
+    let entries = match fs::read_dir(&steamapps) {
+        Ok(entries) => entries,
+        Err(error) if error.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(error) => return Err(error.into()),
+    };
+    let mut games = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        let is_manifest = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .map(|name| name.starts_with("appmanifest_") && name.ends_with(".acf"))
+            .unwrap_or(false);
+        if !is_manifest {
+            continue;
+        }
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            // Like a manifest that can not be parsed, one that can not be read does not stop the scan
+            Err(_) => continue,
+        };
+        if let Some(game) = parse_manifest(&text, folder) {
+            games.push(game);
+        }
+    }
+    Ok(games)
+}
+
+/// Parse an `appmanifest_<app id>.acf` file of a game in the library folder
+fn parse_manifest(text: &str, library_folder: &Path) -> Option<InstalledGame> {
+    let entries = text_vdf::parse(text).ok()?;
+    let state = text_vdf::get(&entries, "AppState")?;
+    let field = |key: &str| state.get(key).and_then(Value::as_str);
+    let install_dir = field("installdir")?;
+    Some(InstalledGame {
+        app_id: field("appid")?.parse().ok()?,
+        name: field("name")?.to_string(),
+        install_dir: library_folder
+            .join("steamapps")
+            .join("common")
+            .join(install_dir),
+        size_on_disk: field("SizeOnDisk")
+            .and_then(|size| size.parse().ok())
+            .unwrap_or_default(),
+        library_folder: library_folder.to_path_buf(),
+    })
+}
+
+fn same_dir(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn library_folders_test() {
+        let library = SteamLibrary::new("testdata/steam/root");
+        let folders = library.library_folders().unwrap();
+        assert_eq!(
+            vec![
+                PathBuf::from("testdata/steam/root"),
+                PathBuf::from("testdata/steam/second_library")
+            ],
+            folders
+        );
+    }
+
+    #[test]
+    fn library_folders_old_format_test() {
+        let library = SteamLibrary::new("testdata/steam/old_root");
+        let folders = library.library_folders().unwrap();
+        assert_eq!(
+            vec![
+                PathBuf::from("testdata/steam/old_root"),
+                PathBuf::from("testdata/steam/second_library")
+            ],
+            folders
+        );
+    }
+
+    #[test]
+    fn games_test() {
+        let library = SteamLibrary::new("testdata/steam/root");
+        let games = library.games().unwrap();
+        let ids: Vec<u32> = games.iter().map(|game| game.app_id).collect();
+        assert_eq!(vec![228980, 361420, 504230], ids);
+
+        let astroneer = &games[1];
+        assert_eq!("ASTRONEER", astroneer.name);
+        assert_eq!(
+            Path::new("testdata/steam/root/steamapps/common/Astroneer"),
+            astroneer.install_dir
+        );
+        assert_eq!(5_036_519_538, astroneer.size_on_disk);
+        assert_eq!(Path::new("testdata/steam/root"), astroneer.library_folder);
+
+        let redistributables = library.game(228980).unwrap().unwrap();
+        assert_eq!(
+            Path::new("testdata/steam/second_library"),
+            redistributables.library_folder
+        );
+        assert_eq!(None, library.game(1).unwrap());
+    }
+
+    #[test]
+    fn games_with_non_utf8_manifest_test() {
+        let folder = Path::new("testdata/steam/second_library");
+        assert!(fs::read_to_string(folder.join("steamapps/appmanifest_2.acf")).is_err());
+        let ids: Vec<u32> = games_in_folder(folder)
+            .unwrap()
+            .iter()
+            .map(|game| game.app_id)
+            .collect();
+        assert_eq!(vec![228980], ids);
+    }
+
+    #[test]
+    fn games_without_steamapps_test() {
+        let library = SteamLibrary::new("testdata/steam/missing");
+        assert!(library.games().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,11 @@
+//! Helpers for applying artwork to a local Steam installation.
+//!
+//! [`grid::GridFolder`] knows how Steam names the custom artwork in `userdata/<user id>/config/grid`,
+//! [`shortcut`] reads and writes the non-Steam shortcuts of a user, and computes their app ids,
+//! and [`library::SteamLibrary`] finds the games installed by Steam.
+
+pub mod binary_vdf;
+pub mod grid;
+pub mod library;
+pub mod shortcut;
+pub mod text_vdf;
@@ -0,0 +1,458 @@
+//! Non-Steam shortcuts: reading and writing `shortcuts.vdf`, and the app ids Steam gives them.
+//!
+//! Steam derives the id of a shortcut from the CRC-32 of its exe and app name.
+//! The 32-bit app id is used for the artwork in the grid folder, see [`crate::steam::grid::GridFolder`],
+//! and the 64-bit id is used by older Steam clients and for `steam://rungameid/` links.
+
+use std::path::{Path, PathBuf};
+
+use crate::{
+    crc32::crc32,
+    error::Error,
+    files::write_atomically,
+    steam::binary_vdf::{self, Entry, Value},
+};
+
+/// The bit Steam sets in the app ids of shortcuts, so they never clash with the ids of Steam games
+const SHORTCUT_BIT: u32 = 0x8000_0000;
+
+/// The low bits of the 64-bit ids of shortcuts
+const LEGACY_ID_TYPE: u64 = 0x0200_0000;
+
+/// Get the 32-bit app id of a shortcut, as used for the artwork in the grid folder.
+///
+/// The exe has to be exactly as Steam stores it in `shortcuts.vdf`, which usually includes surrounding quotes.
+/// The id is stored as a signed 32-bit number in `shortcuts.vdf`.
+///
+/// ### Examples
+/// ```
+/// use steamgriddb_api::steam::shortcut::shortcut_app_id;
+/// let app_id = shortcut_app_id("\"C:\\Games\\Celeste\\Celeste.exe\"", "Celeste");
+/// assert_eq!(2885808326, app_id);
+/// ```
+pub fn shortcut_app_id(exe: &str, app_name: &str) -> u32 {
+    let mut key = String::with_capacity(exe.len() + app_name.len());
+    key.push_str(exe);
+    key.push_str(app_name);
+    crc32(key.as_bytes()) | SHORTCUT_BIT
+}
+
+/// Get the 64-bit id of a shortcut, as used by older Steam clients and in `steam://rungameid/` links.
+///
+/// ### Examples
+/// ```
+/// use steamgriddb_api::steam::shortcut::shortcut_legacy_id;
+/// let id = shortcut_legacy_id("\"C:\\Games\\Celeste\\Celeste.exe\"", "Celeste");
+/// assert_eq!(12394452382728060928, id);
+/// ```
+pub fn shortcut_legacy_id(exe: &str, app_name: &str) -> u64 {
+    legacy_id_from_app_id(shortcut_app_id(exe, app_name))
+}
+
+/// Get the 64-bit id of a shortcut from its 32-bit app id
+pub fn legacy_id_from_app_id(app_id: u32) -> u64 {
+    (u64::from(app_id) << 32) | LEGACY_ID_TYPE
+}
+
+/// Get the 32-bit app id of a shortcut from its 64-bit id
+pub fn app_id_from_legacy_id(legacy_id: u64) -> u32 {
+    (legacy_id >> 32) as u32
+}
+
+/// A non-Steam shortcut in `shortcuts.vdf`
+///
+/// ### Examples
+/// ```
+/// use steamgriddb_api::steam::shortcut::Shortcut;
+/// let mut shortcut = Shortcut::new("Celeste", "\"C:\\Games\\Celeste\\Celeste.exe\"", "\"C:\\Games\\Celeste\\\"");
+/// shortcut.icon = "C:\\icons\\celeste.png".to_string();
+/// assert_eq!(Some(2885808326), shortcut.app_id);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Shortcut {
+    /// The 32-bit app id, missing in files written by old Steam clients
+    pub app_id: Option<u32>,
+    /// The name shown in the library
+    pub app_name: String,
+    /// The command to run, usually with quotes around the path
+    pub exe: String,
+    /// The directory the command is started in
+    pub start_dir: String,
+    /// The path of the icon
+    pub icon: String,
+    /// The path of the desktop shortcut the shortcut was made from
+    pub shortcut_path: String,
+    /// The arguments given to the command
+    pub launch_options: String,
+    /// The collections the shortcut is in
+    pub tags: Vec<String>,
+    /// The other fields of the shortcut, like IsHidden and LastPlayTime, in the order they appear in the file
+    pub other: Vec<Entry>,
+    /// The names of the fields in the file, so they are written back with the same case and order
+    names: Vec<String>,
+}
+
+// The names only decide how the shortcut is written, so they are left out of comparisons
+impl PartialEq for Shortcut {
+    fn eq(&self, other: &Self) -> bool {
+        self.app_id == other.app_id
+            && self.app_name == other.app_name
+            && self.exe == other.exe
+            && self.start_dir == other.start_dir
+            && self.icon == other.icon
+            && self.shortcut_path == other.shortcut_path
+            && self.launch_options == other.launch_options
+            && self.tags == other.tags
+            && self.other == other.other
+    }
+}
+
+impl Shortcut {
+    /// Create a shortcut, with the app id Steam would give it
+    pub fn new<N, E, D>(app_name: N, exe: E, start_dir: D) -> Self
+    where
+        N: Into<String>,
+        E: Into<String>,
+        D: Into<String>,
+    {
+        let app_name = app_name.into();
+        let exe = exe.into();
+        Self {
+            app_id: Some(shortcut_app_id(&exe, &app_name)),
+            app_name,
+            exe,
+            start_dir: start_dir.into(),
+            ..Default::default()
+        }
+    }
+
+    /// The app id used for the artwork of the shortcut.
+    ///
+    /// This is the stored app id, or the id Steam would compute when it is missing.
+    pub fn grid_app_id(&self) -> u32 {
+        self.app_id
+            .unwrap_or_else(|| shortcut_app_id(&self.exe, &self.app_name))
+    }
+
+    fn from_entries(entries: &[Entry]) -> Self {
+        let mut shortcut = Shortcut {
+            names: entries.iter().map(|(name, _)| name.clone()).collect(),
+            ..Default::default()
+        };
+        for (name, value) in entries {
+            let string = || value.as_str().unwrap_or_default().to_string();
+            match (name.to_ascii_lowercase().as_str(), value) {
+                ("appid", Value::Int(app_id)) => shortcut.app_id = Some(*app_id as u32),
+                ("appname", _) => shortcut.app_name = string(),
+                ("exe", _) => shortcut.exe = string(),
+                ("startdir", _) => shortcut.start_dir = string(),
+                ("icon", _) => shortcut.icon = string(),
+                ("shortcutpath", _) => shortcut.shortcut_path = string(),
+                ("launchoptions", _) => shortcut.launch_options = string(),
+                ("tags", Value::Map(tags)) => {
+                    shortcut.tags = tags
+                        .iter()
+                        .filter_map(|(_, tag)| tag.as_str())
+                        .map(|tag| tag.to_string())
+                        .collect()
+                }
+                _ => shortcut.other.push((name.clone(), value.clone())),
+            }
+        }
+        shortcut
+    }
+
+    /// The value of a field this struct has a member for, by its lowercase name.
+    ///
+    /// Returns None for other fields, and Some(None) for a missing app id.
+    fn known_value(&self, name: &str) -> Option<Option<Value>> {
+        let string = |value: &String| Some(Value::String(value.clone()));
+        Some(match name {
+            "appid" => self.app_id.map(|app_id| Value::Int(app_id as i32)),
+            "appname" => string(&self.app_name),
+            "exe" => string(&self.exe),
+            "startdir" => string(&self.start_dir),
+            "icon" => string(&self.icon),
+            "shortcutpath" => string(&self.shortcut_path),
+            "launchoptions" => string(&self.launch_options),
+            "tags" => {
+                let tags = self
+                    .tags
+                    .iter()
+                    .enumerate()
+                    .map(|(index, tag)| (index.to_string(), Value::String(tag.clone())))
+                    .collect();
+                Some(Value::Map(tags))
+            }
+            _ => return None,
+        })
+    }
+
+    /// The fields to write, in the order and with the names they were read with.
+    ///
+    /// Fields that were not in the file are added with the names the Steam client uses,
+    /// before the other fields and the tags.
+    fn to_entries(&self) -> Vec<Entry> {
+        let mut entries = Vec::with_capacity(8 + self.other.len());
+        let mut other: Vec<&Entry> = self.other.iter().collect();
+        let mut written = Vec::new();
+        for name in self.names.iter() {
+            if let Some(index) = other.iter().position(|(other_name, _)| other_name == name) {
+                entries.push(other.remove(index).clone());
+                continue;
+            }
+            let lowercase = name.to_ascii_lowercase();
+            if written.contains(&lowercase) {
+                continue;
+            }
+            if let Some(Some(value)) = self.known_value(&lowercase) {
+                entries.push((name.clone(), value));
+            }
+            written.push(lowercase);
+        }
+        let added = [
+            "appid",
+            "AppName",
+            "Exe",
+            "StartDir",
+            "icon",
+            "ShortcutPath",
+            "LaunchOptions",
+        ];
+        for name in added.iter() {
+            let lowercase = name.to_ascii_lowercase();
+            if written.contains(&lowercase) {
+                continue;
+            }
+            if let Some(Some(value)) = self.known_value(&lowercase) {
+                entries.push((name.to_string(), value));
+            }
+        }
+        entries.extend(other.into_iter().cloned());
+        if !written.iter().any(|name| name == "tags") {
+            entries.extend(
+                self.known_value("tags")
+                    .flatten()
+                    .map(|tags| ("tags".to_string(), tags)),
+            );
+        }
+        entries
+    }
+}
+
+/// The shortcuts of a Steam user, as stored in `userdata/<user id>/config/shortcuts.vdf`
+///
+/// ### Examples
+/// ```no_run
+/// use steamgriddb_api::steam::shortcut::{shortcuts_path, Shortcuts};
+///
+/// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let path = shortcuts_path("/home/me/.steam/steam/userdata/12345678");
+/// let mut shortcuts = Shortcuts::read(&path)?;
+/// for shortcut in shortcuts.shortcuts.iter_mut() {
+///     shortcut.icon = format!("/home/me/icons/{}.png", shortcut.grid_app_id());
+/// }
+/// shortcuts.write(&path)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Shortcuts {
+    /// The shortcuts, in the order they appear in the file
+    pub shortcuts: Vec<Shortcut>,
+}
+
+impl Shortcuts {
+    /// Parse the content of a `shortcuts.vdf` file
+    pub fn parse(data: &[u8]) -> Result<Self, Error> {
+        let entries = binary_vdf::parse(data)?;
+        let list = entries
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case("shortcuts"))
+            .and_then(|(_, value)| value.as_map())
+            .ok_or_else(|| Error::Vdf("missing shortcuts".to_string()))?;
+        let shortcuts = list
+            .iter()
+            .filter_map(|(_, value)| value.as_map())
+            .map(Shortcut::from_entries)
+            .collect();
+        Ok(Self { shortcuts })
+    }
+
+    /// Read a `shortcuts.vdf` file
+    pub fn read<P>(path: P) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+    {
+        Self::parse(&std::fs::read(path)?)
+    }
+
+    /// Get the content of a `shortcuts.vdf` file with these shortcuts.
+    ///
+    /// Fails with [`Error::Vdf`] if a name or string contains a null byte, which the format can not store.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let list = self
+            .shortcuts
+            .iter()
+            .enumerate()
+            .map(|(index, shortcut)| (index.to_string(), Value::Map(shortcut.to_entries())))
+            .collect();
+        binary_vdf::to_bytes(&[("shortcuts".to_string(), Value::Map(list))])
+    }
+
+    /// Write the shortcuts to a `shortcuts.vdf` file, replacing it atomically
+    pub fn write<P>(&self, path: P) -> Result<(), Error>
+    where
+        P: AsRef<Path>,
+    {
+        write_atomically(path.as_ref(), &self.to_bytes()?)
+    }
+
+    /// Find a shortcut by the app id used for its artwork
+    pub fn find_by_app_id(&self, app_id: u32) -> Option<&Shortcut> {
+        self.shortcuts
+            .iter()
+            .find(|shortcut| shortcut.grid_app_id() == app_id)
+    }
+}
+
+/// Get the path of the `shortcuts.vdf` file, given the userdata directory of a user, like `userdata/12345678`
+pub fn shortcuts_path<P>(user_dir: P) -> PathBuf
+where
+    P: AsRef<Path>,
+{
+    user_dir.as_ref().join("config").join("shortcuts.vdf")
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn shortcut_app_id_test() {
+        assert_eq!(
+            0xAC01_F0C6,
+            shortcut_app_id("\"C:\\Games\\Celeste\\Celeste.exe\"", "Celeste")
+        );
+        assert_eq!(
+            0xED86_A348,
+            shortcut_app_id("\"/usr/bin/retroarch\"", "RetroArch")
+        );
+        assert_eq!(0x8000_0000, shortcut_app_id("", ""));
+        // CRC-32 of "123456789" is 0xCBF43926, which already has the shortcut bit set
+        assert_eq!(0xCBF4_3926, shortcut_app_id("12345", "6789"));
+    }
+
+    #[test]
+    fn shortcut_legacy_id_test() {
+        assert_eq!(
+            17_115_546_963_534_675_968,
+            shortcut_legacy_id("\"/usr/bin/retroarch\"", "RetroArch")
+        );
+        assert_eq!(0x8000_0000_0200_0000, shortcut_legacy_id("", ""));
+    }
+
+    #[test]
+    fn legacy_id_round_trip_test() {
+        let app_id = shortcut_app_id("\"/usr/bin/retroarch\"", "RetroArch");
+        assert_eq!(app_id, app_id_from_legacy_id(legacy_id_from_app_id(app_id)));
+    }
+
+    #[test]
+    fn parse_shortcuts_test() {
+        let shortcuts = Shortcuts::read("testdata/steam/shortcuts.vdf").unwrap();
+        assert_eq!(2, shortcuts.shortcuts.len());
+        let celeste = &shortcuts.shortcuts[0];
+        assert_eq!(Some(0xAC01_F0C6), celeste.app_id);
+        assert_eq!("Celeste", celeste.app_name);
+        assert_eq!("\"C:\\Games\\Celeste\\Celeste.exe\"", celeste.exe);
+        assert_eq!("\"C:\\Games\\Celeste\\\"", celeste.start_dir);
+        assert_eq!("", celeste.icon);
+        assert_eq!(vec!["Platformer", "Favorites"], celeste.tags);
+        assert!(celeste
+            .other
+            .contains(&("LastPlayTime".to_string(), Value::Int(1_700_000_000))));
+        assert_eq!(
+            shortcut_app_id(&celeste.exe, &celeste.app_name),
+            celeste.grid_app_id()
+        );
+
+        let retroarch = shortcuts.find_by_app_id(0xBB34_AF52).unwrap();
+        assert_eq!("RetroArch", retroarch.app_name);
+        assert_eq!("/home/me/icons/retroarch.png", retroarch.icon);
+        assert!(retroarch.tags.is_empty());
+    }
+
+    #[test]
+    fn shortcuts_round_trip_test() {
+        let data = std::fs::read("testdata/steam/shortcuts.vdf").unwrap();
+        let shortcuts = Shortcuts::parse(&data).unwrap();
+        assert_eq!(data, shortcuts.to_bytes().unwrap());
+    }
+
+    #[test]
+    fn set_icon_test() {
+        let mut shortcuts = Shortcuts::read("testdata/steam/shortcuts.vdf").unwrap();
+        shortcuts.shortcuts[0].icon = "/home/me/grid/2885808326_icon.png".to_string();
+        shortcuts.shortcuts.push(Shortcut::new(
+            "Added",
+            "\"/usr/bin/added\"",
+            "\"/usr/bin/\"",
+        ));
+        let reread = Shortcuts::parse(&shortcuts.to_bytes().unwrap()).unwrap();
+        assert_eq!(shortcuts, reread);
+        assert_eq!(
+            "/home/me/grid/2885808326_icon.png",
+            reread.shortcuts[0].icon
+        );
+    }
+
+    #[test]
+    fn parse_missing_shortcuts_test() {
+        let result = Shortcuts::parse(b"\x00other\x00\x08\x08");
+        assert!(matches!(result, Err(Error::Vdf(_))));
+    }
+
+    #[test]
+    fn old_client_round_trip_test() {
+        let data = std::fs::read("testdata/steam/shortcuts_old.vdf").unwrap();
+        let shortcuts = Shortcuts::parse(&data).unwrap();
+        let celeste = &shortcuts.shortcuts[0];
+        assert_eq!(None, celeste.app_id);
+        assert_eq!("Celeste", celeste.app_name);
+        assert_eq!(0xAC01_F0C6, celeste.grid_app_id());
+        assert_eq!(data, shortcuts.to_bytes().unwrap());
+    }
+
+    #[test]
+    fn set_icon_keeps_names_test() {
+        let mut shortcuts = Shortcuts::read("testdata/steam/shortcuts_old.vdf").unwrap();
+        shortcuts.shortcuts[1].icon = "/home/me/grid/3989098322_icon.png".to_string();
+        let entries = shortcuts.shortcuts[1].to_entries();
+        let names: Vec<&str> = entries.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(
+            vec![
+                "appname",
+                "exe",
+                "StartDir",
+                "icon",
+                "ShortcutPath",
+                "LaunchOptions",
+                "IsHidden",
+                "AllowDesktopConfig",
+                "OpenVR",
+                "LastPlayTime",
+                "tags"
+            ],
+            names
+        );
+        assert_eq!(
+            (
+                "icon".to_string(),
+                Value::String("/home/me/grid/3989098322_icon.png".to_string())
+            ),
+            entries[3]
+        );
+    }
+}
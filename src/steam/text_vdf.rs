@@ -0,0 +1,263 @@
+//! Parsing the text VDF (KeyValues) format Steam uses for files like `libraryfolders.vdf` and `appmanifest_*.acf`.
+//!
+//! A text VDF file is a list of keys, where each key has a string value or a map of more keys in braces.
+//! Keys and values can be quoted, and `//` starts a comment.
+
+use crate::error::Error;
+
+/// A named value in a text VDF file
+pub type Entry = (String, Value);
+
+/// A value in a text VDF file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    /// A string
+    String(String),
+    /// A map of named values, in the order they appear in the file
+    Map(Vec<Entry>),
+}
+
+impl Value {
+    /// Get the string, if this is a string
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(value) => Some(value),
+            Value::Map(_) => None,
+        }
+    }
+
+    /// Get the entries, if this is a map
+    pub fn as_map(&self) -> Option<&[Entry]> {
+        match self {
+            Value::Map(entries) => Some(entries),
+            Value::String(_) => None,
+        }
+    }
+
+    /// Get the value of the first key with the given name, ignoring case, if this is a map
+    ///
+    /// ### Examples
+    /// ```
+    /// use steamgriddb_api::steam::text_vdf::parse;
+    /// let entries = parse("\"AppState\" { \"appid\" \"361420\" }").unwrap();
+    /// let app_id = entries[0].1.get("AppID").and_then(|value| value.as_str());
+    /// assert_eq!(Some("361420"), app_id);
+    /// ```
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        get(self.as_map()?, key)
+    }
+}
+
+/// Get the value of the first key with the given name, ignoring case
+pub fn get<'a>(entries: &'a [Entry], key: &str) -> Option<&'a Value> {
+    entries
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(key))
+        .map(|(_, value)| value)
+}
+
+/// Parse the entries of a text VDF file.
+///
+/// ### Examples
+/// ```
+/// use steamgriddb_api::steam::text_vdf::{parse, Value};
+/// let entries = parse("\"libraryfolders\" { \"0\" { \"path\" \"C:\\\\Steam\" } }").unwrap();
+/// let path = entries[0].1.get("0").and_then(|folder| folder.get("path"));
+/// assert_eq!(Some(&Value::String("C:\\Steam".to_string())), path);
+/// ```
+pub fn parse(text: &str) -> Result<Vec<Entry>, Error> {
+    let mut parser = Parser {
+        chars: text.chars().peekable(),
+        line: 1,
+    };
+    parser.parse_map(false)
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Open,
+    Close,
+    String(String),
+    /// A condition like `[$WIN32]`, which is ignored
+    Condition,
+}
+
+struct Parser<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+    line: usize,
+}
+
+impl Parser<'_> {
+    fn error(&self, message: &str) -> Error {
+        Error::Vdf(format!("{} on line {}", message, self.line))
+    }
+
+    fn next_char(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+        if c == '\n' {
+            self.line += 1;
+        }
+        Some(c)
+    }
+
+    /// Skip whitespace and comments
+    fn skip_ignored(&mut self) {
+        while let Some(c) = self.chars.peek().copied() {
+            if c.is_whitespace() {
+                self.next_char();
+            } else if c == '/' {
+                let mut lookahead = self.chars.clone();
+                lookahead.next();
+                if lookahead.peek() != Some(&'/') {
+                    return;
+                }
+                while let Some(c) = self.next_char() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            } else {
+                return;
+            }
+        }
+    }
+
+    fn next_token(&mut self) -> Result<Option<Token>, Error> {
+        self.skip_ignored();
+        let c = match self.chars.peek().copied() {
+            Some(c) => c,
+            None => return Ok(None),
+        };
+        let token = match c {
+            '{' => {
+                self.next_char();
+                Token::Open
+            }
+            '}' => {
+                self.next_char();
+                Token::Close
+            }
+            '"' => {
+                self.next_char();
+                Token::String(self.read_quoted()?)
+            }
+            '[' => {
+                while let Some(c) = self.next_char() {
+                    if c == ']' {
+                        break;
+                    }
+                }
+                Token::Condition
+            }
+            _ => Token::String(self.read_unquoted()),
+        };
+        Ok(Some(token))
+    }
+
+    fn read_quoted(&mut self) -> Result<String, Error> {
+        let mut value = String::new();
+        loop {
+            match self.next_char() {
+                Some('"') => return Ok(value),
+                Some('\\') => match self.next_char() {
+                    Some('n') => value.push('\n'),
+                    Some('t') => value.push('\t'),
+                    Some(c) => value.push(c),
+                    None => return Err(self.error("unterminated string")),
+                },
+                Some(c) => value.push(c),
+                None => return Err(self.error("unterminated string")),
+            }
+        }
+    }
+
+    fn read_unquoted(&mut self) -> String {
+        let mut value = String::new();
+        while let Some(c) = self.chars.peek().copied() {
+            if c.is_whitespace() || c == '{' || c == '}' || c == '"' {
+                break;
+            }
+            value.push(c);
+            self.next_char();
+        }
+        value
+    }
+
+    /// Parse entries until the end of the map, or the end of the file at the top level
+    fn parse_map(&mut self, nested: bool) -> Result<Vec<Entry>, Error> {
+        let mut entries = Vec::new();
+        loop {
+            let key = match self.next_token()? {
+                Some(Token::String(key)) => key,
+                Some(Token::Close) if nested => return Ok(entries),
+                Some(Token::Condition) => continue,
+                None if !nested => return Ok(entries),
+                None => return Err(self.error("missing closing brace")),
+                Some(_) => return Err(self.error("expected a key")),
+            };
+            let value = match self.next_token()? {
+                Some(Token::String(value)) => Value::String(value),
+                Some(Token::Open) => Value::Map(self.parse_map(true)?),
+                _ => return Err(self.error(&format!("expected a value for {}", key))),
+            };
+            entries.push((key, value));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn string(value: &str) -> Value {
+        Value::String(value.to_string())
+    }
+
+    #[test]
+    fn parse_test() {
+        let text = r#"
+// A comment
+"AppState"
+{
+    "appid"     "361420"
+    "name"      "ASTRONEER"   [$WIN32]
+    unquoted    value
+    "escaped"   "C:\\Games\\\"Quoted\""
+    "UserConfig"
+    {
+        "language"  "english"
+    }
+}
+"#;
+        let entries = parse(text).unwrap();
+        let expected = vec![(
+            "AppState".to_string(),
+            Value::Map(vec![
+                ("appid".to_string(), string("361420")),
+                ("name".to_string(), string("ASTRONEER")),
+                ("unquoted".to_string(), string("value")),
+                ("escaped".to_string(), string("C:\\Games\\\"Quoted\"")),
+                (
+                    "UserConfig".to_string(),
+                    Value::Map(vec![("language".to_string(), string("english"))]),
+                ),
+            ]),
+        )];
+        assert_eq!(expected, entries);
+        let language = entries[0].1.get("userconfig").unwrap().get("LANGUAGE");
+        assert_eq!(Some(&string("english")), language);
+    }
+
+    #[test]
+    fn parse_errors_test() {
+        let error = |text: &str| match parse(text) {
+            Err(Error::Vdf(message)) => message,
+            _ => panic!("Expected a vdf error for {}", text),
+        };
+        assert_eq!("missing closing brace on line 2", error("\"a\" {\n"));
+        assert_eq!("unterminated string on line 1", error("\"a\" \"b"));
+        assert_eq!("expected a value for a on line 1", error("\"a\""));
+        assert_eq!("expected a key on line 1", error("}"));
+    }
+}
@@ -1,5 +1,179 @@
 use serde::{Deserialize, Serialize};
 
+use crate::{
+    error::{body_snippet, Error},
+    query_parameters::{
+        GridDimentions, GridQueryParameters, HeroDimentions, HeroQueryParameters, QueryType,
+    },
+    transport::HttpResponse,
+};
+
+/// The dimensions of SteamGridDB grids shaped like Steam's wide capsules
+const WIDE_GRIDS: &[GridDimentions] = &[GridDimentions::D460x215, GridDimentions::D920x430];
+
+/// The dimensions of SteamGridDB grids shaped like Steam's portrait library capsules
+const PORTRAIT_GRIDS: &[GridDimentions] = &[
+    GridDimentions::D600x900,
+    GridDimentions::D342x482,
+    GridDimentions::D660x930,
+];
+
+/// The dimensions of SteamGridDB heroes shaped like Steam's library heroes
+const LIBRARY_HEROES: &[HeroDimentions] = &[HeroDimentions::D1920x620, HeroDimentions::D3840x1240];
+
+/// The CDN hosts serving Steam's static images
+#[derive(Serialize, Deserialize, Debug, Clone, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SteamCdn {
+    /// cdn.cloudflare.steamstatic.com, the default
+    #[default]
+    Cloudflare,
+    /// cdn.akamai.steamstatic.com
+    Akamai,
+    /// steamcdn-a.akamaihd.net, the host used by older Steam clients
+    LegacyAkamai,
+    /// Another host, given as the url that the app ids are appended to, like `https://cdn.cloudflare.steamstatic.com/steam/apps`
+    Custom(String),
+}
+
+impl SteamCdn {
+    /// The url that the app ids are appended to
+    ///
+    /// ### Examples
+    /// ```
+    /// use steamgriddb_api::steam_static::SteamCdn;
+    /// assert_eq!("https://cdn.akamai.steamstatic.com/steam/apps", SteamCdn::Akamai.base_url());
+    /// ```
+    pub fn base_url(&self) -> &str {
+        match self {
+            SteamCdn::Cloudflare => "https://cdn.cloudflare.steamstatic.com/steam/apps",
+            SteamCdn::Akamai => "https://cdn.akamai.steamstatic.com/steam/apps",
+            SteamCdn::LegacyAkamai => "https://steamcdn-a.akamaihd.net/steam/apps",
+            SteamCdn::Custom(base_url) => base_url.trim_end_matches('/'),
+        }
+    }
+}
+
+/// The official images Steam publishes for a game
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SteamAsset {
+    /// The store header, 460x215
+    Header,
+    /// The library capsule in high resolution, 600x900 at 2x
+    Capsule,
+    /// The library capsule, 600x900
+    LibraryCapsule,
+    /// The small store capsule, 231x87
+    SmallCapsule,
+    /// The main store capsule, 616x353
+    MainCapsule,
+    /// The store hero capsule, 374x448
+    HeroCapsule,
+    /// The library hero, 1920x620
+    Hero,
+    /// The blurred library hero, shown while the hero loads
+    HeroBlur,
+    /// The library logo
+    Logo,
+    /// The library logo in high resolution
+    Logo2x,
+    /// The background of the store page
+    PageBackground,
+    /// The left panel shown next to broadcasts
+    BroadcastLeftPanel,
+    /// The right panel shown next to broadcasts
+    BroadcastRightPanel,
+}
+
+impl SteamAsset {
+    /// All the assets
+    pub const ALL: [SteamAsset; 13] = [
+        SteamAsset::Header,
+        SteamAsset::Capsule,
+        SteamAsset::LibraryCapsule,
+        SteamAsset::SmallCapsule,
+        SteamAsset::MainCapsule,
+        SteamAsset::HeroCapsule,
+        SteamAsset::Hero,
+        SteamAsset::HeroBlur,
+        SteamAsset::Logo,
+        SteamAsset::Logo2x,
+        SteamAsset::PageBackground,
+        SteamAsset::BroadcastLeftPanel,
+        SteamAsset::BroadcastRightPanel,
+    ];
+
+    /// The file name of the asset on the CDN
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            SteamAsset::Header => "header.jpg",
+            SteamAsset::Capsule => "library_600x900_2x.jpg",
+            SteamAsset::LibraryCapsule => "library_600x900.jpg",
+            SteamAsset::SmallCapsule => "capsule_231x87.jpg",
+            SteamAsset::MainCapsule => "capsule_616x353.jpg",
+            SteamAsset::HeroCapsule => "hero_capsule.jpg",
+            SteamAsset::Hero => "library_hero.jpg",
+            SteamAsset::HeroBlur => "library_hero_blur.jpg",
+            SteamAsset::Logo => "logo.png",
+            SteamAsset::Logo2x => "logo_2x.png",
+            SteamAsset::PageBackground => "page_bg_generated_v6b.jpg",
+            SteamAsset::BroadcastLeftPanel => "broadcast_left_panel.jpg",
+            SteamAsset::BroadcastRightPanel => "broadcast_right_panel.jpg",
+        }
+    }
+
+    /// Get the url of the asset for a game on the CDN
+    ///
+    /// ### Examples
+    /// ```
+    /// use steamgriddb_api::steam_static::{SteamAsset, SteamCdn};
+    /// let url = SteamAsset::Hero.url(&SteamCdn::Cloudflare, "361420");
+    /// assert_eq!("https://cdn.cloudflare.steamstatic.com/steam/apps/361420/library_hero.jpg", url);
+    /// ```
+    pub fn url(&self, cdn: &SteamCdn, steam_id: &str) -> String {
+        format!("{}/{}/{}", cdn.base_url(), steam_id, self.file_name())
+    }
+
+    /// Get the SteamGridDB query for images that can replace this asset, if there are such images.
+    ///
+    /// Grids and heroes are limited to the dimensions with the same shape as the asset, and the page background can be any hero.
+    /// The small, main and hero store capsules have no SteamGridDB size of the same shape,
+    /// and broadcast panels have no SteamGridDB counterpart, so they have no query.
+    ///
+    /// ### Examples
+    /// ```
+    /// use steamgriddb_api::query_parameters::ImageKind;
+    /// use steamgriddb_api::steam_static::SteamAsset;
+    /// let query = SteamAsset::Capsule.query_type().unwrap();
+    /// assert_eq!(ImageKind::Grid, query.kind());
+    /// assert!(SteamAsset::BroadcastLeftPanel.query_type().is_none());
+    /// ```
+    pub fn query_type(&self) -> Option<QueryType<'static>> {
+        let grid = |dimentions| {
+            QueryType::Grid(Some(GridQueryParameters {
+                dimentions: Some(dimentions),
+                ..Default::default()
+            }))
+        };
+        match self {
+            SteamAsset::Header => Some(grid(WIDE_GRIDS)),
+            SteamAsset::Capsule | SteamAsset::LibraryCapsule => Some(grid(PORTRAIT_GRIDS)),
+            SteamAsset::Hero | SteamAsset::HeroBlur => {
+                Some(QueryType::Hero(Some(HeroQueryParameters {
+                    dimentions: Some(LIBRARY_HEROES),
+                    ..Default::default()
+                })))
+            }
+            SteamAsset::PageBackground => Some(QueryType::Hero(None)),
+            SteamAsset::Logo | SteamAsset::Logo2x => Some(QueryType::Logo(None)),
+            SteamAsset::SmallCapsule
+            | SteamAsset::MainCapsule
+            | SteamAsset::HeroCapsule
+            | SteamAsset::BroadcastLeftPanel
+            | SteamAsset::BroadcastRightPanel => None,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
 
 /// Urls for steam static files
@@ -7,22 +181,274 @@ pub struct SteamStaticUrls {
     /// Steam header url
     pub header: String,
     /// Steam capsule url
-    pub capsule: String,    
+    pub capsule: String,
     /// Steam hero url
     pub hero: String,
     /// Steam logo url
     pub logo: String,
+    /// Steam library capsule url, in normal resolution
+    #[serde(default)]
+    pub library_capsule: String,
+    /// Steam small capsule url
+    #[serde(default)]
+    pub small_capsule: String,
+    /// Steam main capsule url
+    #[serde(default)]
+    pub main_capsule: String,
+    /// Steam hero capsule url
+    #[serde(default)]
+    pub hero_capsule: String,
+    /// Steam blurred hero url
+    #[serde(default)]
+    pub hero_blur: String,
+    /// Steam logo url, in high resolution
+    #[serde(default)]
+    pub logo_2x: String,
+    /// Steam store page background url
+    #[serde(default)]
+    pub page_background: String,
+    /// Steam broadcast left panel url
+    #[serde(default)]
+    pub broadcast_left_panel: String,
+    /// Steam broadcast right panel url
+    #[serde(default)]
+    pub broadcast_right_panel: String,
 }
 
 impl SteamStaticUrls {
-
     /// Create a new instance of SteamStaticUrls
     pub fn new(steam_id: &str) -> Self {
-        Self{
-            header:format!("https://cdn.cloudflare.steamstatic.com/steam/apps/{steam_id}/header.jpg", steam_id=steam_id),
-            capsule:format!("https://cdn.cloudflare.steamstatic.com/steam/apps/{steam_id}/library_600x900_2x.jpg", steam_id=steam_id),
-            hero:format!("https://cdn.cloudflare.steamstatic.com/steam/apps/{steam_id}/library_hero.jpg", steam_id=steam_id),
-            logo:format!("https://cdn.cloudflare.steamstatic.com/steam/apps/{steam_id}/logo.png", steam_id=steam_id),
+        Self::with_cdn(steam_id, &SteamCdn::default())
+    }
+
+    /// Create a new instance of SteamStaticUrls, with urls on the given CDN
+    ///
+    /// ### Examples
+    /// ```
+    /// use steamgriddb_api::steam_static::{SteamCdn, SteamStaticUrls};
+    /// let cdn = SteamCdn::Custom("https://steam.example.com/apps/".to_string());
+    /// let urls = SteamStaticUrls::with_cdn("361420", &cdn);
+    /// assert_eq!("https://steam.example.com/apps/361420/logo.png", urls.logo);
+    /// ```
+    pub fn with_cdn(steam_id: &str, cdn: &SteamCdn) -> Self {
+        let url = |asset: SteamAsset| asset.url(cdn, steam_id);
+        Self {
+            header: url(SteamAsset::Header),
+            capsule: url(SteamAsset::Capsule),
+            hero: url(SteamAsset::Hero),
+            logo: url(SteamAsset::Logo),
+            library_capsule: url(SteamAsset::LibraryCapsule),
+            small_capsule: url(SteamAsset::SmallCapsule),
+            main_capsule: url(SteamAsset::MainCapsule),
+            hero_capsule: url(SteamAsset::HeroCapsule),
+            hero_blur: url(SteamAsset::HeroBlur),
+            logo_2x: url(SteamAsset::Logo2x),
+            page_background: url(SteamAsset::PageBackground),
+            broadcast_left_panel: url(SteamAsset::BroadcastLeftPanel),
+            broadcast_right_panel: url(SteamAsset::BroadcastRightPanel),
+        }
+    }
+
+    /// Get the url of an asset
+    pub fn get(&self, asset: SteamAsset) -> &str {
+        match asset {
+            SteamAsset::Header => &self.header,
+            SteamAsset::Capsule => &self.capsule,
+            SteamAsset::LibraryCapsule => &self.library_capsule,
+            SteamAsset::SmallCapsule => &self.small_capsule,
+            SteamAsset::MainCapsule => &self.main_capsule,
+            SteamAsset::HeroCapsule => &self.hero_capsule,
+            SteamAsset::Hero => &self.hero,
+            SteamAsset::HeroBlur => &self.hero_blur,
+            SteamAsset::Logo => &self.logo,
+            SteamAsset::Logo2x => &self.logo_2x,
+            SteamAsset::PageBackground => &self.page_background,
+            SteamAsset::BroadcastLeftPanel => &self.broadcast_left_panel,
+            SteamAsset::BroadcastRightPanel => &self.broadcast_right_panel,
         }
     }
+
+    /// Get the url of each asset
+    pub fn iter(&self) -> impl Iterator<Item = (SteamAsset, &str)> {
+        SteamAsset::ALL
+            .iter()
+            .map(move |asset| (*asset, self.get(*asset)))
+    }
+}
+
+/// An official Steam image, checked with a HEAD request
+#[derive(Serialize, Deserialize, Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct StaticAsset {
+    /// The url of the image.
+    ///
+    /// When the image was found at a fallback url, this is the fallback url.
+    pub url: String,
+    /// Does the image exist
+    pub exists: bool,
+    /// The size of the image in bytes, if the server reported it
+    pub content_length: Option<u64>,
+    /// The content type of the image, if the server reported it
+    pub content_type: Option<String>,
+}
+
+impl StaticAsset {
+    /// Create an asset from the response to a HEAD request for the url
+    pub(crate) fn from_response(url: String, response: &HttpResponse) -> Result<Self, Error> {
+        if response.status >= 500 {
+            return Err(Error::Status {
+                status: response.status,
+                body: body_snippet(&response.body),
+            });
+        }
+        let exists = (200..300).contains(&response.status);
+        Ok(Self {
+            url,
+            exists,
+            content_length: response
+                .header("Content-Length")
+                .filter(|_| exists)
+                .and_then(|length| length.trim().parse().ok()),
+            content_type: response
+                .header("Content-Type")
+                .filter(|_| exists)
+                .map(|content_type| content_type.to_string()),
+        })
+    }
+}
+
+/// The official Steam images of a game, with which of them exist
+#[derive(Serialize, Deserialize, Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct CheckedSteamStaticUrls {
+    /// Steam header
+    pub header: StaticAsset,
+    /// Steam capsule
+    pub capsule: StaticAsset,
+    /// Steam hero
+    pub hero: StaticAsset,
+    /// Steam logo
+    pub logo: StaticAsset,
+}
+
+/// Get the urls to try for an image, starting with the url itself.
+///
+/// High resolution images, like `library_600x900_2x.jpg`, fall back to their normal resolution, like `library_600x900.jpg`.
+pub(crate) fn fallback_urls(url: &str) -> Vec<String> {
+    let mut urls = vec![url.to_string()];
+    if url.contains("_2x.") {
+        urls.push(url.replacen("_2x.", ".", 1));
+    }
+    urls
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn response(status: u16, headers: &[(&str, &str)]) -> HttpResponse {
+        HttpResponse {
+            status,
+            headers: headers
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+            body: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn fallback_urls_test() {
+        let urls = SteamStaticUrls::new("361420");
+        assert_eq!(
+            vec![
+                "https://cdn.cloudflare.steamstatic.com/steam/apps/361420/library_600x900_2x.jpg",
+                "https://cdn.cloudflare.steamstatic.com/steam/apps/361420/library_600x900.jpg"
+            ],
+            fallback_urls(&urls.capsule)
+        );
+        assert_eq!(vec![urls.hero.clone()], fallback_urls(&urls.hero));
+    }
+
+    #[test]
+    fn static_asset_from_response_test() {
+        let found = response(
+            200,
+            &[("Content-Length", "84564"), ("Content-Type", "image/jpeg")],
+        );
+        let asset = StaticAsset::from_response("url".to_string(), &found).unwrap();
+        assert!(asset.exists);
+        assert_eq!(Some(84564), asset.content_length);
+        assert_eq!(Some("image/jpeg".to_string()), asset.content_type);
+
+        let missing = response(
+            404,
+            &[("Content-Length", "0"), ("Content-Type", "text/html")],
+        );
+        let asset = StaticAsset::from_response("url".to_string(), &missing).unwrap();
+        assert!(!asset.exists);
+        assert_eq!(None, asset.content_length);
+        assert_eq!(None, asset.content_type);
+
+        let error = StaticAsset::from_response("url".to_string(), &response(503, &[]));
+        assert_eq!(Some(503), error.unwrap_err().status());
+    }
+
+    #[test]
+    fn steam_static_urls_test() {
+        let urls = SteamStaticUrls::new("361420");
+        let base = "https://cdn.cloudflare.steamstatic.com/steam/apps/361420";
+        assert_eq!(format!("{}/header.jpg", base), urls.header);
+        assert_eq!(format!("{}/library_600x900_2x.jpg", base), urls.capsule);
+        assert_eq!(format!("{}/library_hero.jpg", base), urls.hero);
+        assert_eq!(format!("{}/logo.png", base), urls.logo);
+        assert_eq!(
+            format!("{}/library_600x900.jpg", base),
+            urls.library_capsule
+        );
+        assert_eq!(
+            format!("{}/page_bg_generated_v6b.jpg", base),
+            urls.page_background
+        );
+        assert_eq!(13, urls.iter().count());
+        assert!(urls
+            .iter()
+            .all(|(asset, url)| url == asset.url(&SteamCdn::Cloudflare, "361420")));
+    }
+
+    #[test]
+    fn steam_static_urls_with_cdn_test() {
+        let urls = SteamStaticUrls::with_cdn("361420", &SteamCdn::LegacyAkamai);
+        assert_eq!(
+            "https://steamcdn-a.akamaihd.net/steam/apps/361420/library_hero_blur.jpg",
+            urls.hero_blur
+        );
+    }
+
+    #[test]
+    fn deserialize_old_steam_static_urls_test() {
+        let json = r#"{"header":"h","capsule":"c","hero":"hr","logo":"l"}"#;
+        let urls: SteamStaticUrls = serde_json::from_str(json).unwrap();
+        assert_eq!("hr", urls.hero);
+        assert_eq!("", urls.hero_blur);
+    }
+
+    #[test]
+    fn query_type_test() {
+        use crate::query_parameters::{ImageKind, ToQuerys};
+        let capsule = SteamAsset::Capsule.query_type().unwrap();
+        assert_eq!(ImageKind::Grid, capsule.kind());
+        assert_eq!("dimensions=600x900,342x482,660x930", capsule.to_querys());
+        let header = SteamAsset::Header.query_type().unwrap();
+        assert_eq!("dimensions=460x215,920x430", header.to_querys());
+        let hero = SteamAsset::HeroBlur.query_type().unwrap();
+        assert_eq!(ImageKind::Hero, hero.kind());
+        assert_eq!(
+            ImageKind::Logo,
+            SteamAsset::Logo2x.query_type().unwrap().kind()
+        );
+        assert!(SteamAsset::BroadcastRightPanel.query_type().is_none());
+        assert!(SteamAsset::SmallCapsule.query_type().is_none());
+        assert!(SteamAsset::MainCapsule.query_type().is_none());
+        assert!(SteamAsset::HeroCapsule.query_type().is_none());
+    }
 }
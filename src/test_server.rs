@@ -0,0 +1,237 @@
+//! A minimal HTTP server and an in-memory transport, for testing the clients against canned responses.
+
+use std::{
+    collections::HashMap,
+    io::{BufRead, BufReader, Write},
+    net::TcpListener,
+    sync::{Arc, Mutex},
+    thread,
+    time::Duration,
+};
+
+use crate::{
+    error::Error,
+    transport::{
+        BlockingTransport, HttpRequest, HttpResponse, SleepFuture, Transport, TransportFuture,
+    },
+};
+
+/// A request received by the stub server
+#[derive(Debug, Clone)]
+pub(crate) struct RecordedRequest {
+    pub method: String,
+    pub path: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl RecordedRequest {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// A response the stub server answers with
+pub(crate) struct StubResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+    /// Close the connection without answering
+    pub disconnect: bool,
+}
+
+impl StubResponse {
+    pub fn json(status: u16, body: &str) -> Self {
+        Self {
+            status,
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: body.as_bytes().to_vec(),
+            disconnect: false,
+        }
+    }
+
+    /// A response that closes the connection after reading the request
+    pub fn disconnect() -> Self {
+        Self {
+            status: 0,
+            headers: Vec::new(),
+            body: Vec::new(),
+            disconnect: true,
+        }
+    }
+
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+}
+
+/// A server that answers each connection with the next canned response
+pub(crate) struct StubServer {
+    pub url: String,
+    requests: Arc<Mutex<Vec<RecordedRequest>>>,
+}
+
+impl StubServer {
+    /// Starts a server that answers the given responses in order, one per connection.
+    pub fn start(responses: Vec<StubResponse>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let recorded = requests.clone();
+        thread::spawn(move || {
+            for response in responses {
+                let (stream, _) = match listener.accept() {
+                    Ok(connection) => connection,
+                    Err(_) => return,
+                };
+                let mut reader = BufReader::new(stream);
+                let request = match read_request(&mut reader) {
+                    Some(request) => request,
+                    None => continue,
+                };
+                recorded.lock().unwrap().push(request);
+                if response.disconnect {
+                    continue;
+                }
+                let mut stream = reader.into_inner();
+                let _ = write_response(&mut stream, &response);
+            }
+        });
+        Self { url, requests }
+    }
+
+    /// The requests received so far
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+fn read_request<R: BufRead>(reader: &mut R) -> Option<RecordedRequest> {
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).ok()?;
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next()?.to_string();
+    let path = parts.next()?.to_string();
+    let mut headers = Vec::new();
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line).ok()?;
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            headers.push((name.trim().to_string(), value.trim().to_string()));
+        }
+    }
+    let length = headers
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case("content-length"))
+        .and_then(|(_, value)| value.parse::<usize>().ok())
+        .unwrap_or(0);
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).ok()?;
+    Some(RecordedRequest {
+        method,
+        path,
+        headers,
+        body,
+    })
+}
+
+fn write_response<W: Write>(stream: &mut W, response: &StubResponse) -> std::io::Result<()> {
+    write!(stream, "HTTP/1.1 {} Stub\r\n", response.status)?;
+    for (name, value) in response.headers.iter() {
+        write!(stream, "{}: {}\r\n", name, value)?;
+    }
+    write!(
+        stream,
+        "Content-Length: {}\r\nConnection: close\r\n\r\n",
+        response.body.len()
+    )?;
+    stream.write_all(&response.body)?;
+    stream.flush()
+}
+
+/// A transport that answers requests from a map of urls to canned responses, without any network.
+///
+/// Urls without a canned response are answered with a 404.
+#[derive(Default)]
+pub(crate) struct FakeTransport {
+    responses: HashMap<String, HttpResponse>,
+    requests: Arc<Mutex<Vec<HttpRequest>>>,
+}
+
+impl FakeTransport {
+    pub fn with_json(mut self, url: &str, status: u16, body: &str) -> Self {
+        self.responses.insert(
+            url.to_string(),
+            HttpResponse {
+                status,
+                headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+                body: body.as_bytes().to_vec(),
+            },
+        );
+        self
+    }
+
+    pub fn with_response(mut self, url: &str, status: u16, headers: &[(&str, &str)]) -> Self {
+        self.responses.insert(
+            url.to_string(),
+            HttpResponse {
+                status,
+                headers: headers
+                    .iter()
+                    .map(|(name, value)| (name.to_string(), value.to_string()))
+                    .collect(),
+                body: Vec::new(),
+            },
+        );
+        self
+    }
+
+    pub fn with_fixture(self, url: &str, path: &str) -> Self {
+        let json = std::fs::read_to_string(path).unwrap();
+        self.with_json(url, 200, &json)
+    }
+
+    /// A handle to the requests sent through this transport
+    pub fn requests(&self) -> Arc<Mutex<Vec<HttpRequest>>> {
+        self.requests.clone()
+    }
+
+    fn respond(&self, request: HttpRequest) -> Result<HttpResponse, Error> {
+        let response = self
+            .responses
+            .get(&request.url)
+            .cloned()
+            .unwrap_or_else(|| HttpResponse {
+                status: 404,
+                headers: Vec::new(),
+                body: br#"{"success":false,"errors":["Not found"]}"#.to_vec(),
+            });
+        self.requests.lock().unwrap().push(request);
+        Ok(response)
+    }
+}
+
+impl Transport for FakeTransport {
+    fn send(&self, request: HttpRequest) -> TransportFuture<'_> {
+        let response = self.respond(request);
+        Box::pin(async move { response })
+    }
+
+    fn sleep(&self, duration: Duration) -> SleepFuture<'_> {
+        Box::pin(tokio::time::sleep(duration))
+    }
+}
+
+impl BlockingTransport for FakeTransport {
+    fn send(&self, request: HttpRequest) -> Result<HttpResponse, Error> {
+        self.respond(request)
+    }
+}
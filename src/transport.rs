@@ -0,0 +1,421 @@
+//! The HTTP layer used by the clients.
+//!
+//! The clients build an [`HttpRequest`] for each api call, and hand it to a transport that sends it.
+//! By default the transports use [reqwest](https://crates.io/crates/reqwest), which requires the `reqwest` feature.
+//! Any other HTTP client can be used by implementing [`Transport`] or [`BlockingTransport`],
+//! and creating the client with `with_transport`.
+
+use std::{future::Future, io::Read, pin::Pin, time::Duration};
+
+use crate::error::Error;
+
+/// The HTTP methods used by the clients
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum Method {
+    /// GET
+    Get,
+    /// HEAD
+    Head,
+    /// POST
+    Post,
+    /// DELETE
+    Delete,
+}
+
+/// A HTTP request to send
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpRequest {
+    /// The method of the request
+    pub method: Method,
+    /// The full url of the request
+    pub url: String,
+    /// The headers of the request
+    pub headers: Vec<(String, String)>,
+    /// The body of the request
+    pub body: Option<Vec<u8>>,
+}
+
+impl HttpRequest {
+    /// Create a new request without any headers or body
+    pub fn new<S>(method: Method, url: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            method,
+            url: url.into(),
+            headers: Vec::new(),
+            body: None,
+        }
+    }
+
+    /// Add a header to the request
+    pub fn with_header<N, V>(mut self, name: N, value: V) -> Self
+    where
+        N: Into<String>,
+        V: Into<String>,
+    {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Set the body of the request
+    pub fn with_body(mut self, body: Vec<u8>) -> Self {
+        self.body = Some(body);
+        self
+    }
+}
+
+/// A HTTP response, as returned by a transport
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpResponse {
+    /// The status code of the response
+    pub status: u16,
+    /// The headers of the response
+    pub headers: Vec<(String, String)>,
+    /// The full body of the response
+    pub body: Vec<u8>,
+}
+
+impl HttpResponse {
+    /// Get the value of a header, ignoring the case of the name
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// A HTTP response whose body is read while it arrives, as returned by `send_streaming`
+pub struct StreamingResponse<B> {
+    /// The status code of the response
+    pub status: u16,
+    /// The headers of the response
+    pub headers: Vec<(String, String)>,
+    /// The body of the response, that has not been read yet
+    pub body: B,
+}
+
+impl<B> StreamingResponse<B> {
+    /// Get the value of a header, ignoring the case of the name
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+impl From<HttpResponse> for StreamingResponse<Box<dyn BodyStream>> {
+    /// A streaming response with a body that is already read, given as a single chunk
+    fn from(response: HttpResponse) -> Self {
+        Self {
+            status: response.status,
+            headers: response.headers,
+            body: Box::new(Some(response.body)),
+        }
+    }
+}
+
+impl From<HttpResponse> for StreamingResponse<Box<dyn Read + Send>> {
+    /// A streaming response with a body that is already read
+    fn from(response: HttpResponse) -> Self {
+        Self {
+            status: response.status,
+            headers: response.headers,
+            body: Box::new(std::io::Cursor::new(response.body)),
+        }
+    }
+}
+
+/// The status and headers of a response, used to decide if a request is retried
+pub(crate) trait ResponseHead {
+    fn status(&self) -> u16;
+    fn header(&self, name: &str) -> Option<&str>;
+}
+
+impl ResponseHead for HttpResponse {
+    fn status(&self) -> u16 {
+        self.status
+    }
+
+    fn header(&self, name: &str) -> Option<&str> {
+        HttpResponse::header(self, name)
+    }
+}
+
+impl<B> ResponseHead for StreamingResponse<B> {
+    fn status(&self) -> u16 {
+        self.status
+    }
+
+    fn header(&self, name: &str) -> Option<&str> {
+        StreamingResponse::header(self, name)
+    }
+}
+
+/// The future returned by [`BodyStream::chunk`]
+pub type ChunkFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<Option<Vec<u8>>, Error>> + Send + 'a>>;
+
+/// The body of a response for the async [`crate::client::Client`], read in chunks as they arrive
+pub trait BodyStream: Send {
+    /// Get the next chunk of the body, or None when the whole body has been read
+    fn chunk(&mut self) -> ChunkFuture<'_>;
+}
+
+impl BodyStream for Option<Vec<u8>> {
+    /// The whole body as one chunk
+    fn chunk(&mut self) -> ChunkFuture<'_> {
+        let chunk = self.take();
+        Box::pin(async move { Ok(chunk) })
+    }
+}
+
+/// The future returned by [`Transport::send_streaming`]
+pub type StreamingFuture<'a> = Pin<
+    Box<dyn Future<Output = Result<StreamingResponse<Box<dyn BodyStream>>, Error>> + Send + 'a>,
+>;
+
+/// The future returned by [`Transport::send`]
+pub type TransportFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<HttpResponse, Error>> + Send + 'a>>;
+
+/// The future returned by [`Transport::sleep`]
+pub type SleepFuture<'a> = Pin<Box<dyn Future<Output = ()> + Send + 'a>>;
+
+/// Sends HTTP requests for the async [`crate::client::Client`].
+///
+/// ### Examples
+/// A transport that answers every request with the same body, and never waits, for testing:
+/// ```
+/// use std::time::Duration;
+/// use steamgriddb_api::transport::*;
+///
+/// struct FakeTransport(&'static str);
+///
+/// impl Transport for FakeTransport {
+///     fn send(&self, _request: HttpRequest) -> TransportFuture<'_> {
+///         let body = self.0.as_bytes().to_vec();
+///         Box::pin(async move {
+///             Ok(HttpResponse {
+///                 status: 200,
+///                 headers: Vec::new(),
+///                 body,
+///             })
+///         })
+///     }
+///
+///     fn sleep(&self, _duration: Duration) -> SleepFuture<'_> {
+///         Box::pin(async {})
+///     }
+/// }
+/// ```
+pub trait Transport: Send + Sync {
+    /// Send the request, and return the response
+    ///
+    /// Responses with error statuses should be returned as responses, not as errors.
+    fn send(&self, request: HttpRequest) -> TransportFuture<'_>;
+
+    /// Send the request, and return the response before its body is read.
+    ///
+    /// Used for downloads, so their size can be limited and their progress reported while they arrive.
+    /// The default implementation reads the whole body with [`Transport::send`].
+    fn send_streaming(&self, request: HttpRequest) -> StreamingFuture<'_> {
+        let response = self.send(request);
+        Box::pin(async move { Ok(response.await?.into()) })
+    }
+
+    /// Wait for the given duration, used between retries and when rate limited.
+    ///
+    /// Implementations should use the timer of the async runtime their requests are sent on.
+    fn sleep(&self, duration: Duration) -> SleepFuture<'_>;
+}
+
+/// Sends HTTP requests for the blocking client.
+pub trait BlockingTransport: Send + Sync {
+    /// Send the request, and return the response
+    ///
+    /// Responses with error statuses should be returned as responses, not as errors.
+    fn send(&self, request: HttpRequest) -> Result<HttpResponse, Error>;
+
+    /// Send the request, and return the response before its body is read.
+    ///
+    /// Used for downloads, so their size can be limited and their progress reported while they arrive.
+    /// The default implementation reads the whole body with [`BlockingTransport::send`].
+    fn send_streaming(
+        &self,
+        request: HttpRequest,
+    ) -> Result<StreamingResponse<Box<dyn Read + Send>>, Error> {
+        Ok(self.send(request)?.into())
+    }
+
+    /// Wait for the given duration, used between retries.
+    fn sleep(&self, duration: Duration) {
+        std::thread::sleep(duration)
+    }
+}
+
+/// A [`Transport`] using the async [reqwest](https://crates.io/crates/reqwest) client.
+#[cfg(feature = "reqwest")]
+#[derive(Debug, Clone, Default)]
+pub struct ReqwestTransport {
+    client: reqwest::Client,
+}
+
+#[cfg(feature = "reqwest")]
+impl ReqwestTransport {
+    /// Create a transport with a default reqwest client
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a transport using the given reqwest client
+    pub fn with_client(client: reqwest::Client) -> Self {
+        Self { client }
+    }
+
+    fn builder(&self, request: HttpRequest) -> reqwest::RequestBuilder {
+        let mut builder = self
+            .client
+            .request(to_reqwest_method(request.method), request.url.as_str());
+        for (name, value) in request.headers.iter() {
+            builder = builder.header(name.as_str(), value.as_str());
+        }
+        if let Some(body) = request.body {
+            builder = builder.body(body);
+        }
+        builder
+    }
+}
+
+#[cfg(feature = "reqwest")]
+impl Transport for ReqwestTransport {
+    fn send(&self, request: HttpRequest) -> TransportFuture<'_> {
+        Box::pin(async move {
+            let response = self.builder(request).send().await?;
+            let status = response.status().as_u16();
+            let headers = from_reqwest_headers(response.headers());
+            let body = response.bytes().await?.to_vec();
+            Ok(HttpResponse {
+                status,
+                headers,
+                body,
+            })
+        })
+    }
+
+    fn send_streaming(&self, request: HttpRequest) -> StreamingFuture<'_> {
+        Box::pin(async move {
+            let response = self.builder(request).send().await?;
+            let status = response.status().as_u16();
+            let headers = from_reqwest_headers(response.headers());
+            let body: Box<dyn BodyStream> = Box::new(response);
+            Ok(StreamingResponse {
+                status,
+                headers,
+                body,
+            })
+        })
+    }
+
+    /// Wait using the timer of tokio, the runtime reqwest runs on
+    fn sleep(&self, duration: Duration) -> SleepFuture<'_> {
+        Box::pin(tokio::time::sleep(duration))
+    }
+}
+
+#[cfg(feature = "reqwest")]
+impl BodyStream for reqwest::Response {
+    fn chunk(&mut self) -> ChunkFuture<'_> {
+        Box::pin(async move {
+            Ok(reqwest::Response::chunk(self)
+                .await?
+                .map(|chunk| chunk.to_vec()))
+        })
+    }
+}
+
+/// A [`BlockingTransport`] using the blocking [reqwest](https://crates.io/crates/reqwest) client.
+#[cfg(all(feature = "reqwest", feature = "blocking"))]
+#[derive(Debug, Clone, Default)]
+pub struct ReqwestBlockingTransport {
+    client: reqwest::blocking::Client,
+}
+
+#[cfg(all(feature = "reqwest", feature = "blocking"))]
+impl ReqwestBlockingTransport {
+    /// Create a transport with a default blocking reqwest client
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a transport using the given blocking reqwest client
+    pub fn with_client(client: reqwest::blocking::Client) -> Self {
+        Self { client }
+    }
+
+    fn builder(&self, request: HttpRequest) -> reqwest::blocking::RequestBuilder {
+        let mut builder = self
+            .client
+            .request(to_reqwest_method(request.method), request.url.as_str());
+        for (name, value) in request.headers.iter() {
+            builder = builder.header(name.as_str(), value.as_str());
+        }
+        if let Some(body) = request.body {
+            builder = builder.body(body);
+        }
+        builder
+    }
+}
+
+#[cfg(all(feature = "reqwest", feature = "blocking"))]
+impl BlockingTransport for ReqwestBlockingTransport {
+    fn send(&self, request: HttpRequest) -> Result<HttpResponse, Error> {
+        let response = self.builder(request).send()?;
+        let status = response.status().as_u16();
+        let headers = from_reqwest_headers(response.headers());
+        let body = response.bytes()?.to_vec();
+        Ok(HttpResponse {
+            status,
+            headers,
+            body,
+        })
+    }
+
+    fn send_streaming(
+        &self,
+        request: HttpRequest,
+    ) -> Result<StreamingResponse<Box<dyn Read + Send>>, Error> {
+        let response = self.builder(request).send()?;
+        Ok(StreamingResponse {
+            status: response.status().as_u16(),
+            headers: from_reqwest_headers(response.headers()),
+            body: Box::new(response),
+        })
+    }
+}
+
+#[cfg(feature = "reqwest")]
+fn to_reqwest_method(method: Method) -> reqwest::Method {
+    match method {
+        Method::Get => reqwest::Method::GET,
+        Method::Head => reqwest::Method::HEAD,
+        Method::Post => reqwest::Method::POST,
+        Method::Delete => reqwest::Method::DELETE,
+    }
+}
+
+#[cfg(feature = "reqwest")]
+fn from_reqwest_headers(headers: &reqwest::header::HeaderMap) -> Vec<(String, String)> {
+    headers
+        .iter()
+        .filter_map(|(name, value)| {
+            value
+                .to_str()
+                .ok()
+                .map(|value| (name.as_str().to_string(), value.to_string()))
+        })
+        .collect()
+}
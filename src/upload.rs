@@ -0,0 +1,177 @@
+#[cfg(any(feature = "async", feature = "blocking"))]
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::query_parameters::{ImageKind, Style, StyleIcon, StyleLogo};
+
+/// The kind and style of an image to upload
+pub enum UploadType {
+    /// Upload a grid image
+    Grid(Style),
+    /// Upload a hero image
+    Hero(Style),
+    /// Upload a logo image
+    Logo(StyleLogo),
+    /// Upload an icon image
+    Icon(StyleIcon),
+}
+
+impl UploadType {
+    /// The kind of image that is uploaded
+    pub fn kind(&self) -> ImageKind {
+        match self {
+            UploadType::Grid(_) => ImageKind::Grid,
+            UploadType::Hero(_) => ImageKind::Hero,
+            UploadType::Logo(_) => ImageKind::Logo,
+            UploadType::Icon(_) => ImageKind::Icon,
+        }
+    }
+
+    fn style(&self) -> String {
+        use crate::query_parameters::ToQueryValue;
+        match self {
+            UploadType::Grid(style) | UploadType::Hero(style) => style.to_query_value().value,
+            UploadType::Logo(style) => style.to_query_value().value,
+            UploadType::Icon(style) => style.to_query_value().value,
+        }
+    }
+
+    /// The name of the form field that contains the image file
+    fn file_field(&self) -> &'static str {
+        match self {
+            UploadType::Grid(_) => "grid",
+            UploadType::Hero(_) => "hero",
+            UploadType::Logo(_) => "logo",
+            UploadType::Icon(_) => "icon",
+        }
+    }
+}
+
+/// Get the URL to upload an image to.
+///
+/// ### Examples
+/// ```
+/// use steamgriddb_api::upload::*;
+/// use steamgriddb_api::query_parameters::Style;
+/// let url = get_upload_url("https://www.steamgriddb.com/api/v2", &UploadType::Grid(Style::Alternate));
+/// assert_eq!("https://www.steamgriddb.com/api/v2/grids", url);
+/// ```
+pub fn get_upload_url(base_url: &str, upload: &UploadType) -> String {
+    format!("{}/{}", base_url, upload.kind().path())
+}
+
+/// A multipart/form-data body for uploading an image.
+pub(crate) struct UploadForm {
+    /// The value of the Content-Type header, including the boundary
+    pub content_type: String,
+    /// The encoded body
+    pub body: Vec<u8>,
+}
+
+impl UploadForm {
+    #[cfg(any(feature = "async", feature = "blocking"))]
+    pub(crate) fn new(game_id: usize, upload: &UploadType, file_name: &str, data: &[u8]) -> Self {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or_default();
+        let boundary = format!("steamgriddb-api-{:x}", nanos);
+        Self::with_boundary(&boundary, game_id, upload, file_name, data)
+    }
+
+    fn with_boundary(
+        boundary: &str,
+        game_id: usize,
+        upload: &UploadType,
+        file_name: &str,
+        data: &[u8],
+    ) -> Self {
+        let mut body = Vec::with_capacity(data.len() + 512);
+        let fields = [("game_id", game_id.to_string()), ("style", upload.style())];
+        for (name, value) in fields.iter() {
+            body.extend_from_slice(
+                format!(
+                    "--{}\r\nContent-Disposition: form-data; name=\"{}\"\r\n\r\n{}\r\n",
+                    boundary, name, value
+                )
+                .as_bytes(),
+            );
+        }
+        body.extend_from_slice(
+            format!(
+                "--{}\r\nContent-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\nContent-Type: {}\r\n\r\n",
+                boundary,
+                upload.file_field(),
+                file_name.replace(&['"', '\r', '\n'][..], ""),
+                guess_mime(data)
+            )
+            .as_bytes(),
+        );
+        body.extend_from_slice(data);
+        body.extend_from_slice(format!("\r\n--{}--\r\n", boundary).as_bytes());
+        Self {
+            content_type: format!("multipart/form-data; boundary={}", boundary),
+            body,
+        }
+    }
+}
+
+/// Guess the mime type of an image from its first bytes
+pub(crate) fn guess_mime(data: &[u8]) -> &'static str {
+    if data.starts_with(b"\x89PNG") {
+        "image/png"
+    } else if data.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        "image/jpeg"
+    } else if data.len() >= 12 && &data[0..4] == b"RIFF" && &data[8..12] == b"WEBP" {
+        "image/webp"
+    } else if data.starts_with(&[0x00, 0x00, 0x01, 0x00]) {
+        "image/vnd.microsoft.icon"
+    } else {
+        "application/octet-stream"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn get_upload_url_test() {
+        let base_url = "https://www.steamgriddb.com/api/v2";
+        let url = get_upload_url(base_url, &UploadType::Logo(StyleLogo::White));
+        assert_eq!("https://www.steamgriddb.com/api/v2/logos", url);
+    }
+
+    #[test]
+    fn upload_form_test() {
+        let data = b"\x89PNG data";
+        let form = UploadForm::with_boundary(
+            "boundary",
+            13136,
+            &UploadType::Hero(Style::Blurred),
+            "hero.png",
+            data,
+        );
+        assert_eq!("multipart/form-data; boundary=boundary", form.content_type);
+        let mut expected = b"--boundary\r\nContent-Disposition: form-data; name=\"game_id\"\r\n\r\n13136\r\n--boundary\r\nContent-Disposition: form-data; name=\"style\"\r\n\r\nblurred\r\n--boundary\r\nContent-Disposition: form-data; name=\"hero\"; filename=\"hero.png\"\r\nContent-Type: image/png\r\n\r\n".to_vec();
+        expected.extend_from_slice(data);
+        expected.extend_from_slice(b"\r\n--boundary--\r\n");
+        assert_eq!(expected, form.body);
+    }
+
+    #[test]
+    fn upload_form_file_name_test() {
+        let form = UploadForm::with_boundary(
+            "boundary",
+            13136,
+            &UploadType::Icon(StyleIcon::Custom),
+            "icon\"\r\nContent-Type: text/html.png",
+            b"\x00\x00\x01\x00",
+        );
+        let body = String::from_utf8_lossy(&form.body);
+        assert!(body.contains("name=\"style\"\r\n\r\ncustom\r\n"));
+        assert!(body.contains(
+            "name=\"icon\"; filename=\"iconContent-Type: text/html.png\"\r\nContent-Type: image/vnd.microsoft.icon\r\n"
+        ));
+    }
+}